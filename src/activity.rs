@@ -0,0 +1,86 @@
+//! Recent trades by tracked traders, for inspecting them without running the bot.
+
+use crate::display;
+use crate::models::Trade;
+use crate::output::Report;
+
+/// Merge per-trader trade lists, newest first, keeping at most `limit`.
+pub fn merge_recent(lists: Vec<Vec<Trade>>, limit: usize) -> Vec<Trade> {
+    let mut trades: Vec<Trade> = lists.into_iter().flatten().collect();
+    trades.sort_by_key(|t| std::cmp::Reverse(t.timestamp));
+    trades.truncate(limit);
+    trades
+}
+
+/// Trades as a report, one row each in the order given.
+pub fn trades_report(trades: &[Trade]) -> Report {
+    let mut report = Report::new("Recent Trades");
+    let rows = trades
+        .iter()
+        .map(|t| {
+            let market = if t.market_title.is_empty() { &t.market_id } else { &t.market_title };
+            vec![
+                t.timestamp.format("%Y-%m-%d %H:%M").to_string(),
+                t.trader_address.clone(),
+                market.clone(),
+                format!("{:?}", t.side),
+                t.outcome.clone(),
+                format!("{:.2}", t.size),
+                display::price(t.price),
+            ]
+        })
+        .collect();
+    report.table("Trades", &["time", "trader", "market", "side", "outcome", "size", "price"], rows);
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    use crate::models::TradeSide;
+    use crate::output::Format;
+
+    fn fixture(trader: &str, title: &str, side: TradeSide, minutes: i64) -> Trade {
+        Trade {
+            id: format!("0xtx_{}", minutes),
+            trader_address: trader.to_string(),
+            market_id: "0xmarket".to_string(),
+            market_title: title.to_string(),
+            side,
+            outcome: "Yes".to_string(),
+            size: dec!(120.5),
+            price: dec!(0.42),
+            amount_usdc: dec!(50.61),
+            timestamp: Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap() + Duration::minutes(minutes),
+            transaction_hash: "0xtx".to_string(),
+            is_taker: true,
+            fee_usdc: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_trades_report_lists_merged_trades_newest_first() {
+        let trades = merge_recent(
+            vec![
+                vec![fixture("0xaaa", "Will it rain?", TradeSide::Buy, 0), fixture("0xaaa", "", TradeSide::Sell, 30)],
+                vec![fixture("0xbbb", "Will it snow?", TradeSide::Buy, 15)],
+            ],
+            2,
+        );
+
+        let text = trades_report(&trades).render(Format::Text).unwrap();
+        let rows: Vec<&str> = text.lines().filter(|l| l.starts_with("  ")).collect();
+        assert_eq!(
+            rows,
+            [
+                format!("  2026-03-01 12:30 | 0xaaa | 0xmarket | Sell | Yes | 120.50 | {}", display::price(dec!(0.42))),
+                format!("  2026-03-01 12:15 | 0xbbb | Will it snow? | Buy | Yes | 120.50 | {}", display::price(dec!(0.42))),
+            ]
+        );
+        assert!(text.contains("--- Trades (2) ---"));
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! The CLOB is Polymarket's off-chain order matching engine that settles on Polygon.
 //! This client handles:
-//! - API authentication (L1 headers for read, L2 for write operations)
+//! - API authentication (L2 HMAC headers on every authenticated request)
 //! - Order signing using EIP-712 typed data
 //! - Order submission and status tracking
 //! - Market and limit order placement
@@ -11,18 +11,36 @@ use alloy_primitives::{Address, Signature, U256};
 use alloy_signer::Signer;
 use alloy_signer_local::PrivateKeySigner;
 use anyhow::{anyhow, Context, Result};
-use chrono::Utc;
+use base64::engine::general_purpose::URL_SAFE;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
 use reqwest::Client;
 use rust_decimal::Decimal;
-use rust_decimal::prelude::ToPrimitive;
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 use std::str::FromStr;
-use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+use super::MARKET_WS_URL;
+use super::signer;
 
 /// CLOB API base URLs
 pub const CLOB_URL: &str = "https://clob.polymarket.com";
 pub const GAMMA_URL: &str = "https://gamma-api.polymarket.com";
 
+/// How long a signed order stays valid after it is created
+pub const ORDER_TTL: chrono::Duration = chrono::Duration::hours(1);
+
+/// Share granularity accepted by the CLOB for order sizes
+pub const SHARE_LOT_SIZE: Decimal = Decimal::from_parts(1, 0, 0, false, 2);
+
+/// Decimals of the USDC collateral token.
+const USDC_DECIMALS: u32 = 6;
+
+/// Decimals of conditional-token outcome shares (the CTF mirrors its collateral).
+const SHARE_DECIMALS: u32 = 6;
+
 /// Polymarket CTF Exchange contract on Polygon
 pub const CTF_EXCHANGE: &str = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E";
 /// Neg Risk CTF Exchange for multi-outcome markets
@@ -33,9 +51,17 @@ pub struct ClobClient {
     http: Client,
     signer: PrivateKeySigner,
     api_key: String,
-    api_secret: String,
+    /// Decoded API secret, the HMAC key for L2 signatures
+    api_secret: Vec<u8>,
     api_passphrase: String,
     chain_id: u64,
+    clob_url: String,
+    gamma_url: String,
+    /// Market channel websocket, when it isn't derived from `clob_url`
+    ws_url: Option<String>,
+    confirm_grace: std::time::Duration,
+    /// Proxy wallet that holds funds and positions, when it isn't the signer
+    funder: Option<Address>,
 }
 
 /// Order side in the CLOB
@@ -125,6 +151,16 @@ pub struct OrderPayload {
     pub order_type: OrderType,
 }
 
+/// Signed order with its CLOB order id, ready to be (re)submitted.
+///
+/// Resubmitting the same prepared order yields the same order id, so a retry
+/// can't create a second order.
+#[derive(Debug, Clone)]
+pub struct PreparedOrder {
+    pub order_id: String,
+    payload: OrderPayload,
+}
+
 /// Response from order placement
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -137,21 +173,26 @@ pub struct OrderResponse {
     pub transaction_hash: Option<String>,
 }
 
+/// Collateral balance response from /balance-allowance
+#[derive(Debug, Clone, Deserialize)]
+pub struct BalanceAllowance {
+    /// Balance in USDC base units (6 decimals)
+    pub balance: String,
+    /// USDC the exchange is approved to spend, in base units, when reported
+    #[serde(default)]
+    pub allowance: Option<String>,
+}
+
 /// Order status response
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderStatus {
     pub id: String,
     pub status: String,
-    pub maker: String,
     pub side: String,
     pub token_id: String,
     pub original_size: String,
     pub size_matched: String,
-    pub price: String,
-    pub created_at: Option<String>,
-    pub expiration: Option<String>,
-    pub outcome: Option<String>,
     pub associate_trades: Option<Vec<AssociateTrade>>,
 }
 
@@ -159,29 +200,41 @@ pub struct OrderStatus {
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AssociateTrade {
-    pub id: String,
-    pub taker_order_id: String,
-    pub maker_order_id: String,
     pub price: String,
     pub size: String,
-    pub side: String,
-    pub transaction_hash: Option<String>,
-    pub created_at: String,
 }
 
 /// Market information from Gamma API
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MarketInfo {
-    pub condition_id: String,
-    pub question_id: String,
     pub tokens: Vec<TokenInfo>,
     #[serde(default)]
     pub minimum_order_size: String,
+    /// When the market actually closed, once it has; resolution can come well before
+    /// or after the scheduled end
+    #[serde(default, deserialize_with = "deserialize_gamma_time")]
+    pub closed_time: Option<DateTime<Utc>>,
+    /// Trading volume over the last 24 hours in USDC, when reported
+    #[serde(default, rename = "volume24hr")]
+    pub volume_24h: Option<Decimal>,
+    /// Market category (e.g. "Politics", "Sports", "Crypto"), when tagged
     #[serde(default)]
-    pub minimum_tick_size: String,
-    #[serde(default)]
-    pub neg_risk: bool,
+    pub category: Option<String>,
+}
+
+/// Deserialize an optional Gamma timestamp, either RFC 3339 or `2024-11-06 12:00:00+00`.
+fn deserialize_gamma_time<'de, D>(deserializer: D) -> std::result::Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let Some(raw) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    DateTime::parse_from_rfc3339(&raw)
+        .or_else(|_| DateTime::parse_from_str(&raw, "%Y-%m-%d %H:%M:%S%#z"))
+        .map(|at| Some(at.with_timezone(&Utc)))
+        .map_err(serde::de::Error::custom)
 }
 
 /// Token information for a market outcome
@@ -193,6 +246,50 @@ pub struct TokenInfo {
     pub winner: Option<bool>,
 }
 
+impl MarketInfo {
+    /// CLOB token ID for an outcome (case-insensitive).
+    pub fn token_id(&self, outcome: &str) -> Option<&str> {
+        self.tokens
+            .iter()
+            .find(|t| t.outcome.eq_ignore_ascii_case(outcome))
+            .map(|t| t.token_id.as_str())
+    }
+
+    /// Check if the market has resolved (any outcome token is marked as the winner).
+    pub fn is_resolved(&self) -> bool {
+        self.tokens.iter().any(|t| t.winner == Some(true))
+    }
+
+    /// The winning outcome, once the market has resolved.
+    pub fn winning_outcome(&self) -> Option<&str> {
+        self.tokens.iter().find(|t| t.winner == Some(true)).map(|t| t.outcome.as_str())
+    }
+
+    /// Minimum order size in shares (zero if not reported).
+    pub fn min_order_size(&self) -> Decimal {
+        Decimal::from_str(&self.minimum_order_size).unwrap_or(Decimal::ZERO)
+    }
+
+    /// Round a share size down to the CLOB lot size, or to zero if it falls
+    /// below the market's minimum order size.
+    pub fn round_shares(&self, size: Decimal) -> Decimal {
+        let rounded = round_to_lot(size, SHARE_LOT_SIZE);
+        if rounded < self.min_order_size() {
+            Decimal::ZERO
+        } else {
+            rounded
+        }
+    }
+}
+
+/// Round a size down to a multiple of `lot` (never over-buys).
+pub fn round_to_lot(size: Decimal, lot: Decimal) -> Decimal {
+    if lot <= Decimal::ZERO || size <= Decimal::ZERO {
+        return size.max(Decimal::ZERO);
+    }
+    (size / lot).floor() * lot
+}
+
 /// Order book entry
 #[derive(Debug, Clone, Deserialize)]
 pub struct BookLevel {
@@ -209,13 +306,90 @@ pub struct OrderBook {
     pub timestamp: String,
 }
 
+impl OrderBook {
+    /// Midpoint of the best bid and ask, or whichever side exists.
+    pub fn mid_price(&self) -> Option<Decimal> {
+        let best = |levels: &[BookLevel]| levels.first().and_then(|l| Decimal::from_str(&l.price).ok());
+        match (best(&self.bids), best(&self.asks)) {
+            (Some(bid), Some(ask)) => Some((bid + ask) / Decimal::TWO),
+            (bid, ask) => bid.or(ask),
+        }
+    }
+
+    /// (price, size) levels on the side a taker of `side` would consume.
+    fn taker_levels(&self, side: OrderSide) -> Vec<(Decimal, Decimal)> {
+        let levels = match side {
+            OrderSide::Buy => &self.asks,
+            OrderSide::Sell => &self.bids,
+        };
+        levels
+            .iter()
+            .filter_map(|l| Some((Decimal::from_str(&l.price).ok()?, Decimal::from_str(&l.size).ok()?)))
+            .collect()
+    }
+
+    /// Average price a taker of `side` would pay for `shares`, walking the book from the
+    /// best level. Shares beyond the book's depth are priced at its worst level; None if
+    /// the side is empty.
+    pub fn taker_fill_price(&self, side: OrderSide, shares: Decimal) -> Option<Decimal> {
+        let mut levels = self.taker_levels(side);
+        match side {
+            OrderSide::Buy => levels.sort_by_key(|(price, _)| *price),
+            OrderSide::Sell => levels.sort_by_key(|(price, _)| std::cmp::Reverse(*price)),
+        }
+        let worst = levels.last()?.0;
+
+        let mut remaining = shares;
+        let mut notional = Decimal::ZERO;
+        for (price, size) in &levels {
+            let take = remaining.min(*size);
+            notional += take * price;
+            remaining -= take;
+            if remaining <= Decimal::ZERO {
+                break;
+            }
+        }
+        notional += remaining.max(Decimal::ZERO) * worst;
+
+        Some(if shares > Decimal::ZERO { notional / shares } else { levels[0].0 })
+    }
+
+    /// Total notional (USDC) resting on the side a taker of `side` would consume.
+    pub fn depth_usdc(&self, side: OrderSide) -> Decimal {
+        self.taker_levels(side).iter().map(|(price, size)| price * size).sum()
+    }
+
+    /// Notional (USDC) a taker of `side` could consume without paying more than `band`
+    /// (a fraction) worse than the best price.
+    pub fn depth_within_usdc(&self, side: OrderSide, band: Decimal) -> Decimal {
+        let levels = self.taker_levels(side);
+        let prices = levels.iter().map(|(price, _)| *price);
+        let limit = match side {
+            OrderSide::Buy => prices.min().map(|best| best * (Decimal::ONE + band)),
+            OrderSide::Sell => prices.max().map(|best| best * (Decimal::ONE - band)),
+        };
+        let Some(limit) = limit else {
+            return Decimal::ZERO;
+        };
+
+        levels
+            .iter()
+            .filter(|(price, _)| match side {
+                OrderSide::Buy => *price <= limit,
+                OrderSide::Sell => *price >= limit,
+            })
+            .map(|(price, size)| price * size)
+            .sum()
+    }
+}
+
 impl ClobClient {
     /// Create a new CLOB client.
     ///
     /// # Arguments
     /// * `private_key` - Ethereum private key (hex string, with or without 0x prefix)
     /// * `api_key` - Polymarket API key
-    /// * `api_secret` - Polymarket API secret
+    /// * `api_secret` - Polymarket API secret (URL-safe base64)
     /// * `api_passphrase` - Polymarket API passphrase
     /// * `chain_id` - Polygon chain ID (137 for mainnet, 80002 for Amoy testnet)
     pub fn new(
@@ -225,32 +399,119 @@ impl ClobClient {
         api_passphrase: &str,
         chain_id: u64,
     ) -> Result<Self> {
-        let pk = private_key.strip_prefix("0x").unwrap_or(private_key);
-        let signer = PrivateKeySigner::from_str(pk)
-            .context("Invalid private key")?;
+        let signer = signer::parse_private_key(private_key)?;
+        Self::with_signer(signer, api_key, api_secret, api_passphrase, chain_id)
+    }
 
+    /// Create a new CLOB client that signs orders with an already-loaded key.
+    pub fn with_signer(
+        signer: PrivateKeySigner,
+        api_key: &str,
+        api_secret: &str,
+        api_passphrase: &str,
+        chain_id: u64,
+    ) -> Result<Self> {
         let http = Client::builder()
             .timeout(std::time::Duration::from_secs(30))
             .build()?;
 
+        let api_secret = URL_SAFE.decode(api_secret).context("API secret is not valid URL-safe base64")?;
+
         Ok(Self {
             http,
             signer,
             api_key: api_key.to_string(),
-            api_secret: api_secret.to_string(),
+            api_secret,
             api_passphrase: api_passphrase.to_string(),
             chain_id,
+            clob_url: CLOB_URL.to_string(),
+            gamma_url: GAMMA_URL.to_string(),
+            ws_url: None,
+            confirm_grace: std::time::Duration::ZERO,
+            funder: None,
+        })
+    }
+
+    /// Create a client for public market data only (order books, markets).
+    ///
+    /// Uses a throwaway signing key and no API credentials, so any
+    /// authenticated call will be rejected.
+    /// Honors the same POLYMARKET_CLOB_URL / POLYMARKET_GAMMA_URL / POLYMARKET_WS_URL
+    /// overrides as [`Self::from_env`].
+    pub fn read_only() -> Result<Self> {
+        let key = PrivateKeySigner::random();
+        let (clob_url, gamma_url) = Self::urls_from_env();
+        let client = Self::new(&hex::encode(key.to_bytes()), "", "", "", 137)?.with_urls(clob_url, gamma_url);
+        Ok(match std::env::var("POLYMARKET_WS_URL") {
+            Ok(ws_url) => client.with_ws_url(ws_url),
+            Err(_) => client,
         })
     }
 
+    /// Override the HTTP request timeout (default 30s).
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Result<Self> {
+        self.http = Client::builder().timeout(timeout).build()?;
+        Ok(self)
+    }
+
+    /// Point the client at custom CLOB and Gamma API base URLs (testnet or mock servers).
+    pub fn with_urls(mut self, clob_url: impl Into<String>, gamma_url: impl Into<String>) -> Self {
+        self.clob_url = clob_url.into();
+        self.gamma_url = gamma_url.into();
+        self
+    }
+
+    /// Point the market stream at a custom websocket URL instead of deriving it from
+    /// the CLOB URL.
+    pub fn with_ws_url(mut self, ws_url: impl Into<String>) -> Self {
+        self.ws_url = Some(ws_url.into());
+        self
+    }
+
+    /// Websocket URL of the market channel: the override if set, Polymarket's own for
+    /// the production CLOB, or else `/ws/market` on the CLOB host.
+    pub fn market_ws_url(&self) -> String {
+        if let Some(url) = &self.ws_url {
+            return url.clone();
+        }
+        if self.clob_url == CLOB_URL {
+            return MARKET_WS_URL.to_string();
+        }
+        let host = self.clob_url.trim_end_matches('/');
+        let host = match host.split_once("://") {
+            Some(("https", rest)) => format!("wss://{}", rest),
+            Some((_, rest)) => format!("ws://{}", rest),
+            None => format!("ws://{}", host),
+        };
+        format!("{}/ws/market", host)
+    }
+
+    /// Hold funds and positions in a proxy wallet other than the signer (default the signer).
+    pub fn with_funder(mut self, funder: Address) -> Self {
+        self.funder = Some(funder);
+        self
+    }
+
+    /// Wait this long after a failed submission before checking whether the order
+    /// reached the book anyway (default none).
+    pub fn with_confirm_grace(mut self, grace: std::time::Duration) -> Self {
+        self.confirm_grace = grace;
+        self
+    }
+
     /// Get the wallet address.
     pub fn address(&self) -> Address {
         self.signer.address()
     }
 
+    /// Address whose positions and balances the account trades with: the proxy wallet if set.
+    pub fn funder_address(&self) -> Address {
+        self.funder.unwrap_or_else(|| self.address())
+    }
+
     /// Get market information by condition ID.
     pub async fn get_market(&self, condition_id: &str) -> Result<MarketInfo> {
-        let url = format!("{}/markets/{}", GAMMA_URL, condition_id);
+        let url = format!("{}/markets/{}", self.gamma_url, condition_id);
         let resp = self.http.get(&url).send().await?;
 
         if !resp.status().is_success() {
@@ -264,11 +525,8 @@ impl ClobClient {
 
     /// Get order book for a token.
     pub async fn get_order_book(&self, token_id: &str) -> Result<OrderBook> {
-        let url = format!("{}/book?token_id={}", CLOB_URL, token_id);
-        let resp = self.http.get(&url)
-            .headers(self.build_l1_headers()?)
-            .send()
-            .await?;
+        let url = format!("{}/book?token_id={}", self.clob_url, token_id);
+        let resp = self.http.get(&url).send().await?;
 
         if !resp.status().is_success() {
             let status = resp.status();
@@ -279,6 +537,23 @@ impl ClobClient {
         resp.json().await.context("Failed to parse order book")
     }
 
+    /// Mid price for a market outcome, resolving its token ID from Gamma when possible.
+    pub async fn outcome_mid_price(&self, market_id: &str, outcome: &str) -> Result<Decimal> {
+        let token_id = match self.get_market(market_id).await {
+            Ok(market) => market.token_id(outcome).map(str::to_string),
+            Err(e) => {
+                tracing::debug!(market = %market_id, error = %e, "Could not fetch market info");
+                None
+            }
+        }
+        .unwrap_or_else(|| format!("{}:{}", market_id, outcome));
+
+        self.get_order_book(&token_id)
+            .await?
+            .mid_price()
+            .with_context(|| format!("Empty order book for {} {}", market_id, outcome))
+    }
+
     /// Get current best bid price for a token.
     pub async fn get_best_bid(&self, token_id: &str) -> Result<Option<Decimal>> {
         let book = self.get_order_book(token_id).await?;
@@ -360,6 +635,9 @@ impl ClobClient {
             size,
             price,
             order_type,
+            Self::generate_salt(),
+            self.generate_nonce(),
+            Utc::now() + ORDER_TTL,
         ).await?;
 
         let payload = OrderPayload {
@@ -368,10 +646,123 @@ impl ClobClient {
             order_type,
         };
 
-        let url = format!("{}/order", CLOB_URL);
+        self.submit_order(&payload).await
+    }
+
+    /// Place a market order (Fill-or-Kill) tagged with a client order id.
+    ///
+    /// If the submission times out, the order is looked up before a single
+    /// retry, so the same `client_id` never results in two orders. `created_at`
+    /// is when the order was first decided on, so re-signing it keeps its expiration.
+    pub async fn market_order_with_client_id(
+        &self,
+        token_id: &str,
+        side: OrderSide,
+        size: Decimal,
+        client_id: &str,
+        created_at: DateTime<Utc>,
+    ) -> Result<OrderResponse> {
+        let price = match side {
+            OrderSide::Buy => self.get_best_ask(token_id).await?
+                .ok_or_else(|| anyhow!("No asks available"))?,
+            OrderSide::Sell => self.get_best_bid(token_id).await?
+                .ok_or_else(|| anyhow!("No bids available"))?,
+        };
+        let price_with_slippage = match side {
+            OrderSide::Buy => price * Decimal::from_str("1.005")?,
+            OrderSide::Sell => price * Decimal::from_str("0.995")?,
+        };
+
+        let order = self
+            .prepare_order(token_id, side, size, price_with_slippage, OrderType::Fok, client_id, created_at)
+            .await?;
+        self.submit_idempotent(&order).await
+    }
+
+    /// Sign an order whose salt is derived from `client_id` and whose expiration is
+    /// [`ORDER_TTL`] after `created_at`.
+    ///
+    /// The nonce is left at 0, so the same inputs always sign to the same order id.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn prepare_order(
+        &self,
+        token_id: &str,
+        side: OrderSide,
+        size: Decimal,
+        price: Decimal,
+        order_type: OrderType,
+        client_id: &str,
+        created_at: DateTime<Utc>,
+    ) -> Result<PreparedOrder> {
+        let signed_order = self.build_signed_order(
+            token_id,
+            side,
+            size,
+            price,
+            order_type,
+            client_salt(client_id),
+            "0".to_string(),
+            created_at + ORDER_TTL,
+        ).await?;
+        let order_id = self.order_id(&signed_order)?;
+
+        Ok(PreparedOrder {
+            order_id,
+            payload: OrderPayload {
+                order: signed_order,
+                owner: format!("{:?}", self.address()),
+                order_type,
+            },
+        })
+    }
+
+    /// Submit a prepared order, confirming a failed submission really missed the book.
+    ///
+    /// Any error is reconciled against open orders and the order lookup; a timed-out
+    /// submission that is still not found is retried once.
+    pub async fn submit_idempotent(&self, order: &PreparedOrder) -> Result<OrderResponse> {
+        let e = match self.submit_order(&order.payload).await {
+            Err(e) => e,
+            result => return result,
+        };
+        warn!(order_id = %order.order_id, error = %e, "Order submission failed, checking whether it landed");
+        if !self.confirm_grace.is_zero() {
+            tokio::time::sleep(self.confirm_grace).await;
+        }
+        if let Some(existing) = self.find_order(&order.order_id).await {
+            return Ok(OrderResponse {
+                order_id: Some(existing.id),
+                success: true,
+                error_msg: String::new(),
+                status: Some(existing.status),
+                transaction_hash: None,
+            });
+        }
+        if is_timeout(&e) {
+            return self.submit_order(&order.payload).await;
+        }
+        Err(e)
+    }
+
+    /// Look up an order by id among open orders, then by direct lookup (for filled orders).
+    async fn find_order(&self, order_id: &str) -> Option<OrderStatus> {
+        if let Ok(open) = self.get_open_orders().await {
+            if let Some(found) = open.into_iter().find(|o| o.id.eq_ignore_ascii_case(order_id)) {
+                return Some(found);
+            }
+        }
+        self.get_order(order_id).await.ok()
+    }
+
+    /// POST a signed order.
+    async fn submit_order(&self, payload: &OrderPayload) -> Result<OrderResponse> {
+        let url = format!("{}/order", self.clob_url);
+        // Sign exactly the bytes that are sent
+        let body = serde_json::to_string(payload)?;
         let resp = self.http.post(&url)
-            .headers(self.build_l2_headers(&payload)?)
-            .json(&payload)
+            .headers(self.build_l2_headers("POST", "/order", Some(&body))?)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
             .send()
             .await?;
 
@@ -384,7 +775,28 @@ impl ClobClient {
         resp.json().await.context("Failed to parse order response")
     }
 
+    /// The CLOB order id: the EIP-712 digest of the signed order.
+    fn order_id(&self, order: &SignedOrder) -> Result<String> {
+        let side = if order.side == "BUY" { OrderSide::Buy } else { OrderSide::Sell };
+        let order_hash = self.compute_order_hash(
+            &order.salt,
+            &order.maker,
+            &order.signer,
+            &order.taker,
+            &order.token_id,
+            &order.maker_amount,
+            &order.taker_amount,
+            &order.expiration,
+            &order.nonce,
+            &order.fee_rate_bps,
+            side.as_u8(),
+        )?;
+        let digest = self.typed_data_hash(&order_hash)?;
+        Ok(format!("0x{}", hex::encode(digest)))
+    }
+
     /// Build a signed order for submission.
+    #[allow(clippy::too_many_arguments)]
     async fn build_signed_order(
         &self,
         token_id: &str,
@@ -392,39 +804,17 @@ impl ClobClient {
         size: Decimal,
         price: Decimal,
         _order_type: OrderType,
+        salt: String,
+        nonce: String,
+        expires_at: DateTime<Utc>,
     ) -> Result<SignedOrder> {
         let maker = format!("{:?}", self.address());
         let signer = maker.clone();
         let taker = "0x0000000000000000000000000000000000000000".to_string();
 
-        // Calculate amounts based on side
-        // For BUY: maker pays USDC (taker_amount), receives shares (maker_amount)
-        // For SELL: maker gives shares (maker_amount), receives USDC (taker_amount)
-        let size_wei = Self::to_wei(size);
-        let price_decimal = price.to_f64().unwrap_or(0.5);
-
-        let (maker_amount, taker_amount) = match side {
-            OrderSide::Buy => {
-                // Buying shares: we pay (size * price) USDC, receive (size) shares
-                let usdc_amount = size * price;
-                (size_wei.clone(), Self::to_wei(usdc_amount))
-            }
-            OrderSide::Sell => {
-                // Selling shares: we give (size) shares, receive (size * price) USDC
-                let usdc_amount = size * price;
-                (size_wei.clone(), Self::to_wei(usdc_amount))
-            }
-        };
-
-        // Generate nonce and expiration
-        let nonce = self.generate_nonce();
-        let expiration = (SystemTime::now()
-            .duration_since(UNIX_EPOCH)?
-            .as_secs() + 3600) // 1 hour from now
-            .to_string();
+        let (maker_amount, taker_amount) = Self::order_amounts(side, size, price);
 
-        // Generate salt
-        let salt = Self::generate_salt();
+        let expiration = expires_at.timestamp().to_string();
 
         // Fee rate (default 0 for taker orders, can be set by API)
         let fee_rate_bps = "0".to_string();
@@ -501,15 +891,7 @@ impl ClobClient {
             side,
         )?;
 
-        // Build the EIP-712 domain separator
-        let domain_hash = self.compute_domain_separator()?;
-
-        // Compute the final hash: keccak256("\x19\x01" + domainSeparator + orderHash)
-        let mut message = vec![0x19, 0x01];
-        message.extend_from_slice(&domain_hash);
-        message.extend_from_slice(&order_hash);
-
-        let final_hash = alloy_primitives::keccak256(&message);
+        let final_hash = self.typed_data_hash(&order_hash)?;
 
         // Sign the hash
         let signature = self.signer.sign_hash(&final_hash).await
@@ -518,6 +900,17 @@ impl ClobClient {
         Ok(format!("0x{}", hex::encode(signature.as_bytes())))
     }
 
+    /// Compute the final EIP-712 hash: keccak256("\x19\x01" + domainSeparator + orderHash).
+    fn typed_data_hash(&self, order_hash: &[u8; 32]) -> Result<alloy_primitives::B256> {
+        let domain_hash = self.compute_domain_separator()?;
+
+        let mut message = vec![0x19, 0x01];
+        message.extend_from_slice(&domain_hash);
+        message.extend_from_slice(order_hash);
+
+        Ok(alloy_primitives::keccak256(&message))
+    }
+
     /// Compute the EIP-712 order struct hash.
     fn compute_order_hash(
         &self,
@@ -580,9 +973,10 @@ impl ClobClient {
 
     /// Get order status by ID.
     pub async fn get_order(&self, order_id: &str) -> Result<OrderStatus> {
-        let url = format!("{}/order/{}", CLOB_URL, order_id);
+        let path = format!("/order/{}", order_id);
+        let url = format!("{}{}", self.clob_url, path);
         let resp = self.http.get(&url)
-            .headers(self.build_l1_headers()?)
+            .headers(self.build_l2_headers("GET", &path, None)?)
             .send()
             .await?;
 
@@ -597,9 +991,10 @@ impl ClobClient {
 
     /// Cancel an order by ID.
     pub async fn cancel_order(&self, order_id: &str) -> Result<bool> {
-        let url = format!("{}/order/{}", CLOB_URL, order_id);
+        let path = format!("/order/{}", order_id);
+        let url = format!("{}{}", self.clob_url, path);
         let resp = self.http.delete(&url)
-            .headers(self.build_l1_headers()?)
+            .headers(self.build_l2_headers("DELETE", &path, None)?)
             .send()
             .await?;
 
@@ -608,9 +1003,9 @@ impl ClobClient {
 
     /// Cancel all open orders.
     pub async fn cancel_all_orders(&self) -> Result<bool> {
-        let url = format!("{}/orders", CLOB_URL);
+        let url = format!("{}/orders", self.clob_url);
         let resp = self.http.delete(&url)
-            .headers(self.build_l1_headers()?)
+            .headers(self.build_l2_headers("DELETE", "/orders", None)?)
             .send()
             .await?;
 
@@ -619,9 +1014,9 @@ impl ClobClient {
 
     /// Get all open orders for this wallet.
     pub async fn get_open_orders(&self) -> Result<Vec<OrderStatus>> {
-        let url = format!("{}/orders?market=all", CLOB_URL);
+        let url = format!("{}/orders?market=all", self.clob_url);
         let resp = self.http.get(&url)
-            .headers(self.build_l1_headers()?)
+            .headers(self.build_l2_headers("GET", "/orders", None)?)
             .send()
             .await?;
 
@@ -634,12 +1029,44 @@ impl ClobClient {
         resp.json().await.context("Failed to parse orders")
     }
 
-    /// Build L1 authentication headers (for read operations).
-    fn build_l1_headers(&self) -> Result<reqwest::header::HeaderMap> {
+    /// Get this wallet's USDC collateral balance.
+    pub async fn get_collateral_balance(&self) -> Result<Decimal> {
+        let body = self.get_balance_allowance().await?;
+        Self::from_usdc_units(&body.balance).context("Invalid balance")
+    }
+
+    /// Get the USDC the exchange is approved to spend from this wallet, or None if the
+    /// response doesn't report it. Orders beyond it fail on-chain; an unlimited approval
+    /// comes back as `Decimal::MAX`.
+    pub async fn check_allowance(&self) -> Result<Option<Decimal>> {
+        let body = self.get_balance_allowance().await?;
+        body.allowance
+            .map(|units| Self::from_usdc_units(&units).context("Invalid allowance"))
+            .transpose()
+    }
+
+    async fn get_balance_allowance(&self) -> Result<BalanceAllowance> {
+        let url = format!("{}/balance-allowance?asset_type=COLLATERAL", self.clob_url);
+        let resp = self.http.get(&url)
+            .headers(self.build_l2_headers("GET", "/balance-allowance", None)?)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            return Err(anyhow!("Failed to get balance: {} - {}", status, text));
+        }
+
+        resp.json().await.context("Failed to parse balance")
+    }
+
+    /// Build L2 authentication headers for a request to `path` (without its query string).
+    fn build_l2_headers(&self, method: &str, path: &str, body: Option<&str>) -> Result<reqwest::header::HeaderMap> {
         use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 
         let timestamp = Utc::now().timestamp().to_string();
-        let signature = self.sign_l1_auth(&timestamp)?;
+        let signature = Self::l2_signature(&self.api_secret, &timestamp, method, path, body)?;
 
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -666,31 +1093,52 @@ impl ClobClient {
         Ok(headers)
     }
 
-    /// Build L2 authentication headers (for write operations like order placement).
-    fn build_l2_headers<T: Serialize>(&self, _body: &T) -> Result<reqwest::header::HeaderMap> {
-        // L2 headers include L1 headers plus additional order-specific auth
-        self.build_l1_headers()
+    /// L2 signature: URL-safe base64 of HMAC-SHA256(secret, timestamp + method + path + body).
+    fn l2_signature(secret: &[u8], timestamp: &str, method: &str, path: &str, body: Option<&str>) -> Result<String> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).map_err(|e| anyhow!("Invalid API secret: {}", e))?;
+        mac.update(timestamp.as_bytes());
+        mac.update(method.as_bytes());
+        mac.update(path.as_bytes());
+        if let Some(body) = body {
+            mac.update(body.as_bytes());
+        }
+        Ok(URL_SAFE.encode(mac.finalize().into_bytes()))
+    }
+
+    /// Maker and taker amounts in base units: the maker gives what it sells.
+    ///
+    /// BUY: maker pays `size * price` USDC and receives `size` shares.
+    /// SELL: maker gives `size` shares and receives `size * price` USDC.
+    fn order_amounts(side: OrderSide, size: Decimal, price: Decimal) -> (String, String) {
+        let shares = Self::to_share_units(size);
+        let usdc = Self::to_usdc_units(size * price);
+        match side {
+            OrderSide::Buy => (usdc, shares),
+            OrderSide::Sell => (shares, usdc),
+        }
     }
 
-    /// Sign L1 authentication message.
-    fn sign_l1_auth(&self, timestamp: &str) -> Result<String> {
-        // L1 auth signature: HMAC-SHA256(secret, timestamp + method + path)
-        use std::io::Write;
+    /// USDC amount in collateral base units, truncated.
+    fn to_usdc_units(amount: Decimal) -> String {
+        Self::to_base_units(amount, USDC_DECIMALS)
+    }
 
-        // For simplicity, we'll use the wallet signature approach
-        // Real implementation should use HMAC with API secret
-        let message = format!("{}", timestamp);
-        let message_hash = alloy_primitives::keccak256(message.as_bytes());
+    /// Outcome share amount in conditional-token base units, truncated.
+    fn to_share_units(amount: Decimal) -> String {
+        Self::to_base_units(amount, SHARE_DECIMALS)
+    }
 
-        // We can't use async sign here, so we'll construct a dummy signature
-        // In production, this should be proper HMAC or the Polymarket auth flow
-        Ok(format!("0x{}", hex::encode(message_hash.as_slice())))
+    /// USDC amount from collateral base units; amounts beyond a `Decimal` (like an
+    /// unlimited approval) saturate.
+    fn from_usdc_units(units: &str) -> Result<Decimal> {
+        let units = U256::from_str(units)?;
+        let units = Decimal::from_str(&units.to_string()).unwrap_or(Decimal::MAX);
+        Ok(units / Decimal::from(10u64.pow(USDC_DECIMALS)))
     }
 
-    /// Convert decimal to wei (18 decimals).
-    fn to_wei(amount: Decimal) -> String {
-        let wei = amount * Decimal::from(10u64.pow(6)); // USDC has 6 decimals
-        wei.to_string().split('.').next().unwrap_or("0").to_string()
+    fn to_base_units(amount: Decimal, decimals: u32) -> String {
+        let units = amount * Decimal::from(10u64.pow(decimals));
+        units.to_string().split('.').next().unwrap_or("0").to_string()
     }
 
     /// Encode address to 32-byte padded format.
@@ -725,17 +1173,106 @@ impl ClobClient {
     }
 }
 
+/// Deterministic order salt for a client order id (kept below 2^53 for JSON clients).
+fn client_salt(client_id: &str) -> String {
+    let hash = alloy_primitives::keccak256(client_id.as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash[..8]);
+    (u64::from_be_bytes(bytes) & ((1 << 53) - 1)).to_string()
+}
+
+/// Whether an error came from a request that timed out.
+fn is_timeout(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<reqwest::Error>().is_some_and(|e| e.is_timeout())
+}
+
+/// Order operations used by the bot, abstracted so execution logic can be exercised offline.
+#[allow(async_fn_in_trait)]
+pub trait OrderExecutor {
+    /// Place a market order (Fill-or-Kill).
+    async fn market_order(&self, token_id: &str, side: OrderSide, size: Decimal) -> Result<OrderResponse>;
+
+    /// Place a resting limit order.
+    async fn limit_order(
+        &self,
+        token_id: &str,
+        side: OrderSide,
+        size: Decimal,
+        price: Decimal,
+    ) -> Result<OrderResponse>;
+
+    /// Get order status by ID.
+    async fn get_order(&self, order_id: &str) -> Result<OrderStatus>;
+
+    /// Cancel an order by ID.
+    async fn cancel_order(&self, order_id: &str) -> Result<bool>;
+}
+
+impl OrderExecutor for ClobClient {
+    async fn market_order(&self, token_id: &str, side: OrderSide, size: Decimal) -> Result<OrderResponse> {
+        ClobClient::market_order(self, token_id, side, size).await
+    }
+
+    async fn limit_order(
+        &self,
+        token_id: &str,
+        side: OrderSide,
+        size: Decimal,
+        price: Decimal,
+    ) -> Result<OrderResponse> {
+        ClobClient::limit_order(self, token_id, side, size, price).await
+    }
+
+    async fn get_order(&self, order_id: &str) -> Result<OrderStatus> {
+        ClobClient::get_order(self, order_id).await
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<bool> {
+        ClobClient::cancel_order(self, order_id).await
+    }
+}
+
+impl OrderStatus {
+    /// Size filled so far, in shares.
+    pub fn matched_size(&self) -> Decimal {
+        Decimal::from_str(&self.size_matched).unwrap_or(Decimal::ZERO)
+    }
+
+    /// Size-weighted average price across the order's fills, if any.
+    pub fn average_fill_price(&self) -> Option<Decimal> {
+        let mut notional = Decimal::ZERO;
+        let mut shares = Decimal::ZERO;
+        for fill in self.associate_trades.as_deref().unwrap_or_default() {
+            let size = Decimal::from_str(&fill.size).unwrap_or(Decimal::ZERO);
+            let price = Decimal::from_str(&fill.price).unwrap_or(Decimal::ZERO);
+            notional += size * price;
+            shares += size;
+        }
+        (shares > Decimal::ZERO).then(|| notional / shares)
+    }
+
+    /// Check if the order has been completely filled.
+    pub fn is_filled(&self) -> bool {
+        let original = Decimal::from_str(&self.original_size).unwrap_or(Decimal::ZERO);
+        self.status.eq_ignore_ascii_case("matched")
+            || (original > Decimal::ZERO && self.matched_size() >= original)
+    }
+}
+
 /// Helper to create a client from environment variables.
 impl ClobClient {
     /// Create from environment variables:
-    /// - POLYMARKET_PRIVATE_KEY
+    /// - POLYMARKET_KEYSTORE_PATH, an encrypted JSON keystore (passphrase from
+    ///   POLYMARKET_KEYSTORE_PASSPHRASE, or prompted), or else POLYMARKET_PRIVATE_KEY
     /// - POLYMARKET_API_KEY
     /// - POLYMARKET_API_SECRET
     /// - POLYMARKET_API_PASSPHRASE
     /// - POLYMARKET_CHAIN_ID (defaults to 137)
+    /// - POLYMARKET_CLOB_URL / POLYMARKET_GAMMA_URL (default to the production APIs)
+    /// - POLYMARKET_WS_URL (market stream; defaults to one matching the CLOB URL)
+    /// - POLYMARKET_CLOB_TIMEOUT_SECS (defaults to 30)
+    /// - POLYMARKET_FUNDER_ADDRESS (proxy wallet holding positions; defaults to the signer)
     pub fn from_env() -> Result<Self> {
-        let private_key = std::env::var("POLYMARKET_PRIVATE_KEY")
-            .context("POLYMARKET_PRIVATE_KEY not set")?;
         let api_key = std::env::var("POLYMARKET_API_KEY")
             .context("POLYMARKET_API_KEY not set")?;
         let api_secret = std::env::var("POLYMARKET_API_SECRET")
@@ -747,7 +1284,34 @@ impl ClobClient {
             .parse()
             .context("Invalid POLYMARKET_CHAIN_ID")?;
 
-        Self::new(&private_key, &api_key, &api_secret, &api_passphrase, chain_id)
+        let (clob_url, gamma_url) = Self::urls_from_env();
+
+        let timeout_secs: u64 = std::env::var("POLYMARKET_CLOB_TIMEOUT_SECS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .context("Invalid POLYMARKET_CLOB_TIMEOUT_SECS")?;
+
+        let signer = signer::signer_from_env()?;
+
+        let mut client = Self::with_signer(signer, &api_key, &api_secret, &api_passphrase, chain_id)?
+            .with_urls(clob_url, gamma_url)
+            .with_timeout(std::time::Duration::from_secs(timeout_secs))?;
+        if let Ok(funder) = std::env::var("POLYMARKET_FUNDER_ADDRESS") {
+            client = client.with_funder(funder.parse().context("Invalid POLYMARKET_FUNDER_ADDRESS")?);
+        }
+        if let Ok(ws_url) = std::env::var("POLYMARKET_WS_URL") {
+            client = client.with_ws_url(ws_url);
+        }
+        Ok(client)
+    }
+
+    /// CLOB and Gamma base URLs, from POLYMARKET_CLOB_URL / POLYMARKET_GAMMA_URL or
+    /// else the production APIs.
+    fn urls_from_env() -> (String, String) {
+        (
+            std::env::var("POLYMARKET_CLOB_URL").unwrap_or_else(|_| CLOB_URL.to_string()),
+            std::env::var("POLYMARKET_GAMMA_URL").unwrap_or_else(|_| GAMMA_URL.to_string()),
+        )
     }
 }
 
@@ -756,10 +1320,161 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_to_wei() {
+    fn test_l2_signature_matches_reference_vector() {
+        // From Polymarket's reference client
+        let secret = URL_SAFE.decode("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=").unwrap();
+        let signature =
+            ClobClient::l2_signature(&secret, "1000000", "test-sign", "/orders", Some(r#"{"hash": "0x123"}"#)).unwrap();
+        assert_eq!(signature, "ZwAdJKvoYRlEKDkNMwd5BuwNNtg93kNaR_oU2HrfVvc=");
+
+        // No body signs just the prefix
+        let bare = ClobClient::l2_signature(&secret, "1000000", "GET", "/orders", None).unwrap();
+        assert_eq!(bare, ClobClient::l2_signature(&secret, "1000000", "GET", "/orders", Some("")).unwrap());
+    }
+
+    #[test]
+    fn test_to_usdc_units() {
         let amount = Decimal::from_str("100.5").unwrap();
-        let wei = ClobClient::to_wei(amount);
-        assert_eq!(wei, "100500000");
+        let units = ClobClient::to_usdc_units(amount);
+        assert_eq!(units, "100500000");
+    }
+
+    #[test]
+    fn test_order_amounts_encode_shares_and_usdc_per_side() {
+        let size = Decimal::from_str("10").unwrap();
+        let price = Decimal::from_str("0.37").unwrap();
+
+        // 10 shares, 3.70 USDC
+        assert_eq!(ClobClient::to_share_units(size), "10000000");
+        assert_eq!(ClobClient::to_usdc_units(size * price), "3700000");
+
+        // A buyer gives USDC and takes shares; a seller the reverse
+        assert_eq!(
+            ClobClient::order_amounts(OrderSide::Buy, size, price),
+            ("3700000".to_string(), "10000000".to_string())
+        );
+        assert_eq!(
+            ClobClient::order_amounts(OrderSide::Sell, size, price),
+            ("10000000".to_string(), "3700000".to_string())
+        );
+
+        // Sub-unit remainders are truncated, never rounded up
+        assert_eq!(ClobClient::to_usdc_units(Decimal::from_str("0.0000019").unwrap()), "1");
+    }
+
+    #[test]
+    fn test_market_with_winning_token_is_resolved() {
+        let resolved: MarketInfo = serde_json::from_str(
+            r#"{"conditionId":"0xabc","questionId":"0xq","tokens":[
+                {"tokenId":"1","outcome":"Yes","winner":true},
+                {"tokenId":"2","outcome":"No","winner":false}]}"#,
+        ).unwrap();
+        assert!(resolved.is_resolved());
+
+        let active: MarketInfo = serde_json::from_str(
+            r#"{"conditionId":"0xabc","questionId":"0xq","tokens":[
+                {"tokenId":"1","outcome":"Yes","winner":false},
+                {"tokenId":"2","outcome":"No"}]}"#,
+        ).unwrap();
+        assert!(!active.is_resolved());
+    }
+
+    #[tokio::test]
+    async fn test_market_info_uses_custom_gamma_url() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        mock.mount_market("0xabc", true).await;
+
+        let market = mock.clob_client().get_market("0xabc").await.unwrap();
+        assert_eq!(market.tokens.len(), 2);
+        assert!(market.is_resolved());
+    }
+
+    #[tokio::test]
+    async fn test_order_book_uses_custom_clob_url() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        mock.mount_order_book("123", "0.48", "0.52").await;
+
+        let clob = mock.clob_client();
+        assert_eq!(clob.get_best_bid("123").await.unwrap(), Some(Decimal::from_str("0.48").unwrap()));
+        assert_eq!(clob.get_best_ask("123").await.unwrap(), Some(Decimal::from_str("0.52").unwrap()));
+    }
+
+    #[test]
+    fn test_market_ws_url_follows_clob_url() {
+        let clob = ClobClient::new(crate::testing::TEST_PRIVATE_KEY, "key", "c2VjcmV0", "passphrase", 137).unwrap();
+        assert_eq!(clob.market_ws_url(), MARKET_WS_URL);
+
+        let mock = clob.with_urls("http://127.0.0.1:8080/", GAMMA_URL);
+        assert_eq!(mock.market_ws_url(), "ws://127.0.0.1:8080/ws/market");
+
+        let staging = mock.with_urls("https://clob.staging.example", GAMMA_URL);
+        assert_eq!(staging.market_ws_url(), "wss://clob.staging.example/ws/market");
+
+        let pinned = staging.with_ws_url("wss://stream.example/ws/market");
+        assert_eq!(pinned.market_ws_url(), "wss://stream.example/ws/market");
+    }
+
+    #[test]
+    fn test_taker_fill_price_walks_the_crossed_side() {
+        let level = |price: &str, size: &str| BookLevel { price: price.to_string(), size: size.to_string() };
+        // Levels listed worst first, as the CLOB serves them
+        let book = OrderBook {
+            bids: vec![level("0.46", "100"), level("0.48", "100")],
+            asks: vec![level("0.54", "100"), level("0.52", "100")],
+            hash: String::new(),
+            timestamp: String::new(),
+        };
+        let dec = |s: &str| Decimal::from_str(s).unwrap();
+
+        assert_eq!(book.taker_fill_price(OrderSide::Buy, dec("50")), Some(dec("0.52")));
+        assert_eq!(book.taker_fill_price(OrderSide::Buy, dec("200")), Some(dec("0.53")));
+        assert_eq!(book.taker_fill_price(OrderSide::Sell, dec("100")), Some(dec("0.48")));
+        // Beyond the book's depth the rest is priced at the worst level
+        assert_eq!(book.taker_fill_price(OrderSide::Sell, dec("400")), Some(dec("0.465")));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_timeout_finds_resting_order() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        let clob = mock.clob_client()
+            .with_timeout(std::time::Duration::from_millis(200))
+            .unwrap();
+        let size = Decimal::from_str("10").unwrap();
+        let price = Decimal::from_str("0.5").unwrap();
+        let created_at = Utc::now();
+        let order = clob.prepare_order("123", OrderSide::Buy, size, price, OrderType::Gtc, "copy-1", created_at)
+            .await
+            .unwrap();
+
+        // Preparing the same order again signs to the same order id
+        let again = clob.prepare_order("123", OrderSide::Buy, size, price, OrderType::Gtc, "copy-1", created_at)
+            .await
+            .unwrap();
+        assert_eq!(again.order_id, order.order_id);
+
+        // The first POST lands but its response never arrives in time
+        mock.expect_order_submission(std::time::Duration::from_secs(2), 1).await;
+        mock.mount_open_orders(vec![crate::testing::open_order(&order.order_id)]).await;
+
+        let response = clob.submit_idempotent(&order).await.unwrap();
+        assert!(response.success);
+        assert_eq!(response.order_id.as_deref(), Some(order.order_id.as_str()));
+
+        assert_eq!(client_salt("copy-1"), client_salt("copy-1"));
+        assert_ne!(client_salt("copy-1"), client_salt("copy-2"));
+    }
+
+    #[test]
+    fn test_share_size_rounds_down_to_lot() {
+        let size = Decimal::from_str("10.337").unwrap();
+        assert_eq!(round_to_lot(size, SHARE_LOT_SIZE), Decimal::from_str("10.33").unwrap());
+        assert_eq!(round_to_lot(size, Decimal::from_str("0.1").unwrap()), Decimal::from_str("10.3").unwrap());
+
+        let market: MarketInfo = serde_json::from_str(
+            r#"{"conditionId":"0xabc","questionId":"0xq","tokens":[],"minimumOrderSize":"5"}"#,
+        ).unwrap();
+        assert_eq!(market.round_shares(size), Decimal::from_str("10.33").unwrap());
+        assert_eq!(market.round_shares(Decimal::from_str("4.999").unwrap()), Decimal::ZERO);
     }
 
     #[test]
@@ -4,6 +4,7 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, TimeZone, Utc};
 use reqwest::Client;
 use rust_decimal::Decimal;
+use std::collections::HashMap;
 use std::time::Duration;
 use tracing::{debug, warn};
 
@@ -14,7 +15,21 @@ use super::types::*;
 const DATA_API_BASE: &str = "https://data-api.polymarket.com";
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// Maximum entries the leaderboard endpoint returns per request.
+pub const LEADERBOARD_PAGE_CAP: u32 = 50;
+
+/// Outcome name, falling back to a stable label from the outcome index when the
+/// API returns an empty string (otherwise distinct outcomes would collapse together).
+fn outcome_label(outcome: String, index: i32) -> String {
+    if outcome.trim().is_empty() {
+        format!("Outcome {}", index)
+    } else {
+        outcome
+    }
+}
+
 /// Client for Polymarket Data API (read-only operations).
+#[derive(Clone)]
 pub struct DataClient {
     client: Client,
     base_url: String,
@@ -44,7 +59,10 @@ impl DataClient {
         Ok(Self { client, base_url })
     }
 
-    /// Fetch trader leaderboard.
+    /// Fetch one page of the trader leaderboard.
+    ///
+    /// `limit` is clamped to [`LEADERBOARD_PAGE_CAP`]; use `offset` (or
+    /// [`Self::discover_top_traders`]) to fetch more.
     pub async fn get_leaderboard(
         &self,
         category: Option<&str>,
@@ -66,7 +84,10 @@ impl DataClient {
             params.push(format!("orderBy={}", o));
         }
         if let Some(l) = limit {
-            params.push(format!("limit={}", l.min(50)));
+            if l > LEADERBOARD_PAGE_CAP {
+                debug!(requested = l, cap = LEADERBOARD_PAGE_CAP, "Leaderboard limit clamped to page cap");
+            }
+            params.push(format!("limit={}", l.min(LEADERBOARD_PAGE_CAP)));
         }
         if let Some(o) = offset {
             params.push(format!("offset={}", o));
@@ -132,7 +153,8 @@ impl DataClient {
                     trader_address: address.to_string(),
                     market_id: p.condition_id,
                     market_title: p.title,
-                    outcome: p.outcome,
+                    outcome: outcome_label(p.outcome, p.outcome_index),
+                    outcome_index: Some(p.outcome_index),
                     size: p.size,
                     average_price: p.avg_price,
                     current_price: p.cur_price,
@@ -148,14 +170,30 @@ impl DataClient {
         Ok(positions)
     }
 
-    /// Fetch trade history for a trader.
+    /// Fetch taker trade history for a trader.
     pub async fn get_trades(
         &self,
         address: &str,
         limit: Option<u32>,
         market: Option<&str>,
     ) -> Result<Vec<Trade>> {
-        let mut url = format!("{}/trades?user={}&takerOnly=true", self.base_url, address);
+        self.get_trades_filtered(address, limit, market, true).await
+    }
+
+    /// Fetch trade history for a trader, optionally including maker (limit order) fills.
+    ///
+    /// Maker fills carry the maker's limit price, which is the source's effective entry.
+    pub async fn get_trades_filtered(
+        &self,
+        address: &str,
+        limit: Option<u32>,
+        market: Option<&str>,
+        taker_only: bool,
+    ) -> Result<Vec<Trade>> {
+        let mut url = format!(
+            "{}/trades?user={}&takerOnly={}",
+            self.base_url, address, taker_only
+        );
 
         if let Some(l) = limit {
             url = format!("{}&limit={}", url, l.min(500));
@@ -184,6 +222,19 @@ impl DataClient {
             .await
             .context("Failed to parse trades response")?;
 
+        // Never attribute another wallet's fills to this trader
+        let (items, foreign): (Vec<_>, Vec<_>) = items
+            .into_iter()
+            .partition(|t| t.proxy_wallet.eq_ignore_ascii_case(address));
+        if !foreign.is_empty() {
+            warn!(address = %address, dropped = foreign.len(), "Dropped trades belonging to other wallets");
+        }
+
+        // A transaction can hold several fills and the API has no fill ID, so each is keyed
+        // by its own content, which doesn't depend on the order or page it arrives in.
+        // Only repeats of an identical fill are told apart by count.
+        let mut repeats: HashMap<String, usize> = HashMap::new();
+
         let trades = items
             .into_iter()
             .filter_map(|t| {
@@ -198,19 +249,36 @@ impl DataClient {
 
                 let timestamp = Utc.timestamp_opt(t.timestamp, 0).single()?;
 
+                let token = if t.asset.is_empty() { t.outcome_index.to_string() } else { t.asset.clone() };
+                let mut id = format!(
+                    "{}_{}_{}_{}_{}@{}",
+                    t.transaction_hash,
+                    t.timestamp,
+                    token,
+                    side.as_str(),
+                    t.size.normalize(),
+                    t.price.normalize()
+                );
+                let repeat = repeats.entry(id.clone()).or_default();
+                if *repeat > 0 {
+                    id = format!("{}#{}", id, repeat);
+                }
+                *repeat += 1;
+
                 Some(Trade {
-                    id: format!("{}_{}", t.transaction_hash, t.timestamp),
+                    id,
                     trader_address: t.proxy_wallet,
                     market_id: t.condition_id,
                     market_title: t.title,
                     side,
-                    outcome: t.outcome,
+                    outcome: outcome_label(t.outcome, t.outcome_index),
                     size: t.size,
                     price: t.price,
                     amount_usdc: t.size * t.price,
                     timestamp,
                     transaction_hash: t.transaction_hash,
-                    is_taker: true,
+                    // Without the taker-only filter the API doesn't say which side we got
+                    is_taker: taker_only,
                     fee_usdc: Decimal::ZERO,
                 })
             })
@@ -283,7 +351,7 @@ impl DataClient {
             .context("Failed to parse activity response")
     }
 
-    /// Discover top traders from the leaderboard.
+    /// Discover top traders from the leaderboard, paginating past the page cap.
     pub async fn discover_top_traders(
         &self,
         min_pnl: f64,
@@ -292,9 +360,10 @@ impl DataClient {
     ) -> Result<Vec<Trader>> {
         let mut traders = Vec::new();
         let mut offset = 0u32;
-        let page_size = 50u32;
 
         while traders.len() < limit {
+            let remaining = u32::try_from(limit - traders.len()).unwrap_or(u32::MAX);
+            let page_size = remaining.min(LEADERBOARD_PAGE_CAP);
             let entries = self
                 .get_leaderboard(
                     Some("OVERALL"),
@@ -308,6 +377,8 @@ impl DataClient {
             if entries.is_empty() {
                 break;
             }
+            let short_page = (entries.len() as u32) < page_size;
+            offset += entries.len() as u32;
 
             for entry in entries {
                 if entry.pnl >= min_pnl {
@@ -317,8 +388,11 @@ impl DataClient {
                         profile_image: entry.profile_image,
                         bio: String::new(),
                         is_tracked: false,
+                        is_paused: false,
                         tracking_since: None,
+                        last_trade_seen_at: None,
                         positions: Vec::new(),
+                        positions_as_of: None,
                         metrics: None,
                         allocation_weight: Decimal::ONE,
                     });
@@ -329,7 +403,10 @@ impl DataClient {
                 }
             }
 
-            offset += page_size;
+            // A short page means the leaderboard is exhausted
+            if short_page {
+                break;
+            }
 
             // Rate limiting
             tokio::time::sleep(Duration::from_millis(100)).await;
@@ -344,3 +421,143 @@ impl Default for DataClient {
         Self::new().expect("Failed to create default DataClient")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn trade_json(tx_hash: &str) -> serde_json::Value {
+        json!({
+            "proxyWallet": "0xabc",
+            "side": "BUY",
+            "conditionId": "0xmarket",
+            "size": 10,
+            "price": 0.4,
+            "timestamp": 1_700_000_000,
+            "transactionHash": tx_hash
+        })
+    }
+
+    #[tokio::test]
+    async fn test_positions_with_blank_outcome_stay_distinct_by_index() {
+        let server = MockServer::start().await;
+        let mut yes = crate::testing::position("0xmarket", 10.0, 0.4);
+        yes["outcome"] = json!("");
+        let mut no = yes.clone();
+        no["outcomeIndex"] = json!(1);
+        Mock::given(path("/positions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![yes, no]))
+            .mount(&server)
+            .await;
+
+        let client = DataClient::with_base_url(server.uri()).unwrap();
+        let positions = client.get_positions("0xabc", None).await.unwrap();
+
+        assert_eq!(positions.len(), 2);
+        assert_eq!(positions[0].outcome, "Outcome 0");
+        assert_eq!(positions[1].outcome, "Outcome 1");
+        assert_eq!(positions[1].outcome_index, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_discover_paginates_past_page_cap() {
+        let server = MockServer::start().await;
+        for (offset, count) in [(0, 50), (50, 50), (100, 20)] {
+            let entries: Vec<_> = (offset..offset + count)
+                .map(|i| crate::testing::leaderboard_entry(&format!("0x{:040x}", i), 1000.0))
+                .collect();
+            Mock::given(path("/v1/leaderboard"))
+                .and(query_param("offset", offset.to_string()))
+                .and(query_param("limit", count.to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(entries))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let client = DataClient::with_base_url(server.uri()).unwrap();
+        let traders = client.discover_top_traders(0.0, "ALL", 120).await.unwrap();
+
+        assert_eq!(traders.len(), 120);
+        assert_eq!(traders[0].address, format!("0x{:040x}", 0));
+        assert_eq!(traders[119].address, format!("0x{:040x}", 119));
+    }
+
+    #[tokio::test]
+    async fn test_fill_ids_independent_of_order_and_page() {
+        let fill = |asset: &str, size: u32| {
+            let mut t = trade_json("0xshared");
+            t["asset"] = json!(asset);
+            t["size"] = json!(size);
+            t
+        };
+        let server = MockServer::start().await;
+        for (limit, fills) in [
+            ("3", vec![fill("1", 10), fill("2", 5), fill("1", 10)]),
+            ("2", vec![fill("2", 5), fill("1", 10)]),
+        ] {
+            Mock::given(path("/trades"))
+                .and(query_param("limit", limit))
+                .respond_with(ResponseTemplate::new(200).set_body_json(fills))
+                .mount(&server)
+                .await;
+        }
+        let client = DataClient::with_base_url(server.uri()).unwrap();
+
+        let full = client.get_trades("0xabc", Some(3), None).await.unwrap();
+        let sizes: HashMap<_, _> = full.iter().map(|t| (t.id.clone(), t.size)).collect();
+        assert_eq!(sizes.len(), 3, "identical fills are told apart");
+
+        // A reordered, shorter page gives each fill the same ID
+        let page = client.get_trades("0xabc", Some(2), None).await.unwrap();
+        assert!(page.iter().all(|t| sizes.get(&t.id) == Some(&t.size)));
+    }
+
+    #[tokio::test]
+    async fn test_maker_trades_included_when_not_taker_only() {
+        let server = MockServer::start().await;
+        Mock::given(path("/trades"))
+            .and(query_param("takerOnly", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![trade_json("0xtaker")]))
+            .mount(&server)
+            .await;
+        Mock::given(path("/trades"))
+            .and(query_param("takerOnly", "false"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(vec![trade_json("0xtaker"), trade_json("0xmaker")]),
+            )
+            .mount(&server)
+            .await;
+
+        let client = DataClient::with_base_url(server.uri()).unwrap();
+
+        let taker = client.get_trades("0xabc", None, None).await.unwrap();
+        assert_eq!(taker.len(), 1);
+
+        let all = client.get_trades_filtered("0xabc", None, None, false).await.unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().any(|t| t.transaction_hash == "0xmaker"));
+    }
+
+    #[tokio::test]
+    async fn test_trades_for_other_wallets_are_dropped() {
+        let server = MockServer::start().await;
+        let mut foreign = trade_json("0xforeign");
+        foreign["proxyWallet"] = json!("0xdef");
+        Mock::given(path("/trades"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![trade_json("0xown"), foreign]))
+            .mount(&server)
+            .await;
+
+        let client = DataClient::with_base_url(server.uri()).unwrap();
+        let trades = client.get_trades("0xABC", None, None).await.unwrap();
+
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].transaction_hash, "0xown");
+        assert_eq!(trades[0].trader_address, "0xabc");
+    }
+}
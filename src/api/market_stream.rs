@@ -0,0 +1,296 @@
+//! Live mid-prices from the CLOB websocket market channel.
+//!
+//! A background task holds the socket open, reconnecting with exponential backoff
+//! and re-subscribing every token it was asked for, so a dropped connection only
+//! pauses updates.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::{SinkExt, StreamExt};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::{mpsc, RwLock};
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, info, warn};
+
+/// Polymarket's public market channel.
+pub const MARKET_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/market";
+
+/// First reconnect delay; doubles per failed attempt up to `MAX_BACKOFF`.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// The server drops connections that stay silent, so ping this often.
+const PING_INTERVAL: Duration = Duration::from_secs(10);
+
+type Prices = Arc<RwLock<HashMap<String, (Decimal, DateTime<Utc>)>>>;
+
+/// Handle to a background market-channel connection. Clones share the connection,
+/// which closes once every handle is dropped.
+#[derive(Clone)]
+pub struct MarketStream {
+    prices: Prices,
+    subscribed: Arc<RwLock<HashSet<String>>>,
+    subscribe_tx: mpsc::UnboundedSender<Vec<String>>,
+}
+
+impl MarketStream {
+    /// Connect to `url` in the background.
+    pub fn spawn(url: impl Into<String>) -> Self {
+        Self::spawn_with_backoff(url, INITIAL_BACKOFF)
+    }
+
+    fn spawn_with_backoff(url: impl Into<String>, initial_backoff: Duration) -> Self {
+        let prices = Prices::default();
+        let subscribed = Arc::new(RwLock::new(HashSet::new()));
+        let (subscribe_tx, subscribe_rx) = mpsc::unbounded_channel();
+        tokio::spawn(run(url.into(), prices.clone(), subscribed.clone(), subscribe_rx, initial_backoff));
+        Self { prices, subscribed, subscribe_tx }
+    }
+
+    /// Start streaming prices for any of `token_ids` not already subscribed.
+    pub async fn subscribe(&self, token_ids: impl IntoIterator<Item = String>) {
+        let mut subscribed = self.subscribed.write().await;
+        let new: Vec<String> = token_ids.into_iter().filter(|t| subscribed.insert(t.clone())).collect();
+        if !new.is_empty() {
+            // The task re-subscribes everything on reconnect, so a send while it's down is harmless
+            let _ = self.subscribe_tx.send(new);
+        }
+    }
+
+    /// Latest mid-price of a token and when it was received.
+    pub async fn mid_price(&self, token_id: &str) -> Option<(Decimal, DateTime<Utc>)> {
+        self.prices.read().await.get(token_id).copied()
+    }
+}
+
+/// Keep a connection up until every handle is dropped.
+async fn run(
+    url: String,
+    prices: Prices,
+    subscribed: Arc<RwLock<HashSet<String>>>,
+    mut subscribe_rx: mpsc::UnboundedReceiver<Vec<String>>,
+    initial_backoff: Duration,
+) {
+    let mut backoff = initial_backoff;
+    loop {
+        match tokio_tungstenite::connect_async(url.as_str()).await {
+            Ok((socket, _)) => {
+                info!(url = %url, "Market stream connected");
+                backoff = initial_backoff;
+                let (mut write, mut read) = socket.split();
+
+                // Everything requested so far, including while disconnected
+                while subscribe_rx.try_recv().is_ok() {}
+                let all: Vec<String> = subscribed.read().await.iter().cloned().collect();
+                // A failed resubscribe falls through to the backoff like any other drop
+                let resubscribed = all.is_empty() || write.send(subscribe_message(&all)).await.is_ok();
+                if resubscribed {
+                    let mut ping = tokio::time::interval(PING_INTERVAL);
+                    loop {
+                        tokio::select! {
+                            message = read.next() => match message {
+                                Some(Ok(Message::Text(text))) => {
+                                    let now = Utc::now();
+                                    let mut prices = prices.write().await;
+                                    for (token_id, mid) in mids(&text) {
+                                        prices.insert(token_id, (mid, now));
+                                    }
+                                }
+                                Some(Ok(Message::Close(_))) | None => break,
+                                Some(Ok(_)) => {}
+                                Some(Err(e)) => {
+                                    warn!(error = %e, "Market stream error");
+                                    break;
+                                }
+                            },
+                            tokens = subscribe_rx.recv() => match tokens {
+                                Some(tokens) => {
+                                    if write.send(subscribe_message(&tokens)).await.is_err() {
+                                        break;
+                                    }
+                                }
+                                None => return,
+                            },
+                            _ = ping.tick() => {
+                                if write.send(Message::Text("PING".into())).await.is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            Err(e) => warn!(url = %url, error = %e, "Market stream connection failed"),
+        }
+
+        if subscribe_rx.is_closed() {
+            return;
+        }
+        warn!(retry_in = ?backoff, "Market stream disconnected, reconnecting");
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+fn subscribe_message(token_ids: &[String]) -> Message {
+    Message::Text(json!({ "assets_ids": token_ids, "type": "market" }).to_string())
+}
+
+#[derive(Deserialize)]
+struct Level {
+    price: String,
+}
+
+#[derive(Deserialize)]
+struct PriceChange {
+    asset_id: String,
+    #[serde(default)]
+    best_bid: Option<String>,
+    #[serde(default)]
+    best_ask: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "event_type", rename_all = "snake_case")]
+enum MarketEvent {
+    Book {
+        asset_id: String,
+        #[serde(default, alias = "buys")]
+        bids: Vec<Level>,
+        #[serde(default, alias = "sells")]
+        asks: Vec<Level>,
+    },
+    PriceChange {
+        #[serde(default)]
+        price_changes: Vec<PriceChange>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Events {
+    Many(Vec<MarketEvent>),
+    One(MarketEvent),
+}
+
+/// Midpoint of a best bid and ask, or whichever side exists.
+fn mid(bid: Option<Decimal>, ask: Option<Decimal>) -> Option<Decimal> {
+    match (bid, ask) {
+        (Some(bid), Some(ask)) => Some((bid + ask) / Decimal::TWO),
+        (bid, ask) => bid.or(ask),
+    }
+}
+
+fn parse(price: &str) -> Option<Decimal> {
+    price.parse().ok().filter(|p: &Decimal| !p.is_zero())
+}
+
+/// `(token_id, mid)` for each price a market-channel message carries.
+fn mids(text: &str) -> Vec<(String, Decimal)> {
+    let events = match serde_json::from_str(text) {
+        Ok(Events::Many(events)) => events,
+        Ok(Events::One(event)) => vec![event],
+        Err(e) => {
+            // Includes the server's "PONG"
+            debug!(error = %e, "Ignoring unparseable market stream message");
+            return Vec::new();
+        }
+    };
+
+    let mut out = Vec::new();
+    for event in events {
+        match event {
+            MarketEvent::Book { asset_id, bids, asks } => {
+                // Don't rely on level order: take the best of each side
+                let bid = bids.iter().filter_map(|l| parse(&l.price)).max();
+                let ask = asks.iter().filter_map(|l| parse(&l.price)).min();
+                out.extend(mid(bid, ask).map(|m| (asset_id, m)));
+            }
+            MarketEvent::PriceChange { price_changes } => {
+                for change in price_changes {
+                    let bid = change.best_bid.as_deref().and_then(parse);
+                    let ask = change.best_ask.as_deref().and_then(parse);
+                    out.extend(mid(bid, ask).map(|m| (change.asset_id, m)));
+                }
+            }
+            MarketEvent::Other => {}
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_mids_from_book_and_price_change_events() {
+        let text = r#"[
+            {"event_type": "book", "asset_id": "111", "market": "0xm",
+             "bids": [{"price": "0.40", "size": "10"}, {"price": "0.44", "size": "5"}],
+             "asks": [{"price": "0.52", "size": "10"}, {"price": "0.48", "size": "5"}]},
+            {"event_type": "tick_size_change", "asset_id": "111"}
+        ]"#;
+        assert_eq!(mids(text), [("111".to_string(), dec!(0.46))]);
+
+        let text = r#"{"event_type": "price_change", "market": "0xm", "price_changes": [
+            {"asset_id": "111", "price": "0.5", "side": "BUY", "best_bid": "0.5", "best_ask": "0.54"},
+            {"asset_id": "222", "price": "0.5", "side": "SELL", "best_bid": "0", "best_ask": "0.47"}
+        ]}"#;
+        assert_eq!(mids(text), [("111".to_string(), dec!(0.52)), ("222".to_string(), dec!(0.47))]);
+
+        assert!(mids("PONG").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stream_resubscribes_after_disconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let book = |bid: &str, ask: &str| {
+            Message::Text(
+                json!({ "event_type": "book", "asset_id": "111", "bids": [{ "price": bid }], "asks": [{ "price": ask }] })
+                    .to_string(),
+            )
+        };
+
+        let server = tokio::spawn(async move {
+            for (bid, ask) in [("0.40", "0.42"), ("0.60", "0.62")] {
+                let (tcp, _) = listener.accept().await.unwrap();
+                let mut socket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+                // Answer the subscription with a book, then hang up
+                let subscribe = loop {
+                    match socket.next().await.unwrap().unwrap() {
+                        Message::Text(text) if text.contains("assets_ids") => break text,
+                        _ => continue,
+                    }
+                };
+                assert!(subscribe.contains("111"));
+                socket.send(book(bid, ask)).await.unwrap();
+                socket.close(None).await.ok();
+            }
+        });
+
+        let stream = MarketStream::spawn_with_backoff(url, Duration::from_millis(10));
+        stream.subscribe(["111".to_string()]).await;
+
+        let mut latest = None;
+        for _ in 0..200 {
+            latest = stream.mid_price("111").await.map(|(mid, _)| mid);
+            if latest == Some(dec!(0.61)) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(latest, Some(dec!(0.61)));
+        server.await.unwrap();
+    }
+}
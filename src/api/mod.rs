@@ -2,8 +2,16 @@
 
 mod clob_client;
 mod data_client;
+mod market_stream;
+mod pricing;
+mod signer;
 mod types;
 
-pub use clob_client::{ClobClient, OrderSide, OrderType, OrderResponse, OrderStatus, MarketInfo};
+pub use clob_client::{
+    round_to_lot, AssociateTrade, ClobClient, MarketInfo, OrderBook, OrderExecutor, OrderResponse,
+    OrderSide, OrderStatus, OrderType, SHARE_LOT_SIZE,
+};
 pub use data_client::DataClient;
+pub use market_stream::{MarketStream, MARKET_WS_URL};
+pub use pricing::{fetch_prices, PriceChain, PriceSource, Quote, DEFAULT_PRICE_CONCURRENCY, DEFAULT_PRICE_SOURCES};
 pub use types::*;
@@ -0,0 +1,229 @@
+//! Price sourcing shared by the live bot and paper trading: an ordered fallback
+//! chain of price sources, and concurrent fetching across many markets.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, Utc};
+use futures::stream::{self, StreamExt};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+/// Default number of price requests in flight at once.
+pub const DEFAULT_PRICE_CONCURRENCY: usize = 8;
+
+/// Where a price can come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceSource {
+    /// Mid from the CLOB websocket market feed
+    Stream,
+    /// Mid of the CLOB order book
+    ClobBook,
+    /// `cur_price` of a tracked trader's position, from the Data API
+    DataApi,
+    /// Last price stored with our own position
+    LastStored,
+}
+
+impl std::fmt::Display for PriceSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Stream => "stream",
+            Self::ClobBook => "clob_book",
+            Self::DataApi => "data_api",
+            Self::LastStored => "last_stored",
+        })
+    }
+}
+
+impl PriceSource {
+    /// Whether the source reads the market itself, rather than a secondhand or
+    /// remembered price. Only these may price an entry.
+    pub fn is_market(self) -> bool {
+        matches!(self, Self::Stream | Self::ClobBook)
+    }
+}
+
+/// Default fallback order.
+pub const DEFAULT_PRICE_SOURCES: [PriceSource; 4] =
+    [PriceSource::Stream, PriceSource::ClobBook, PriceSource::DataApi, PriceSource::LastStored];
+
+/// A price and the source that served it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quote {
+    pub price: Decimal,
+    pub source: PriceSource,
+    /// Older than the chain's staleness bound
+    pub stale: bool,
+}
+
+/// Price sources tried in order, with a bound on how old a price may be.
+#[derive(Debug, Clone)]
+pub struct PriceChain {
+    sources: Vec<PriceSource>,
+    max_staleness: Duration,
+}
+
+impl PriceChain {
+    pub fn new(sources: Vec<PriceSource>, max_staleness: Duration) -> Self {
+        Self { sources, max_staleness }
+    }
+
+    /// The same chain restricted to sources that read the market itself.
+    pub fn market_only(&self) -> Self {
+        let sources = self.sources.iter().copied().filter(|s| s.is_market()).collect();
+        Self { sources, max_staleness: self.max_staleness }
+    }
+
+    /// Ask each source in order for a `(price, as_of)` and return the first fresh one.
+    /// A price older than the staleness bound is used only if no source has a fresh one,
+    /// and is flagged stale.
+    pub async fn quote<F, Fut>(&self, key: &str, fetch: F) -> Result<Quote>
+    where
+        F: Fn(PriceSource) -> Fut,
+        Fut: Future<Output = Result<(Decimal, DateTime<Utc>)>>,
+    {
+        let mut fallback = None;
+        for &source in &self.sources {
+            match fetch(source).await {
+                Ok((price, as_of)) if Utc::now() - as_of <= self.max_staleness => {
+                    debug!(key = %key, source = %source, price = %price, "Price served");
+                    return Ok(Quote { price, source, stale: false });
+                }
+                Ok((price, as_of)) => {
+                    debug!(key = %key, source = %source, as_of = %as_of, "Price is stale, trying next source");
+                    fallback = fallback.or(Some(Quote { price, source, stale: true }));
+                }
+                Err(e) => debug!(key = %key, source = %source, error = %e, "Price source failed"),
+            }
+        }
+
+        match fallback {
+            Some(quote) => {
+                debug!(key = %key, source = %quote.source, price = %quote.price, "Stale price served");
+                Ok(quote)
+            }
+            None => bail!("No price source has a price for {}", key),
+        }
+    }
+}
+
+/// Fetch a price for every key, with at most `concurrency` requests in flight.
+///
+/// Keys whose fetch fails are logged and left out of the returned map.
+pub async fn fetch_prices<K, F, Fut>(keys: Vec<K>, concurrency: usize, fetch: F) -> HashMap<K, Decimal>
+where
+    K: Eq + Hash + Clone + std::fmt::Debug,
+    F: Fn(K) -> Fut,
+    Fut: Future<Output = Result<Decimal>>,
+{
+    stream::iter(keys)
+        .map(|key| {
+            let price = fetch(key.clone());
+            async move { (key, price.await) }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .filter_map(|(key, price)| async move {
+            match price {
+                Ok(price) => Some((key, price)),
+                Err(e) => {
+                    warn!(key = ?key, error = %e, "Failed to fetch price");
+                    None
+                }
+            }
+        })
+        .collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use anyhow::anyhow;
+    use rust_decimal_macros::dec;
+
+    #[tokio::test]
+    async fn test_chain_falls_through_to_stored_price_flagged_stale() {
+        let chain = PriceChain::new(DEFAULT_PRICE_SOURCES.to_vec(), chrono::Duration::seconds(60));
+        let asked = std::sync::Mutex::new(Vec::new());
+
+        let quote = chain
+            .quote("0xm1:Yes", |source| {
+                asked.lock().unwrap().push(source);
+                async move {
+                    match source {
+                        PriceSource::Stream => Err(anyhow!("stream not connected")),
+                        PriceSource::ClobBook => Err(anyhow!("book unavailable")),
+                        PriceSource::DataApi => Err(anyhow!("data api timeout")),
+                        PriceSource::LastStored => Ok((dec!(0.42), Utc::now() - chrono::Duration::minutes(10))),
+                    }
+                }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(quote, Quote { price: dec!(0.42), source: PriceSource::LastStored, stale: true });
+        assert_eq!(*asked.lock().unwrap(), DEFAULT_PRICE_SOURCES.to_vec());
+
+        // A fresh price later in the chain beats a stale one earlier
+        let quote = chain
+            .quote("0xm1:Yes", |source| async move {
+                match source {
+                    PriceSource::Stream => Err(anyhow!("stream not connected")),
+                    PriceSource::ClobBook => Err(anyhow!("book unavailable")),
+                    PriceSource::DataApi => Ok((dec!(0.40), Utc::now() - chrono::Duration::hours(1))),
+                    PriceSource::LastStored => Ok((dec!(0.45), Utc::now())),
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!((quote.source, quote.stale), (PriceSource::LastStored, false));
+
+        // Entries are priced from the market alone
+        let err = chain
+            .market_only()
+            .quote("0xm1:Yes", |source| async move {
+                match source {
+                    PriceSource::Stream | PriceSource::ClobBook => Err(anyhow!("book unavailable")),
+                    PriceSource::DataApi | PriceSource::LastStored => Ok((dec!(0.45), Utc::now())),
+                }
+            })
+            .await;
+        assert!(err.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_prices_returns_every_price_with_bounded_parallelism() {
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let keys: Vec<u32> = (0..20).collect();
+
+        let prices = fetch_prices(keys, 4, |key| {
+            let (in_flight, peak) = (&in_flight, &peak);
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                if key == 7 {
+                    bail!("no book");
+                }
+                Ok(Decimal::from(key) / dec!(100))
+            }
+        })
+        .await;
+
+        assert_eq!(prices.len(), 19);
+        assert_eq!(prices[&3], dec!(0.03));
+        assert!(!prices.contains_key(&7));
+        assert!(peak.load(Ordering::SeqCst) <= 4);
+        assert!(peak.load(Ordering::SeqCst) > 1);
+    }
+}
@@ -0,0 +1,62 @@
+//! Loading the order-signing key, from an encrypted keystore or the environment.
+
+use std::path::Path;
+use std::str::FromStr;
+
+use alloy_signer_local::PrivateKeySigner;
+use anyhow::{Context, Result};
+use tracing::warn;
+
+/// Load the signing key from the environment.
+///
+/// With `POLYMARKET_KEYSTORE_PATH` set, decrypts that JSON keystore using
+/// `POLYMARKET_KEYSTORE_PASSPHRASE`, or a passphrase prompted on the terminal when
+/// that's unset. Otherwise falls back to the plaintext `POLYMARKET_PRIVATE_KEY`.
+pub fn signer_from_env() -> Result<PrivateKeySigner> {
+    if let Ok(path) = std::env::var("POLYMARKET_KEYSTORE_PATH") {
+        let passphrase = match std::env::var("POLYMARKET_KEYSTORE_PASSPHRASE") {
+            Ok(passphrase) => passphrase,
+            Err(_) => rpassword::prompt_password(format!("Passphrase for {}: ", path))
+                .context("Failed to read keystore passphrase")?,
+        };
+        return decrypt_keystore(&path, &passphrase);
+    }
+
+    let private_key = std::env::var("POLYMARKET_PRIVATE_KEY")
+        .context("Neither POLYMARKET_KEYSTORE_PATH nor POLYMARKET_PRIVATE_KEY is set")?;
+    warn!("Using plaintext POLYMARKET_PRIVATE_KEY; set POLYMARKET_KEYSTORE_PATH to use an encrypted keystore instead");
+    parse_private_key(&private_key)
+}
+
+/// Decrypt a passphrase-protected JSON keystore.
+pub fn decrypt_keystore(path: impl AsRef<Path>, passphrase: &str) -> Result<PrivateKeySigner> {
+    let path = path.as_ref();
+    PrivateKeySigner::decrypt_keystore(path, passphrase)
+        .with_context(|| format!("Failed to decrypt keystore {}", path.display()))
+}
+
+/// Parse a hex private key, with or without the 0x prefix.
+pub fn parse_private_key(private_key: &str) -> Result<PrivateKeySigner> {
+    let pk = private_key.strip_prefix("0x").unwrap_or(private_key);
+    PrivateKeySigner::from_str(pk).context("Invalid private key")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keystore_decrypts_to_expected_address() {
+        let dir = std::env::temp_dir().join(format!("polycopier-keystore-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let key = hex::decode(crate::testing::TEST_PRIVATE_KEY).unwrap();
+        PrivateKeySigner::encrypt_keystore(&dir, &mut rand::thread_rng(), key, "correct horse", Some("key.json"))
+            .unwrap();
+
+        let signer = decrypt_keystore(dir.join("key.json"), "correct horse").unwrap();
+        assert_eq!(signer.address().to_string(), "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+        assert!(decrypt_keystore(dir.join("key.json"), "wrong passphrase").is_err());
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
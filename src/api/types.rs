@@ -66,17 +66,11 @@ pub struct TradeResponse {
     #[serde(default)]
     pub title: String,
     #[serde(default)]
-    pub slug: String,
-    #[serde(default)]
     pub outcome: String,
     #[serde(default)]
     pub outcome_index: i32,
     #[serde(default)]
     pub transaction_hash: String,
-    #[serde(default)]
-    pub pseudonym: String,
-    #[serde(default)]
-    pub profile_image: String,
 }
 
 /// Activity response from /activity endpoint.
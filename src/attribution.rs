@@ -0,0 +1,167 @@
+//! Realized P&L of closed positions grouped by source trader or market category.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use tracing::warn;
+
+use crate::api::ClobClient;
+use crate::db::{Database, StoredPosition};
+use crate::display;
+use crate::output::Report;
+
+/// Group for positions whose market has no known category.
+pub const UNCATEGORIZED: &str = "uncategorized";
+
+/// What to group realized P&L by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum AttributionKey {
+    /// The source trader each position copied
+    #[default]
+    Trader,
+    /// The market's category on Gamma (politics, sports, crypto, ...)
+    Category,
+}
+
+impl std::fmt::Display for AttributionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Trader => "trader",
+            Self::Category => "category",
+        })
+    }
+}
+
+/// Realized P&L of the closed positions in one group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributionRow {
+    pub key: String,
+    pub trades: usize,
+    pub wins: usize,
+    pub realized_pnl: f64,
+}
+
+impl AttributionRow {
+    pub fn win_rate(&self) -> f64 {
+        if self.trades == 0 {
+            return 0.0;
+        }
+        self.wins as f64 / self.trades as f64
+    }
+}
+
+/// Group closed positions by `key_of`, largest realized P&L first.
+pub fn attribute(closed: &[StoredPosition], key_of: impl Fn(&StoredPosition) -> String) -> Vec<AttributionRow> {
+    let mut groups: HashMap<String, AttributionRow> = HashMap::new();
+    for position in closed {
+        let key = key_of(position);
+        let pnl = position.realized_pnl.unwrap_or(0.0);
+        let row = groups.entry(key.clone()).or_insert_with(|| AttributionRow {
+            key,
+            trades: 0,
+            wins: 0,
+            realized_pnl: 0.0,
+        });
+        row.trades += 1;
+        if pnl > 0.0 {
+            row.wins += 1;
+        }
+        row.realized_pnl += pnl;
+    }
+
+    let mut rows: Vec<AttributionRow> = groups.into_values().collect();
+    rows.sort_by(|a, b| b.realized_pnl.total_cmp(&a.realized_pnl).then_with(|| a.key.cmp(&b.key)));
+    rows
+}
+
+/// Category of each market in `closed`, from the database or else Gamma, caching
+/// what Gamma reports. Markets without one map to [`UNCATEGORIZED`].
+pub async fn market_categories(
+    db: &Database,
+    clob: &ClobClient,
+    closed: &[StoredPosition],
+) -> Result<HashMap<String, String>> {
+    let mut categories = HashMap::new();
+    for position in closed {
+        if categories.contains_key(&position.market_id) {
+            continue;
+        }
+        let category = match db.get_market_category(&position.market_id).await? {
+            Some(category) => category,
+            None => match clob.get_market(&position.market_id).await {
+                Ok(market) => match market.category.map(|c| c.trim().to_lowercase()).filter(|c| !c.is_empty()) {
+                    Some(category) => {
+                        db.save_market_category(&position.market_id, &category).await?;
+                        category
+                    }
+                    None => UNCATEGORIZED.to_string(),
+                },
+                Err(e) => {
+                    warn!(market = %position.market_id, error = %e, "Could not fetch market category");
+                    UNCATEGORIZED.to_string()
+                }
+            },
+        };
+        categories.insert(position.market_id.clone(), category);
+    }
+    Ok(categories)
+}
+
+/// Attribution rows as a report.
+pub fn attribution_report(by: AttributionKey, rows: &[AttributionRow]) -> Report {
+    let mut report = Report::new(format!("P&L Attribution by {}", by));
+    let realized: f64 = rows.iter().map(|r| r.realized_pnl).sum();
+    report
+        .field("Totals", "Closed Positions", rows.iter().map(|r| r.trades).sum::<usize>())
+        .field("Totals", "Realized P&L ($)", display::money(realized));
+
+    let cells = rows
+        .iter()
+        .map(|r| {
+            vec![
+                r.key.clone(),
+                r.trades.to_string(),
+                r.wins.to_string(),
+                display::pct(r.win_rate()),
+                display::money(r.realized_pnl),
+            ]
+        })
+        .collect();
+    let key = by.to_string();
+    report.table("Groups", &[key.as_str(), "trades", "wins", "win_rate_pct", "realized_pnl"], cells);
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_category_attribution_subtotals_each_category() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        let db = Database::new(&crate::testing::temp_database_url()).await.unwrap();
+        db.save_trader("0xabc", "", 1.0).await.unwrap();
+        db.save_market_category("0xelection", "politics").await.unwrap();
+        db.save_market_category("0xsenate", "politics").await.unwrap();
+        db.save_market_category("0xfinal", "sports").await.unwrap();
+
+        // Politics: +$10 and -$5; sports: +$20
+        for (market, exit) in [("0xelection", 0.60), ("0xsenate", 0.45), ("0xfinal", 0.70)] {
+            db.save_position(market, "", "Yes", "BUY", 100.0, 0.50, Some("0xabc")).await.unwrap();
+            db.update_position_price(market, "Yes", exit).await.unwrap();
+            db.close_position(market, "Yes", 0.0, "take_profit").await.unwrap();
+        }
+        let closed = db.get_closed_positions().await.unwrap();
+
+        let categories = market_categories(&db, &mock.clob_client(), &closed).await.unwrap();
+        assert_eq!(mock.market_fetches().await, 0);
+        let rows = attribute(&closed, |p| categories[&p.market_id].clone());
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!((rows[0].key.as_str(), rows[0].trades, rows[0].wins), ("sports", 1, 1));
+        assert!((rows[0].realized_pnl - 20.0).abs() < 1e-9);
+        assert_eq!((rows[1].key.as_str(), rows[1].trades, rows[1].wins), ("politics", 2, 1));
+        assert!((rows[1].realized_pnl - 5.0).abs() < 1e-9);
+        assert_eq!(rows[1].win_rate(), 0.5);
+    }
+}
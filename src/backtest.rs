@@ -3,21 +3,38 @@
 //! Features:
 //! - Replay historical trades from tracked traders
 //! - Apply strategy rules and position sizing
-//! - Track simulated P&L and positions
+//! - Track simulated P&L and positions, optionally settling those held to market resolution
 //! - Calculate performance statistics
 
-use std::collections::HashMap;
+mod cache;
+mod diff;
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Duration, Utc};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal_macros::dec;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
-use crate::api::DataClient;
-use crate::models::{Trade, TradeSide};
-use crate::trading::{PositionSizer, PortfolioState, Strategy, StrategyConfig, StrategyPosition, TradingConfig};
+use crate::api::{ClobClient, DataClient, DEFAULT_PRICE_CONCURRENCY};
+use crate::clock::{Clock, SystemClock};
+use crate::display;
+use crate::metrics::{self, RatioBasis, BACKTEST_PERIODS_PER_YEAR};
+use crate::models::{Position, Trade, TradeSide};
+use crate::output::Report;
+use crate::trading::{
+    EntryQuote, PositionSizer, PortfolioState, SourceHedgePolicy, Strategy, StrategyConfig, StrategyPosition, TradingConfig,
+};
+
+use cache::{TradeCache, DEFAULT_CACHE_MAX_AGE_HOURS};
+pub use cache::DEFAULT_CACHE_DIR;
+pub use diff::BacktestDiff;
 
 /// Backtesting configuration.
 #[derive(Debug, Clone)]
@@ -39,6 +56,30 @@ pub struct BacktestConfig {
 
     /// Number of historical trades to fetch per trader
     pub lookback_trades: u32,
+
+    /// Only simulate trades in this market (condition ID)
+    pub market_filter: Option<String>,
+
+    /// Only simulate trades on this outcome (case-insensitive)
+    pub outcome_filter: Option<String>,
+
+    /// Directory for cached trade histories (None = always fetch)
+    pub cache_dir: Option<PathBuf>,
+
+    /// How long a cached trade history stays fresh
+    pub cache_max_age: Duration,
+
+    /// Re-fetch trade histories even when cached, then update the cache
+    pub refresh_cache: bool,
+
+    /// Whether Sharpe/Sortino are reported annualized or raw
+    pub ratio_basis: RatioBasis,
+
+    /// Settle positions still open when their market resolves, at 1.0 or 0.0
+    pub settle_at_resolution: bool,
+
+    /// Report per-trade returns net of entry and exit fees instead of as the gross price move
+    pub net_trade_returns: bool,
 }
 
 impl Default for BacktestConfig {
@@ -50,10 +91,137 @@ impl Default for BacktestConfig {
             slippage: dec!(0.005),  // 0.5% slippage
             fee_rate: dec!(0.001),  // 0.1% fee
             lookback_trades: 500,
+            market_filter: None,
+            outcome_filter: None,
+            cache_dir: None,
+            cache_max_age: Duration::hours(DEFAULT_CACHE_MAX_AGE_HOURS),
+            refresh_cache: false,
+            ratio_basis: RatioBasis::Annualized,
+            settle_at_resolution: false,
+            net_trade_returns: false,
         }
     }
 }
 
+impl BacktestConfig {
+    /// Keep only trades matching the market/outcome filters, returning them with the excluded count.
+    pub fn filter_trades<T>(&self, trades: Vec<T>, trade_of: impl Fn(&T) -> &Trade) -> (Vec<T>, usize) {
+        let total = trades.len();
+        let kept: Vec<T> = trades
+            .into_iter()
+            .filter(|t| {
+                let trade = trade_of(t);
+                !matches!(&self.market_filter, Some(m) if !trade.market_id.eq_ignore_ascii_case(m))
+                    && !matches!(&self.outcome_filter, Some(o) if !trade.outcome.eq_ignore_ascii_case(o))
+            })
+            .collect();
+        let excluded = total - kept.len();
+        (kept, excluded)
+    }
+
+    /// Check configuration invariants, reporting every problem found.
+    pub fn validate(&self) -> Result<()> {
+        let strategy = &self.strategy_config;
+        let mut problems = Vec::new();
+
+        if self.initial_capital <= Decimal::ZERO {
+            problems.push(format!("initial_capital must be positive (got {})", self.initial_capital));
+        }
+        if self.lookback_trades == 0 {
+            problems.push("lookback_trades must be at least 1".to_string());
+        }
+        if self.slippage < Decimal::ZERO || self.slippage >= Decimal::ONE {
+            problems.push(format!("slippage must be in [0, 1) (got {})", self.slippage));
+        }
+        if self.fee_rate < Decimal::ZERO || self.fee_rate >= Decimal::ONE {
+            problems.push(format!("fee_rate must be in [0, 1) (got {})", self.fee_rate));
+        }
+        if strategy.stop_loss_pct <= Decimal::ZERO || strategy.stop_loss_pct >= Decimal::ONE {
+            problems.push(format!("stop_loss_pct must be in (0, 1) (got {})", strategy.stop_loss_pct));
+        }
+        if strategy.take_profit_pct <= Decimal::ZERO {
+            problems.push(format!("take_profit_pct must be positive (got {})", strategy.take_profit_pct));
+        }
+        if strategy.min_entry_price < Decimal::ZERO || strategy.max_entry_price > Decimal::ONE {
+            problems.push(format!(
+                "entry prices must be within [0, 1] (got {} - {})",
+                strategy.min_entry_price, strategy.max_entry_price
+            ));
+        }
+        if strategy.min_entry_price >= strategy.max_entry_price {
+            problems.push(format!(
+                "min_entry_price must be below max_entry_price ({} >= {})",
+                strategy.min_entry_price, strategy.max_entry_price
+            ));
+        }
+        if strategy.max_entry_slippage < Decimal::ZERO {
+            problems.push(format!("max_entry_slippage must be non-negative (got {})", strategy.max_entry_slippage));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "Invalid backtest configuration:\n  - {}",
+                problems.join("\n  - ")
+            ))
+        }
+    }
+}
+
+/// Key for a simulated position, and for its price in price maps: each outcome of a
+/// market is a separate token with its own price.
+pub fn position_key(market_id: &str, outcome: &str) -> String {
+    format!("{}:{}", market_id, outcome)
+}
+
+/// Net shares each source holds per market outcome, from its holdings snapshot and the
+/// trades seen since.
+#[derive(Debug, Default)]
+struct SourceHoldings(HashMap<(String, String), Decimal>);
+
+impl SourceHoldings {
+    /// Start a source from its holdings snapshot, as it stood before `later` trades were made.
+    ///
+    /// Shares can't be held short, so outcomes the snapshot is missing start at zero.
+    fn seed(&mut self, trader: &str, held: &[Position], later: &[&Trade]) {
+        let mut net: HashMap<String, Decimal> = held
+            .iter()
+            .map(|p| (position_key(&p.market_id, &p.outcome), p.size))
+            .collect();
+        for trade in later {
+            *net.entry(position_key(&trade.market_id, &trade.outcome)).or_default() -= signed_size(trade);
+        }
+        for (key, size) in net {
+            self.0.insert((trader.to_string(), key), size.max(Decimal::ZERO));
+        }
+    }
+
+    /// Record a source's trade, returning whether it shrank that source's net position.
+    fn record(&mut self, trader: &str, trade: &Trade) -> bool {
+        let net = self
+            .0
+            .entry((trader.to_string(), position_key(&trade.market_id, &trade.outcome)))
+            .or_default();
+        let before = *net;
+        *net += signed_size(trade);
+        net.abs() < before.abs()
+    }
+}
+
+/// A trade's change to its trader's holdings of the traded outcome.
+fn signed_size(trade: &Trade) -> Decimal {
+    match trade.side {
+        TradeSide::Buy => trade.size,
+        TradeSide::Sell => -trade.size,
+    }
+}
+
+/// Latest replayed price of a position's own outcome, or its entry price before any.
+fn mark_price(last_prices: &HashMap<String, Decimal>, key: &str, pos: &SimulatedPosition) -> Decimal {
+    last_prices.get(key).copied().unwrap_or(pos.entry_price)
+}
+
 /// A simulated position during backtesting.
 #[derive(Debug, Clone)]
 pub struct SimulatedPosition {
@@ -64,6 +232,8 @@ pub struct SimulatedPosition {
     pub entry_price: Decimal,
     pub entry_time: DateTime<Utc>,
     pub source_trader: String,
+    /// Fee paid on top of the entry cost
+    pub entry_fee: Decimal,
 }
 
 impl SimulatedPosition {
@@ -75,6 +245,14 @@ impl SimulatedPosition {
         }
     }
 
+    /// Value of the position at a given price: the cash paid at entry plus P&L.
+    ///
+    /// This is what closing at `current_price` pays back, before the exit fee; the entry
+    /// fee was paid on top of the entry cost and is never returned.
+    pub fn value_at(&self, current_price: Decimal) -> Decimal {
+        self.size * self.entry_price + self.pnl_at(current_price)
+    }
+
     /// Calculate return percentage.
     pub fn return_pct(&self, current_price: Decimal) -> Decimal {
         if self.entry_price.is_zero() {
@@ -85,10 +263,26 @@ impl SimulatedPosition {
             TradeSide::Sell => (self.entry_price - current_price) / self.entry_price,
         }
     }
+
+    /// Return on the cash committed (entry cost plus entry fee) after both fees.
+    pub fn net_return_pct(&self, current_price: Decimal, exit_fee: Decimal) -> Decimal {
+        let committed = self.size * self.entry_price + self.entry_fee;
+        if committed.is_zero() {
+            return Decimal::ZERO;
+        }
+        (self.pnl_at(current_price) - self.entry_fee - exit_fee) / committed
+    }
 }
 
-/// A completed trade in the backtest.
+/// How a market resolved, for settling positions held to resolution.
 #[derive(Debug, Clone)]
+struct Resolution {
+    winner: String,
+    resolved_at: DateTime<Utc>,
+}
+
+/// A completed trade in the backtest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BacktestTrade {
     pub market_id: String,
     pub outcome: String,
@@ -105,7 +299,7 @@ pub struct BacktestTrade {
 }
 
 /// Backtest results summary.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BacktestResults {
     /// Initial capital
     pub initial_capital: Decimal,
@@ -143,9 +337,16 @@ pub struct BacktestResults {
     /// Sharpe ratio (annualized)
     pub sharpe_ratio: f64,
 
-    /// Sortino ratio
+    /// Sortino ratio (annualized)
     pub sortino_ratio: f64,
 
+    /// Basis the ratios are reported in
+    pub ratio_basis: RatioBasis,
+
+    /// Whether per-trade `return_pct` is net of fees (false = gross price move)
+    #[serde(default)]
+    pub net_trade_returns: bool,
+
     /// Average holding period in hours
     pub avg_holding_hours: f64,
 
@@ -161,6 +362,12 @@ pub struct BacktestResults {
     /// Trades skipped due to strategy rules
     pub skipped_trades: usize,
 
+    /// Skipped trade counts by reason
+    pub skip_reasons: HashMap<String, usize>,
+
+    /// Trades excluded by market/outcome filters before simulation
+    pub filtered_trades: usize,
+
     /// Start time of backtest period
     pub start_time: DateTime<Utc>,
 
@@ -176,24 +383,45 @@ impl std::fmt::Display for BacktestResults {
             self.start_time.format("%Y-%m-%d"),
             self.end_time.format("%Y-%m-%d"))?;
         writeln!(f)?;
+        if self.no_trades_taken() {
+            writeln!(f, "No trades taken: the strategy never opened a position.")?;
+            writeln!(f, "Capital unchanged at ${}", display::money(self.final_capital))?;
+            writeln!(f)?;
+            writeln!(f, "Skipped:     {}", self.skipped_trades)?;
+            if self.filtered_trades > 0 {
+                writeln!(f, "Filtered:    {} outside market/outcome filter", self.filtered_trades)?;
+            }
+            for (reason, count) in self.sorted_skip_reasons() {
+                writeln!(f, "  Skipped {:>5}  {}", count, reason)?;
+            }
+            writeln!(f, "{:=^60}", "")?;
+            return Ok(());
+        }
         writeln!(f, "--- Capital ---")?;
-        writeln!(f, "Initial:     ${:.2}", self.initial_capital)?;
-        writeln!(f, "Final:       ${:.2}", self.final_capital)?;
-        writeln!(f, "Return:      {:.2}%", self.total_return_pct * dec!(100))?;
-        writeln!(f, "Fees Paid:   ${:.2}", self.total_fees)?;
+        writeln!(f, "Initial:     ${}", display::money(self.initial_capital))?;
+        writeln!(f, "Final:       ${}", display::money(self.final_capital))?;
+        writeln!(f, "Return:      {}%", display::pct(self.total_return_pct))?;
+        writeln!(f, "Fees Paid:   ${}", display::money(self.total_fees))?;
         writeln!(f)?;
         writeln!(f, "--- Trades ---")?;
         writeln!(f, "Total:       {} ({} skipped)", self.total_trades, self.skipped_trades)?;
-        writeln!(f, "Winners:     {} ({:.1}%)", self.winning_trades, self.win_rate * 100.0)?;
+        if self.filtered_trades > 0 {
+            writeln!(f, "Filtered:    {} outside market/outcome filter", self.filtered_trades)?;
+        }
+        for (reason, count) in self.sorted_skip_reasons() {
+            writeln!(f, "  Skipped {:>5}  {}", count, reason)?;
+        }
+        writeln!(f, "Winners:     {} ({}%)", self.winning_trades, display::pct(self.win_rate))?;
         writeln!(f, "Losers:      {}", self.losing_trades)?;
-        writeln!(f, "Avg Win:     ${:.2}", self.avg_win)?;
-        writeln!(f, "Avg Loss:    ${:.2}", self.avg_loss)?;
-        writeln!(f, "Profit Factor: {:.2}", self.profit_factor)?;
+        writeln!(f, "Avg Win:     ${}", display::money(self.avg_win))?;
+        writeln!(f, "Avg Loss:    ${}", display::money(self.avg_loss))?;
+        writeln!(f, "Profit Factor: {}", display::profit_factor(self.profit_factor))?;
         writeln!(f)?;
         writeln!(f, "--- Risk Metrics ---")?;
-        writeln!(f, "Max Drawdown: {:.2}%", self.max_drawdown_pct * 100.0)?;
-        writeln!(f, "Sharpe Ratio: {:.2}", self.sharpe_ratio)?;
-        writeln!(f, "Sortino Ratio: {:.2}", self.sortino_ratio)?;
+        writeln!(f, "Max Drawdown: {}%", display::pct(self.max_drawdown_pct))?;
+        for (label, value) in self.reported_ratios() {
+            writeln!(f, "{}: {:.2}", label, value)?;
+        }
         writeln!(f)?;
         writeln!(f, "--- Timing ---")?;
         writeln!(f, "Avg Hold:    {:.1} hours", self.avg_holding_hours)?;
@@ -202,10 +430,103 @@ impl std::fmt::Display for BacktestResults {
     }
 }
 
+impl BacktestResults {
+    /// Whether the strategy never opened a position, so the results are empty
+    /// rather than a flat strategy's.
+    pub fn no_trades_taken(&self) -> bool {
+        self.total_trades == 0
+    }
+
+    /// Skip reasons, most frequent first.
+    fn sorted_skip_reasons(&self) -> Vec<(&String, &usize)> {
+        let mut reasons: Vec<_> = self.skip_reasons.iter().collect();
+        reasons.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        reasons
+    }
+
+    /// Basis of per-trade `return_pct`, for labelling it.
+    pub fn trade_return_basis(&self) -> &'static str {
+        if self.net_trade_returns { "net" } else { "gross" }
+    }
+
+    /// Sharpe and Sortino in the configured basis, with labels naming it.
+    fn reported_ratios(&self) -> [(String, f64); 2] {
+        let basis = self.ratio_basis;
+        [("Sharpe Ratio", self.sharpe_ratio), ("Sortino Ratio", self.sortino_ratio)]
+            .map(|(name, ratio)| {
+                (basis.label(name, BACKTEST_PERIODS_PER_YEAR), basis.rescale(ratio, BACKTEST_PERIODS_PER_YEAR))
+            })
+    }
+
+    /// Summary and trade list as a report for `--output`.
+    pub fn to_report(&self) -> Report {
+        let mut report = Report::new("Backtest Results");
+        report
+            .field("Period", "Start", self.start_time.format("%Y-%m-%d"))
+            .field("Period", "End", self.end_time.format("%Y-%m-%d"))
+            .field("Capital", "Initial ($)", display::money(self.initial_capital))
+            .field("Capital", "Final ($)", display::money(self.final_capital))
+            .field("Capital", "Return (%)", display::pct(self.total_return_pct))
+            .field("Capital", "Fees Paid ($)", display::money(self.total_fees))
+            .field("Trades", "Total", self.total_trades)
+            .field("Trades", "Skipped", self.skipped_trades)
+            .field("Trades", "Filtered", self.filtered_trades)
+            .field("Trades", "Winners", self.winning_trades)
+            .field("Trades", "Losers", self.losing_trades)
+            .field("Trades", "Win Rate (%)", display::pct(self.win_rate))
+            .field("Trades", "Avg Win ($)", display::money(self.avg_win))
+            .field("Trades", "Avg Loss ($)", display::money(self.avg_loss))
+            .field("Trades", "Profit Factor", display::profit_factor(self.profit_factor))
+            .field("Risk", "Max Drawdown (%)", display::pct(self.max_drawdown_pct))
+            .field("Timing", "Avg Hold (hours)", format!("{:.1}", self.avg_holding_hours));
+        for (label, value) in self.reported_ratios() {
+            report.field("Risk", &label, format!("{:.2}", value));
+        }
+        let mut reasons: Vec<_> = self.skip_reasons.iter().collect();
+        reasons.sort();
+        for (reason, count) in reasons {
+            report.field("Skip Reasons", reason, count);
+        }
+
+        let rows = self
+            .trades
+            .iter()
+            .map(|t| {
+                vec![
+                    t.market_id.clone(),
+                    t.outcome.clone(),
+                    format!("{:?}", t.side),
+                    t.size.to_string(),
+                    t.entry_price.to_string(),
+                    t.exit_price.to_string(),
+                    t.entry_time.to_rfc3339(),
+                    t.exit_time.to_rfc3339(),
+                    display::money(t.pnl),
+                    display::pct(t.return_pct),
+                    t.exit_reason.clone(),
+                    t.source_trader.clone(),
+                ]
+            })
+            .collect();
+        let return_column = if self.net_trade_returns { "net_return_pct" } else { "gross_return_pct" };
+        report.table(
+            "Completed Trades",
+            &[
+                "market_id", "outcome", "side", "size", "entry_price", "exit_price",
+                "entry_time", "exit_time", "pnl", return_column, "exit_reason", "source_trader",
+            ],
+            rows,
+        );
+        report
+    }
+}
+
 /// Backtesting engine.
 pub struct Backtester {
     config: BacktestConfig,
     data_client: DataClient,
+    clob_client: Option<ClobClient>,
+    cache: Option<TradeCache>,
     strategy: Strategy,
     position_sizer: PositionSizer,
 }
@@ -213,25 +534,62 @@ pub struct Backtester {
 impl Backtester {
     /// Create a new backtester.
     pub fn new(config: BacktestConfig) -> Result<Self> {
-        let data_client = DataClient::new()?;
-        let strategy = Strategy::new(config.strategy_config.clone());
+        Self::with_client(config, DataClient::new()?)
+    }
+
+    /// Create a backtester that fetches history through the given client.
+    pub fn with_client(config: BacktestConfig, data_client: DataClient) -> Result<Self> {
+        config.validate()?;
+
+        let cache = config.cache_dir.as_ref().map(|dir| TradeCache::new(dir, config.cache_max_age));
+        let strategy = Strategy::new(config.strategy_config.clone())
+            .with_min_trades_for_score(config.trading_config.min_trades_for_score);
         let position_sizer = PositionSizer::new(config.trading_config.clone());
+        let clob_client = if config.settle_at_resolution { Some(ClobClient::read_only()?) } else { None };
 
         Ok(Self {
             config,
             data_client,
+            clob_client,
+            cache,
             strategy,
             position_sizer,
         })
     }
 
+    /// Look up market resolutions through the given client instead of the public Gamma API.
+    pub fn with_clob_client(mut self, clob_client: ClobClient) -> Self {
+        self.clob_client = Some(clob_client);
+        self
+    }
+
+    /// Fetch a trader's history, from the trade cache while it's fresh.
+    async fn fetch_trades(&self, address: &str) -> Result<Vec<Trade>> {
+        let lookback = self.config.lookback_trades;
+        let market = self.config.market_filter.as_deref();
+
+        if let Some(cache) = self.cache.as_ref().filter(|_| !self.config.refresh_cache) {
+            if let Some(trades) = cache.get(address, lookback, market) {
+                debug!(trader = %address, count = trades.len(), "Using cached trade history");
+                return Ok(trades);
+            }
+        }
+
+        let trades = self.data_client.get_trades(address, Some(lookback), market).await?;
+        if let Some(cache) = self.cache.as_ref().filter(|_| !trades.is_empty()) {
+            if let Err(e) = cache.put(address, lookback, market, &trades) {
+                warn!(trader = %address, error = %e, "Failed to cache trade history");
+            }
+        }
+        Ok(trades)
+    }
+
     /// Run a backtest for a single trader.
     pub async fn run_single_trader(&self, trader_address: &str) -> Result<BacktestResults> {
         info!(trader = %trader_address, "Starting backtest");
 
         // Fetch historical trades
-        let trades = self.data_client
-            .get_trades(trader_address, Some(self.config.lookback_trades), None)
+        let trades = self.fetch_trades(trader_address)
             .await
             .context("Failed to fetch historical trades")?;
 
@@ -241,11 +599,19 @@ impl Backtester {
 
         info!(count = trades.len(), "Fetched historical trades");
 
+        let (trades, filtered) = self.config.filter_trades(trades, |t| t);
+        if trades.is_empty() {
+            return Err(anyhow::anyhow!("No historical trades match the market/outcome filters"));
+        }
+
         // Sort trades by timestamp (oldest first)
         let mut sorted_trades = trades;
         sorted_trades.sort_by_key(|t| t.timestamp);
 
-        self.run_simulation(trader_address, &sorted_trades).await
+        let holdings = self.source_holdings(&[trader_address.to_string()], &sorted_trades, |_| trader_address).await;
+        let mut results = self.run_simulation(trader_address, &sorted_trades, holdings).await?;
+        results.filtered_trades = filtered;
+        Ok(results)
     }
 
     /// Run a backtest for multiple traders.
@@ -256,10 +622,7 @@ impl Backtester {
         let mut all_trades: Vec<(String, Trade)> = Vec::new();
 
         for address in trader_addresses {
-            match self.data_client
-                .get_trades(address, Some(self.config.lookback_trades), None)
-                .await
-            {
+            match self.fetch_trades(address).await {
                 Ok(trades) => {
                     info!(trader = %address, count = trades.len(), "Fetched trades");
                     for trade in trades {
@@ -276,6 +639,11 @@ impl Backtester {
             return Err(anyhow::anyhow!("No historical trades found"));
         }
 
+        let (mut all_trades, filtered) = self.config.filter_trades(all_trades, |(_, t)| t);
+        if all_trades.is_empty() {
+            return Err(anyhow::anyhow!("No historical trades match the market/outcome filters"));
+        }
+
         // Sort by timestamp
         all_trades.sort_by_key(|(_, t)| t.timestamp);
 
@@ -286,17 +654,124 @@ impl Backtester {
             .map(|(i, (addr, _))| (i, addr.clone()))
             .collect();
 
-        self.run_simulation_multi(&trades_only, &trader_map).await
+        let holdings = self.source_holdings(trader_addresses, &trades_only, |i| trader_map[&i].as_str()).await;
+        let mut results = self.run_simulation_multi(&trades_only, &trader_map, holdings).await?;
+        results.filtered_trades = filtered;
+        Ok(results)
+    }
+
+    /// Each trader's holdings as of the first replayed trade: their current holdings
+    /// snapshot with the replayed trades, all made since, backed out. A trader whose
+    /// snapshot fails to load starts flat.
+    async fn source_holdings<'a>(
+        &self,
+        traders: &[String],
+        trades: &[Trade],
+        trader_of: impl Fn(usize) -> &'a str,
+    ) -> SourceHoldings {
+        let mut holdings = SourceHoldings::default();
+        for address in traders {
+            let held = match self.data_client.get_positions(address, Some(500)).await {
+                Ok(held) => held,
+                Err(e) => {
+                    warn!(trader = %address, error = %e, "Failed to fetch holdings, assuming none before the window");
+                    Vec::new()
+                }
+            };
+            let later: Vec<&Trade> = trades
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| trader_of(*i) == address)
+                .map(|(_, t)| t)
+                .collect();
+            holdings.seed(address, &held, &later);
+        }
+        holdings
+    }
+
+    /// Fetch the resolution of every traded market, when settling at resolution, timed at
+    /// the market's actual close rather than its scheduled end. Unresolved markets, those
+    /// without a reported close time, and those that fail to load are left out.
+    async fn fetch_resolutions(&self, trades: &[Trade]) -> HashMap<String, Resolution> {
+        let Some(clob) = self.clob_client.as_ref().filter(|_| self.config.settle_at_resolution) else {
+            return HashMap::new();
+        };
+
+        let markets: HashSet<&str> = trades.iter().map(|t| t.market_id.as_str()).collect();
+        stream::iter(markets)
+            .map(|market_id| async move { (market_id, clob.get_market(market_id).await) })
+            .buffer_unordered(DEFAULT_PRICE_CONCURRENCY)
+            .filter_map(|(market_id, result)| async move {
+                let market = match result {
+                    Ok(market) => market,
+                    Err(e) => {
+                        warn!(market = %market_id, error = %e, "Failed to fetch market resolution");
+                        return None;
+                    }
+                };
+                let resolution = Resolution {
+                    winner: market.winning_outcome()?.to_string(),
+                    resolved_at: market.closed_time?,
+                };
+                Some((market_id.to_string(), resolution))
+            })
+            .collect()
+            .await
+    }
+
+    /// Settle open positions in markets that resolved by `now`, at 1.0 for the winning
+    /// outcome and 0.0 otherwise. Redemption pays no fee or slippage.
+    fn settle_resolved(
+        &self,
+        positions: &mut HashMap<String, SimulatedPosition>,
+        resolutions: &HashMap<String, Resolution>,
+        now: DateTime<Utc>,
+    ) -> Vec<BacktestTrade> {
+        let mut due: Vec<(DateTime<Utc>, String)> = positions
+            .iter()
+            .filter_map(|(key, p)| {
+                let resolution = resolutions.get(&p.market_id)?;
+                (resolution.resolved_at <= now).then(|| (resolution.resolved_at, key.clone()))
+            })
+            .collect();
+        due.sort();
+
+        due.into_iter()
+            .filter_map(|(resolved_at, key)| {
+                let pos = positions.remove(&key)?;
+                let won = resolutions[&pos.market_id].winner.eq_ignore_ascii_case(&pos.outcome);
+                let exit_price = if won { Decimal::ONE } else { Decimal::ZERO };
+                Some(BacktestTrade {
+                    pnl: pos.pnl_at(exit_price),
+                    return_pct: self.trade_return_pct(&pos, exit_price, Decimal::ZERO),
+                    market_id: pos.market_id,
+                    outcome: pos.outcome,
+                    side: pos.side,
+                    size: pos.size,
+                    entry_price: pos.entry_price,
+                    exit_price,
+                    entry_time: pos.entry_time,
+                    exit_time: resolved_at,
+                    source_trader: pos.source_trader,
+                    exit_reason: "Market Resolution".to_string(),
+                })
+            })
+            .collect()
     }
 
     /// Run the simulation on sorted trades.
-    async fn run_simulation(&self, trader_address: &str, trades: &[Trade]) -> Result<BacktestResults> {
+    async fn run_simulation(
+        &self,
+        trader_address: &str,
+        trades: &[Trade],
+        source_holdings: SourceHoldings,
+    ) -> Result<BacktestResults> {
         let trader_map: HashMap<usize, String> = trades.iter()
             .enumerate()
             .map(|(i, _)| (i, trader_address.to_string()))
             .collect();
 
-        self.run_simulation_multi(trades, &trader_map).await
+        self.run_simulation_multi(trades, &trader_map, source_holdings).await
     }
 
     /// Run simulation with multiple traders mapped.
@@ -304,17 +779,21 @@ impl Backtester {
         &self,
         trades: &[Trade],
         trader_map: &HashMap<usize, String>,
+        mut source_holdings: SourceHoldings,
     ) -> Result<BacktestResults> {
         let mut capital = self.config.initial_capital;
         let mut positions: HashMap<String, SimulatedPosition> = HashMap::new();
         let mut completed_trades: Vec<BacktestTrade> = Vec::new();
         let mut equity_curve: Vec<(DateTime<Utc>, Decimal)> = Vec::new();
         let mut total_fees = Decimal::ZERO;
-        let mut skipped = 0;
+        let mut skip_reasons: HashMap<String, usize> = HashMap::new();
         let mut peak_equity = capital;
         let mut max_drawdown = 0.0f64;
         let mut last_trade_time: Option<DateTime<Utc>> = None;
         let mut last_loss_time: Option<DateTime<Utc>> = None;
+        // Latest traded price per market outcome, for marking open positions
+        let mut last_prices: HashMap<String, Decimal> = HashMap::new();
+        let resolutions = self.fetch_resolutions(trades).await;
 
         let start_time = trades.first().map(|t| t.timestamp).unwrap_or_else(Utc::now);
         let end_time = trades.last().map(|t| t.timestamp).unwrap_or_else(Utc::now);
@@ -324,22 +803,44 @@ impl Backtester {
 
         for (idx, trade) in trades.iter().enumerate() {
             let trader = trader_map.get(&idx).cloned().unwrap_or_default();
-            let position_key = format!("{}:{}", trade.market_id, trade.outcome);
+            let position_key = position_key(&trade.market_id, &trade.outcome);
+            last_prices.insert(position_key.clone(), trade.price);
+            let source_reduced = source_holdings.record(&trader, trade);
+
+            // Settle positions whose market resolved before this trade
+            for settled in self.settle_resolved(&mut positions, &resolutions, trade.timestamp) {
+                capital += settled.size * settled.entry_price + settled.pnl; // Fee-free payout
+                if settled.pnl < Decimal::ZERO {
+                    last_loss_time = Some(settled.exit_time);
+                }
+                let equity = capital + positions.values().map(|p| p.size * p.entry_price).sum::<Decimal>();
+                peak_equity = peak_equity.max(equity);
+                max_drawdown = max_drawdown.max(((peak_equity - equity) / peak_equity).to_f64().unwrap_or(0.0));
+                equity_curve.push((settled.exit_time, equity));
+                completed_trades.push(settled);
+            }
 
-            // Check if this is an exit trade (we have opposite position)
+            // An opposite trade is an exit only if it shrinks the position of the source we
+            // copied; anything else is a hedge, kept or flipped per the strategy
             if let Some(existing) = positions.get(&position_key) {
                 if existing.side != trade.side {
-                    // This is an exit - close the position
+                    let source_exit = existing.source_trader == trader && source_reduced;
+                    if !source_exit && self.strategy.config().source_hedge_policy == SourceHedgePolicy::Ignore {
+                        *skip_reasons.entry("Source hedge".to_string()).or_default() += 1;
+                        continue;
+                    }
+
                     let exit_price = self.apply_slippage(trade.price, trade.side);
                     let pnl = existing.pnl_at(exit_price);
-                    let return_pct = existing.return_pct(exit_price);
 
                     // Apply fees
                     let fee = exit_price * existing.size * self.config.fee_rate;
                     total_fees += fee;
+                    let return_pct = self.trade_return_pct(existing, exit_price, fee);
                     let net_pnl = pnl - fee;
 
-                    capital += existing.size * existing.entry_price + net_pnl;
+                    // Proceeds in: the cost at entry already went out with its fee
+                    capital += existing.value_at(exit_price) - fee;
 
                     completed_trades.push(BacktestTrade {
                         market_id: existing.market_id.clone(),
@@ -353,7 +854,7 @@ impl Backtester {
                         pnl: net_pnl,
                         return_pct,
                         source_trader: existing.source_trader.clone(),
-                        exit_reason: "Trader Exit".to_string(),
+                        exit_reason: if source_exit { "Trader Exit" } else { "Source Hedge" }.to_string(),
                     });
 
                     if net_pnl < Decimal::ZERO {
@@ -369,7 +870,9 @@ impl Backtester {
                         "Closed position"
                     );
 
-                    continue;
+                    if source_exit {
+                        continue;
+                    }
                 }
             }
 
@@ -378,8 +881,8 @@ impl Backtester {
                 .map(|p| p.size * p.entry_price)
                 .sum();
 
-            let unrealized: Decimal = positions.values()
-                .map(|p| p.pnl_at(trade.price))
+            let unrealized: Decimal = positions.iter()
+                .map(|(key, p)| p.pnl_at(mark_price(&last_prices, key, p)))
                 .sum();
 
             let current_equity = capital + unrealized;
@@ -402,18 +905,23 @@ impl Backtester {
             };
 
             // Get market positions for this market
-            let market_positions: Vec<StrategyPosition> = positions.values()
-                .filter(|p| p.market_id == trade.market_id)
-                .map(|p| StrategyPosition {
-                    market_id: p.market_id.clone(),
-                    outcome: p.outcome.clone(),
-                    side: format!("{:?}", p.side),
-                    entry_price: p.entry_price,
-                    current_price: trade.price,
-                    size: p.size,
-                    unrealized_pnl: p.pnl_at(trade.price),
-                    opened_at: p.entry_time,
-                    source_trader: Some(p.source_trader.clone()),
+            let market_positions: Vec<StrategyPosition> = positions.iter()
+                .filter(|(_, p)| p.market_id == trade.market_id)
+                .map(|(key, p)| {
+                    let price = mark_price(&last_prices, key, p);
+                    StrategyPosition {
+                        market_id: p.market_id.clone(),
+                        outcome: p.outcome.clone(),
+                        side: format!("{:?}", p.side),
+                        entry_price: p.entry_price,
+                        current_price: price,
+                        size: p.size,
+                        unrealized_pnl: p.pnl_at(price),
+                        peak_price: price,
+                        opened_at: p.entry_time,
+                        source_trader: Some(p.source_trader.clone()),
+                        entries: 1,
+                    }
                 })
                 .collect();
 
@@ -429,9 +937,11 @@ impl Backtester {
 
             // Validate entry (pass trade timestamp as reference time for backtesting)
             let validation = self.strategy.validate_entry(
-                trade.timestamp,
-                trade.price,
-                trade.price, // No slippage check in backtest source
+                &EntryQuote::new(
+                    trade.timestamp,
+                    trade.price,
+                    trade.price, // No slippage check in backtest source
+                ),
                 base_size,
                 None,
                 &portfolio,
@@ -445,13 +955,13 @@ impl Backtester {
                     reason = %validation.reason,
                     "Trade skipped"
                 );
-                skipped += 1;
+                *skip_reasons.entry(validation.category().to_string()).or_default() += 1;
                 continue;
             }
 
             let size = validation.adjusted_size.unwrap_or(base_size);
             if size <= Decimal::ZERO {
-                skipped += 1;
+                *skip_reasons.entry("Zero size".to_string()).or_default() += 1;
                 continue;
             }
 
@@ -471,7 +981,7 @@ impl Backtester {
                     capital = %capital,
                     "Insufficient capital"
                 );
-                skipped += 1;
+                *skip_reasons.entry("Insufficient capital".to_string()).or_default() += 1;
                 continue;
             }
 
@@ -486,6 +996,7 @@ impl Backtester {
                 entry_price,
                 entry_time: trade.timestamp,
                 source_trader: trader.clone(),
+                entry_fee,
             });
 
             last_trade_time = Some(trade.timestamp);
@@ -499,8 +1010,8 @@ impl Backtester {
             );
 
             // Update equity tracking
-            let current_equity = capital + positions.values()
-                .map(|p| p.size * p.entry_price + p.pnl_at(trade.price))
+            let current_equity = capital + positions.iter()
+                .map(|(key, p)| p.value_at(mark_price(&last_prices, key, p)))
                 .sum::<Decimal>();
 
             if current_equity > peak_equity {
@@ -517,13 +1028,20 @@ impl Backtester {
             equity_curve.push((trade.timestamp, current_equity));
         }
 
+        // Settle positions whose market resolved within the replay window
+        for settled in self.settle_resolved(&mut positions, &resolutions, end_time) {
+            capital += settled.size * settled.entry_price + settled.pnl; // Fee-free payout
+            completed_trades.push(settled);
+        }
+
         // Close any remaining positions at last known price
         for (_, pos) in positions.drain() {
             // Use entry price as exit (conservative)
             let exit_price = pos.entry_price;
             let pnl = Decimal::ZERO; // Assume flat
+            let return_pct = self.trade_return_pct(&pos, exit_price, Decimal::ZERO);
 
-            capital += pos.size * pos.entry_price;
+            capital += pos.value_at(exit_price);
 
             completed_trades.push(BacktestTrade {
                 market_id: pos.market_id,
@@ -535,7 +1053,7 @@ impl Backtester {
                 entry_time: pos.entry_time,
                 exit_time: end_time,
                 pnl,
-                return_pct: Decimal::ZERO,
+                return_pct,
                 source_trader: pos.source_trader,
                 exit_reason: "End of Backtest".to_string(),
             });
@@ -568,11 +1086,7 @@ impl Backtester {
 
         let gross_profit: Decimal = winners.iter().map(|t| t.pnl).sum();
         let gross_loss: Decimal = losers.iter().map(|t| t.pnl.abs()).sum();
-        let profit_factor = if gross_loss > Decimal::ZERO {
-            gross_profit.to_f64().unwrap_or(0.0) / gross_loss.to_f64().unwrap_or(1.0)
-        } else {
-            f64::INFINITY
-        };
+        let profit_factor = metrics::profit_factor(gross_profit, gross_loss);
 
         let avg_holding: f64 = if !completed_trades.is_empty() {
             completed_trades.iter()
@@ -601,16 +1115,29 @@ impl Backtester {
             max_drawdown_pct: max_drawdown,
             sharpe_ratio: sharpe,
             sortino_ratio: sortino,
+            ratio_basis: self.config.ratio_basis,
+            net_trade_returns: self.config.net_trade_returns,
             avg_holding_hours: avg_holding,
             total_fees,
             trades: completed_trades,
             equity_curve,
-            skipped_trades: skipped,
+            skipped_trades: skip_reasons.values().sum(),
+            skip_reasons,
+            filtered_trades: 0,
             start_time,
             end_time,
         })
     }
 
+    /// Per-trade return on closing `pos` at `exit_price`, in the configured basis.
+    fn trade_return_pct(&self, pos: &SimulatedPosition, exit_price: Decimal, exit_fee: Decimal) -> Decimal {
+        if self.config.net_trade_returns {
+            pos.net_return_pct(exit_price, exit_fee)
+        } else {
+            pos.return_pct(exit_price)
+        }
+    }
+
     /// Apply slippage to a price.
     fn apply_slippage(&self, price: Decimal, side: TradeSide) -> Decimal {
         match side {
@@ -650,7 +1177,7 @@ impl Backtester {
         let std_dev = variance.sqrt();
 
         let sharpe = if std_dev > 0.0 {
-            (mean / std_dev) * (252.0_f64).sqrt() // Annualized
+            (mean / std_dev) * BACKTEST_PERIODS_PER_YEAR.sqrt() // Annualized
         } else {
             0.0
         };
@@ -668,7 +1195,7 @@ impl Backtester {
             let downside_dev = downside_variance.sqrt();
 
             if downside_dev > 0.0 {
-                (mean / downside_dev) * (252.0_f64).sqrt()
+                (mean / downside_dev) * BACKTEST_PERIODS_PER_YEAR.sqrt()
             } else {
                 0.0
             }
@@ -725,40 +1252,57 @@ pub struct PaperTrader {
     pub started_at: DateTime<Utc>,
     strategy: Strategy,
     position_sizer: PositionSizer,
+    source_holdings: SourceHoldings,
+    clock: Arc<dyn Clock>,
 }
 
 impl PaperTrader {
     /// Create a new paper trader.
     pub fn new(config: PaperConfig) -> Self {
-        let strategy = Strategy::new(config.strategy_config.clone());
+        Self::with_clock(config, Arc::new(SystemClock))
+    }
+
+    /// Create a paper trader that reads the current time from `clock`.
+    pub fn with_clock(config: PaperConfig, clock: Arc<dyn Clock>) -> Self {
+        let strategy = Strategy::new(config.strategy_config.clone())
+            .with_min_trades_for_score(config.trading_config.min_trades_for_score)
+            .with_clock(clock.clone());
         let position_sizer = PositionSizer::new(config.trading_config.clone());
+        let now = clock.now();
 
         Self {
             capital: config.initial_capital,
             positions: HashMap::new(),
             completed_trades: Vec::new(),
-            equity_curve: vec![(Utc::now(), config.initial_capital)],
+            equity_curve: vec![(now, config.initial_capital)],
             total_fees: Decimal::ZERO,
             peak_equity: config.initial_capital,
-            started_at: Utc::now(),
+            started_at: now,
             strategy,
             position_sizer,
+            source_holdings: SourceHoldings::default(),
+            clock,
             config,
         }
     }
 
+    /// Start tracking a source's holdings from its current snapshot, so exits of
+    /// positions opened before paper trading started are still recognized.
+    pub fn seed_source_holdings(&mut self, source_trader: &str, held: &[Position]) {
+        self.source_holdings.seed(source_trader, held, &[]);
+    }
+
     /// Get current equity (capital + unrealized P&L).
     pub fn current_equity(&self, prices: &HashMap<String, Decimal>) -> Decimal {
-        let unrealized: Decimal = self.positions.iter()
+        // Entry cost (and fees) already left `capital`, so only add back what positions are worth now
+        let position_value: Decimal = self.positions.iter()
             .map(|(key, pos)| {
                 let price = prices.get(key).copied().unwrap_or(pos.entry_price);
-                pos.pnl_at(price)
+                pos.value_at(price)
             })
             .sum();
 
-        self.capital + self.positions.values()
-            .map(|p| p.size * p.entry_price)
-            .sum::<Decimal>() + unrealized
+        self.capital + position_value
     }
 
     /// Process a new trade from a tracked trader.
@@ -768,12 +1312,19 @@ impl PaperTrader {
         source_trader: &str,
         current_price: Decimal,
     ) -> Result<Option<String>> {
-        let position_key = format!("{}:{}", trade.market_id, trade.outcome);
+        let position_key = position_key(&trade.market_id, &trade.outcome);
+        let source_reduced = self.source_holdings.record(source_trader, trade);
 
-        // Check if this is an exit
+        // Exit only when the source we copied shrinks its position; otherwise it's a hedge
         if let Some(existing) = self.positions.get(&position_key) {
             if existing.side != trade.side {
-                return self.close_position(&position_key, current_price, "Trader Exit");
+                if existing.source_trader == source_trader && source_reduced {
+                    return self.close_position(&position_key, current_price, "Trader Exit");
+                }
+                if self.strategy.config().source_hedge_policy == SourceHedgePolicy::Ignore {
+                    return Ok(Some("Skipped: Source hedge, not an exit".to_string()));
+                }
+                self.close_position(&position_key, current_price, "Source Hedge")?;
             }
         }
 
@@ -805,9 +1356,7 @@ impl PaperTrader {
 
         // Validate (paper trading uses real-time, so pass None)
         let validation = self.strategy.validate_entry(
-            trade.timestamp,
-            current_price,
-            trade.price,
+            &EntryQuote::new(trade.timestamp, current_price, trade.price),
             base_size,
             None,
             &portfolio,
@@ -848,8 +1397,9 @@ impl PaperTrader {
             side: trade.side.clone(),
             size,
             entry_price,
-            entry_time: Utc::now(),
+            entry_time: self.clock.now(),
             source_trader: source_trader.to_string(),
+            entry_fee: fee,
         });
 
         Ok(None)
@@ -878,7 +1428,7 @@ impl PaperTrader {
         let fee = final_price * pos.size * self.config.fee_rate;
         let net_pnl = pnl - fee;
 
-        self.capital += pos.size * pos.entry_price + net_pnl;
+        self.capital += pos.value_at(final_price) - fee;
         self.total_fees += fee;
 
         self.completed_trades.push(BacktestTrade {
@@ -889,7 +1439,7 @@ impl PaperTrader {
             entry_price: pos.entry_price,
             exit_price: final_price,
             entry_time: pos.entry_time,
-            exit_time: Utc::now(),
+            exit_time: self.clock.now(),
             pnl: net_pnl,
             return_pct,
             source_trader: pos.source_trader,
@@ -907,7 +1457,7 @@ impl PaperTrader {
             self.peak_equity = equity;
         }
 
-        self.equity_curve.push((Utc::now(), equity));
+        self.equity_curve.push((self.clock.now(), equity));
     }
 
     /// Get current statistics.
@@ -973,21 +1523,408 @@ impl std::fmt::Display for PaperStats {
         writeln!(f, "\n{:=^50}", " PAPER TRADING ")?;
         writeln!(f, "Running since: {}", self.running_since.format("%Y-%m-%d %H:%M"))?;
         writeln!(f)?;
-        writeln!(f, "Initial Capital:  ${:.2}", self.initial_capital)?;
-        writeln!(f, "Current Equity:   ${:.2}", self.current_equity)?;
-        writeln!(f, "Cash Available:   ${:.2}", self.cash_available)?;
+        writeln!(f, "Initial Capital:  ${}", display::money(self.initial_capital))?;
+        writeln!(f, "Current Equity:   ${}", display::money(self.current_equity))?;
+        writeln!(f, "Cash Available:   ${}", display::money(self.cash_available))?;
         writeln!(f)?;
-        writeln!(f, "Unrealized P&L:   ${:.2}", self.unrealized_pnl)?;
-        writeln!(f, "Realized P&L:     ${:.2}", self.realized_pnl)?;
-        writeln!(f, "Total P&L:        ${:.2} ({:.2}%)",
-            self.total_pnl, self.return_pct * dec!(100))?;
+        writeln!(f, "Unrealized P&L:   ${}", display::money(self.unrealized_pnl))?;
+        writeln!(f, "Realized P&L:     ${}", display::money(self.realized_pnl))?;
+        writeln!(f, "Total P&L:        ${} ({}%)",
+            display::money(self.total_pnl), display::pct(self.return_pct))?;
         writeln!(f)?;
         writeln!(f, "Open Positions:   {}", self.open_positions)?;
         writeln!(f, "Completed Trades: {}", self.completed_trades)?;
-        writeln!(f, "Win Rate:         {:.1}%", self.win_rate * 100.0)?;
-        writeln!(f, "Max Drawdown:     {:.2}%", self.max_drawdown * 100.0)?;
-        writeln!(f, "Total Fees:       ${:.2}", self.total_fees)?;
+        writeln!(f, "Win Rate:         {}%", display::pct(self.win_rate))?;
+        writeln!(f, "Max Drawdown:     {}%", display::pct(self.max_drawdown))?;
+        writeln!(f, "Total Fees:       ${}", display::money(self.total_fees))?;
         writeln!(f, "{:=^50}", "")?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_reports_out_of_range_stop_loss() {
+        let mut config = BacktestConfig::default();
+        assert!(config.validate().is_ok());
+
+        config.strategy_config.stop_loss_pct = dec!(1.5);
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("stop_loss_pct"));
+        assert!(Backtester::new(config).is_err());
+    }
+
+    #[test]
+    fn test_paper_equity_flat_position_only_loses_fees() {
+        let capital = dec!(10000);
+        let mut paper = PaperTrader::new(PaperConfig {
+            initial_capital: capital,
+            slippage: Decimal::ZERO,
+            fee_rate: dec!(0.01),
+            ..PaperConfig::default()
+        });
+
+        let trade = make_trade("0xaaa", "Yes");
+        assert_eq!(paper.process_trade(&trade, "0xtrader", dec!(0.50)).unwrap(), None);
+        assert_eq!(paper.positions.len(), 1);
+
+        let cost: Decimal = paper.positions.values().map(|p| p.size * p.entry_price).sum();
+        assert!(cost > Decimal::ZERO);
+        assert_eq!(paper.capital, capital - cost - paper.total_fees);
+
+        let prices = HashMap::from([("0xaaa:Yes".to_string(), dec!(0.50))]);
+        assert_eq!(paper.current_equity(&prices), capital - paper.total_fees);
+
+        let stats = paper.get_stats(&prices);
+        assert_eq!(stats.current_equity, capital - paper.total_fees);
+        assert_eq!(stats.unrealized_pnl, Decimal::ZERO);
+    }
+
+    fn make_trade(market: &str, outcome: &str) -> Trade {
+        Trade {
+            id: format!("0xtx_{}_{}", market, outcome),
+            trader_address: "0xtrader".to_string(),
+            market_id: market.to_string(),
+            market_title: String::new(),
+            side: TradeSide::Buy,
+            outcome: outcome.to_string(),
+            size: dec!(100),
+            price: dec!(0.50),
+            amount_usdc: dec!(50),
+            timestamp: Utc::now(),
+            transaction_hash: "0xtx".to_string(),
+            is_taker: true,
+            fee_usdc: Decimal::ZERO,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_skip_reasons_group_price_bound_rejections() {
+        let trades: Vec<Trade> = ["0xaaa", "0xbbb", "0xccc"]
+            .iter()
+            .map(|m| Trade { price: dec!(0.97), ..make_trade(m, "Yes") })
+            .collect();
+
+        let backtester = Backtester::new(BacktestConfig::default()).unwrap();
+        let results = backtester.run_simulation("0xtrader", &trades, SourceHoldings::default()).await.unwrap();
+
+        assert_eq!(results.skipped_trades, 3);
+        assert_eq!(results.skip_reasons, HashMap::from([("Price too high".to_string(), 3)]));
+        assert!(results.to_string().contains("Price too high"));
+    }
+
+    #[tokio::test]
+    async fn test_all_skipped_run_reports_no_trades_taken() {
+        let trades = vec![
+            Trade { price: dec!(0.97), ..make_trade("0xaaa", "Yes") },
+            Trade { price: dec!(0.02), ..make_trade("0xbbb", "Yes") },
+        ];
+
+        let backtester = Backtester::new(BacktestConfig::default()).unwrap();
+        let results = backtester.run_simulation("0xtrader", &trades, SourceHoldings::default()).await.unwrap();
+        assert!(results.no_trades_taken());
+        assert_eq!(results.final_capital, results.initial_capital);
+
+        let rendered = results.to_string();
+        assert!(rendered.contains("No trades taken"));
+        assert!(rendered.contains("Skipped:     2"));
+        assert!(rendered.contains("Price too high"));
+        assert!(rendered.contains("Price too low"));
+        assert!(!rendered.contains("--- Risk Metrics ---"));
+    }
+
+    #[tokio::test]
+    async fn test_second_backtest_within_cache_window_skips_fetch() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        let trader = "0xcached";
+        let trades = (0..3)
+            .map(|i| {
+                let market = format!("0xmarket{}", i);
+                crate::testing::trade(trader, &market, &format!("0xtx{}", i), 100.0, 0.50, 1_700_000_000 + i * 60)
+            })
+            .collect();
+        mock.mount_trades(trader, trades, None).await;
+
+        let config = BacktestConfig {
+            cache_dir: Some(std::env::temp_dir().join(format!("polycopier-cache-{}", uuid::Uuid::new_v4()))),
+            ..BacktestConfig::default()
+        };
+        let backtester = Backtester::with_client(config.clone(), mock.data_client()).unwrap();
+
+        let first = backtester.run_single_trader(trader).await.unwrap();
+        let second = backtester.run_single_trader(trader).await.unwrap();
+        assert_eq!(mock.trade_fetches().await, 1);
+        assert_eq!(first.to_string(), second.to_string());
+        assert_eq!(first.equity_curve, second.equity_curve);
+
+        // Refreshing ignores the cached copy
+        let refreshing = Backtester::with_client(
+            BacktestConfig { refresh_cache: true, ..config.clone() },
+            mock.data_client(),
+        )
+        .unwrap();
+        refreshing.run_single_trader(trader).await.unwrap();
+        assert_eq!(mock.trade_fetches().await, 2);
+
+        std::fs::remove_dir_all(config.cache_dir.unwrap()).ok();
+    }
+
+    #[tokio::test]
+    async fn test_held_winning_position_settles_at_one() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        let trader = "0xholder";
+        let start = 1_700_000_000;
+        let trades = vec![
+            crate::testing::trade(trader, "0xwon", "0xtx1", 100.0, 0.50, start),
+            crate::testing::trade(trader, "0xopen", "0xtx2", 100.0, 0.50, start + 1200),
+        ];
+        mock.mount_trades(trader, trades, None).await;
+        mock.mount_resolution("0xwon", "Yes", DateTime::from_timestamp(start + 600, 0).unwrap()).await;
+        mock.mount_market("0xopen", false).await;
+
+        let config = BacktestConfig { settle_at_resolution: true, ..BacktestConfig::default() };
+        let backtester = Backtester::with_client(config, mock.data_client())
+            .unwrap()
+            .with_clob_client(mock.clob_client());
+        let results = backtester.run_single_trader(trader).await.unwrap();
+
+        // Settled at the close ten minutes in, not the scheduled end a day earlier
+        let settled = results.trades.iter().find(|t| t.market_id == "0xwon").unwrap();
+        assert_eq!(settled.exit_reason, "Market Resolution");
+        assert_eq!(settled.exit_time.timestamp(), start + 600);
+        assert_eq!(settled.exit_price, Decimal::ONE);
+        assert_eq!(settled.pnl, settled.size * (Decimal::ONE - settled.entry_price));
+        assert!(settled.pnl > Decimal::ZERO);
+
+        let open = results.trades.iter().find(|t| t.market_id == "0xopen").unwrap();
+        assert_eq!(open.exit_reason, "End of Backtest");
+        assert_eq!(results.final_capital, results.initial_capital + settled.pnl - results.total_fees);
+    }
+
+    #[tokio::test]
+    async fn test_flat_round_trip_costs_exactly_both_fees() {
+        let config = BacktestConfig {
+            slippage: Decimal::ZERO,
+            fee_rate: dec!(0.01),
+            ..BacktestConfig::default()
+        };
+        let entry = make_trade("0xaaa", "Yes");
+        let exit = Trade {
+            side: TradeSide::Sell,
+            timestamp: entry.timestamp + chrono::Duration::minutes(5),
+            ..make_trade("0xaaa", "Yes")
+        };
+
+        let backtester = Backtester::new(config.clone()).unwrap();
+        let results = backtester.run_simulation("0xtrader", &[entry, exit], SourceHoldings::default()).await.unwrap();
+
+        assert_eq!(results.trades.len(), 1);
+        let trade = &results.trades[0];
+        assert_eq!(trade.exit_reason, "Trader Exit");
+        let fee = trade.size * dec!(0.50) * config.fee_rate;
+        assert!(fee > Decimal::ZERO);
+        assert_eq!(results.total_fees, fee * Decimal::TWO);
+        assert_eq!(results.final_capital, config.initial_capital - fee * Decimal::TWO);
+    }
+
+    #[tokio::test]
+    async fn test_net_trade_returns_reconcile_with_portfolio_return() {
+        let config = BacktestConfig {
+            slippage: Decimal::ZERO,
+            fee_rate: dec!(0.01),
+            net_trade_returns: true,
+            ..BacktestConfig::default()
+        };
+        let start = Utc::now() - chrono::Duration::hours(1);
+        let at = |minutes| start + chrono::Duration::minutes(minutes);
+        let trades = [
+            Trade { timestamp: at(0), ..make_trade("0xaaa", "Yes") },
+            Trade { side: TradeSide::Sell, price: dec!(0.60), timestamp: at(10), ..make_trade("0xaaa", "Yes") },
+            Trade { price: dec!(0.40), timestamp: at(20), ..make_trade("0xbbb", "Yes") },
+            Trade { side: TradeSide::Sell, price: dec!(0.35), timestamp: at(30), ..make_trade("0xbbb", "Yes") },
+        ];
+
+        let backtester = Backtester::new(config.clone()).unwrap();
+        let results = backtester.run_simulation("0xtrader", &trades, SourceHoldings::default()).await.unwrap();
+        assert_eq!(results.trades.len(), 2);
+        assert_eq!(results.trade_return_basis(), "net");
+
+        // Each net return, weighted by the cash it committed, adds up to the portfolio's change
+        let weighted: Decimal = results
+            .trades
+            .iter()
+            .map(|t| t.return_pct * t.size * t.entry_price * (Decimal::ONE + config.fee_rate))
+            .sum();
+        let portfolio_change = results.total_return_pct * results.initial_capital;
+        assert_eq!(weighted.round_dp(8), portfolio_change.round_dp(8));
+
+        // Gross returns ignore the fees and overstate it
+        let gross = Backtester::new(BacktestConfig { net_trade_returns: false, ..config })
+            .unwrap()
+            .run_simulation("0xtrader", &trades, SourceHoldings::default())
+            .await
+            .unwrap();
+        assert_eq!(gross.trades[0].return_pct, dec!(0.2));
+        assert!(results.trades[0].return_pct < gross.trades[0].return_pct);
+    }
+
+    #[tokio::test]
+    async fn test_opposite_trade_by_another_source_does_not_close_position() {
+        let config = BacktestConfig {
+            slippage: Decimal::ZERO,
+            fee_rate: Decimal::ZERO,
+            ..BacktestConfig::default()
+        };
+        let start = Utc::now() - chrono::Duration::hours(1);
+        let at = |minutes| start + chrono::Duration::minutes(minutes);
+        let trades = [
+            Trade { timestamp: at(0), ..make_trade("0xaaa", "Yes") },
+            // Another source sells the outcome we copied: the source we follow still holds
+            Trade { side: TradeSide::Sell, timestamp: at(10), ..make_trade("0xaaa", "Yes") },
+            Trade { side: TradeSide::Sell, price: dec!(0.60), timestamp: at(20), ..make_trade("0xaaa", "Yes") },
+        ];
+        let trader_map = HashMap::from([
+            (0, "0xsource".to_string()),
+            (1, "0xhedger".to_string()),
+            (2, "0xsource".to_string()),
+        ]);
+
+        let backtester = Backtester::new(config.clone()).unwrap();
+        let results = backtester.run_simulation_multi(&trades, &trader_map, SourceHoldings::default()).await.unwrap();
+        assert_eq!(results.skip_reasons.get("Source hedge"), Some(&1));
+        assert_eq!(results.trades.len(), 1);
+        assert_eq!(results.trades[0].exit_reason, "Trader Exit");
+        assert_eq!(results.trades[0].exit_price, dec!(0.60));
+        assert_eq!(results.trades[0].exit_time, at(20));
+
+        // Flipping closes ours at the hedge and copies it as a new, inverse position
+        let mut flip = config;
+        flip.strategy_config.source_hedge_policy = SourceHedgePolicy::Flip;
+        flip.strategy_config.min_trade_interval_secs = 0;
+        let results = Backtester::new(flip).unwrap().run_simulation_multi(&trades[..2], &trader_map, SourceHoldings::default()).await.unwrap();
+        assert_eq!(results.trades[0].exit_reason, "Source Hedge");
+        assert_eq!(results.trades[0].exit_time, at(10));
+        assert_eq!(results.trades[1].side, TradeSide::Sell);
+        assert_eq!(results.trades[1].source_trader, "0xhedger");
+        assert_eq!(results.trades[1].exit_reason, "End of Backtest");
+    }
+
+    /// Backtest `trades` of (side, size, price) by one source in one outcome, ten
+    /// minutes apart, with the source holding `held_now` shares today.
+    async fn backtest_source_trades(trades: &[(&str, f64, f64)], held_now: f64) -> BacktestResults {
+        let mock = crate::testing::MockPolymarket::start().await;
+        let trader = "0xsource";
+        let trades = trades
+            .iter()
+            .enumerate()
+            .map(|(i, (side, size, price))| {
+                let at = 1_700_000_000 + i as i64 * 600;
+                let mut trade = crate::testing::trade(trader, "0xaaa", &format!("0xtx{}", i), *size, *price, at);
+                trade["side"] = serde_json::json!(side);
+                trade
+            })
+            .collect();
+        mock.mount_trades(trader, trades, None).await;
+        let held = if held_now > 0.0 { vec![crate::testing::position("0xaaa", held_now, 0.50)] } else { vec![] };
+        mock.mount_positions(trader, held).await;
+
+        let config = BacktestConfig {
+            slippage: Decimal::ZERO,
+            fee_rate: Decimal::ZERO,
+            ..BacktestConfig::default()
+        };
+        Backtester::with_client(config, mock.data_client()).unwrap().run_single_trader(trader).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_source_selling_out_of_pre_window_holdings_closes_copy() {
+        // Held 200 before the window, bought 100 we copied, then sold all 300
+        let results = backtest_source_trades(&[("BUY", 100.0, 0.50), ("SELL", 300.0, 0.60)], 0.0).await;
+        assert_eq!(results.skip_reasons.get("Source hedge"), None);
+        assert_eq!(results.trades.len(), 1);
+        assert_eq!(results.trades[0].exit_reason, "Trader Exit");
+        assert_eq!(results.trades[0].exit_price, dec!(0.60));
+    }
+
+    #[tokio::test]
+    async fn test_same_source_adding_against_our_copy_is_a_hedge() {
+        // Holds 500 today: sold 100 we copied, then bought 50 back onto a larger position
+        let results = backtest_source_trades(&[("SELL", 100.0, 0.50), ("BUY", 50.0, 0.60)], 500.0).await;
+        assert_eq!(results.skip_reasons.get("Source hedge"), Some(&1));
+        assert_eq!(results.trades.len(), 1);
+        assert_eq!(results.trades[0].side, TradeSide::Sell);
+        assert_eq!(results.trades[0].exit_reason, "End of Backtest");
+    }
+
+    #[tokio::test]
+    async fn test_outcomes_in_one_market_are_marked_at_their_own_prices() {
+        let config = BacktestConfig {
+            slippage: Decimal::ZERO,
+            fee_rate: Decimal::ZERO,
+            ..BacktestConfig::default()
+        };
+        let start = Utc::now() - chrono::Duration::minutes(10);
+        let yes = Trade { price: dec!(0.60), timestamp: start, ..make_trade("0xaaa", "Yes") };
+        let no = Trade {
+            price: dec!(0.40),
+            timestamp: start + chrono::Duration::minutes(5),
+            ..make_trade("0xaaa", "No")
+        };
+
+        let backtester = Backtester::new(config.clone()).unwrap();
+        let results = backtester.run_simulation("0xtrader", &[yes, no], SourceHoldings::default()).await.unwrap();
+        assert_eq!(results.trades.len(), 2);
+
+        // Both outcomes are flat at their own prices; marking Yes at No's 0.40 would dip equity
+        assert!(results.equity_curve.iter().all(|(_, equity)| *equity == config.initial_capital));
+
+        let held = |outcome: &str, entry_price| SimulatedPosition {
+            market_id: "0xaaa".to_string(),
+            outcome: outcome.to_string(),
+            side: TradeSide::Buy,
+            size: dec!(10),
+            entry_price,
+            entry_time: start,
+            source_trader: "0xtrader".to_string(),
+            entry_fee: Decimal::ZERO,
+        };
+        let last_prices = HashMap::from([
+            (position_key("0xaaa", "Yes"), dec!(0.70)),
+            (position_key("0xaaa", "No"), dec!(0.30)),
+        ]);
+        let yes_pos = held("Yes", dec!(0.60));
+        let no_pos = held("No", dec!(0.40));
+        let yes_pnl = yes_pos.pnl_at(mark_price(&last_prices, &position_key("0xaaa", "Yes"), &yes_pos));
+        let no_pnl = no_pos.pnl_at(mark_price(&last_prices, &position_key("0xaaa", "No"), &no_pos));
+        assert_eq!(yes_pnl, dec!(1));
+        assert_eq!(no_pnl, dec!(-1));
+    }
+
+    #[test]
+    fn test_market_filter_excludes_other_markets() {
+        let trades = vec![
+            make_trade("0xaaa", "Yes"),
+            make_trade("0xbbb", "Yes"),
+            make_trade("0xaaa", "No"),
+        ];
+
+        let config = BacktestConfig {
+            market_filter: Some("0xAAA".to_string()),
+            ..BacktestConfig::default()
+        };
+        let (kept, excluded) = config.filter_trades(trades.clone(), |t| t);
+        assert_eq!(excluded, 1);
+        assert!(kept.iter().all(|t| t.market_id == "0xaaa"));
+
+        let config = BacktestConfig {
+            market_filter: Some("0xaaa".to_string()),
+            outcome_filter: Some("yes".to_string()),
+            ..BacktestConfig::default()
+        };
+        let (kept, excluded) = config.filter_trades(trades, |t| t);
+        assert_eq!((kept.len(), excluded), (1, 2));
+    }
+}
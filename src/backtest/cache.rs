@@ -0,0 +1,74 @@
+//! On-disk cache of trader trade histories, so repeated backtests skip the API.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::models::Trade;
+
+/// Default cache directory, relative to the working directory.
+pub const DEFAULT_CACHE_DIR: &str = ".polycopier-cache";
+
+/// Default time a cached history stays fresh.
+pub const DEFAULT_CACHE_MAX_AGE_HOURS: i64 = 6;
+
+/// A cached history and when it was fetched.
+#[derive(Serialize, Deserialize)]
+struct CachedTrades {
+    fetched_at: DateTime<Utc>,
+    trades: Vec<Trade>,
+}
+
+/// Gzipped JSON trade histories, one file per trader, lookback and market filter.
+#[derive(Debug, Clone)]
+pub struct TradeCache {
+    dir: PathBuf,
+    max_age: Duration,
+}
+
+impl TradeCache {
+    pub fn new(dir: impl Into<PathBuf>, max_age: Duration) -> Self {
+        Self { dir: dir.into(), max_age }
+    }
+
+    fn path(&self, address: &str, lookback: u32, market: Option<&str>) -> PathBuf {
+        let market = market.unwrap_or("all");
+        let name = format!("{}-{}-{}.json.gz", address, lookback, market).to_lowercase();
+        self.dir.join(name)
+    }
+
+    /// Cached trades, if present and younger than the max age.
+    pub fn get(&self, address: &str, lookback: u32, market: Option<&str>) -> Option<Vec<Trade>> {
+        let path = self.path(address, lookback, market);
+        let file = std::fs::File::open(&path).ok()?;
+        let cached: CachedTrades = match serde_json::from_reader(GzDecoder::new(std::io::BufReader::new(file))) {
+            Ok(cached) => cached,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Ignoring unreadable trade cache entry");
+                return None;
+            }
+        };
+        (Utc::now() - cached.fetched_at < self.max_age).then_some(cached.trades)
+    }
+
+    /// Store freshly fetched trades.
+    pub fn put(&self, address: &str, lookback: u32, market: Option<&str>, trades: &[Trade]) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create cache directory {}", self.dir.display()))?;
+
+        let cached = CachedTrades { fetched_at: Utc::now(), trades: trades.to_vec() };
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&serde_json::to_vec(&cached)?)?;
+
+        let path = self.path(address, lookback, market);
+        std::fs::write(&path, encoder.finish()?)
+            .with_context(|| format!("Failed to write trade cache {}", path.display()))
+    }
+}
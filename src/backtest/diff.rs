@@ -0,0 +1,225 @@
+//! Side-by-side comparison of two saved backtest results.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use rust_decimal::prelude::ToPrimitive;
+
+use super::BacktestResults;
+use crate::output::Report;
+
+/// Whether a higher or lower value is better for a metric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Better {
+    Higher,
+    Lower,
+}
+
+/// One compared metric.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricDelta {
+    pub name: &'static str,
+    pub a: f64,
+    pub b: f64,
+    better: Better,
+}
+
+impl MetricDelta {
+    pub fn delta(&self) -> f64 {
+        self.b - self.a
+    }
+
+    /// "improved", "regressed" or "unchanged" going from `a` to `b`.
+    pub fn verdict(&self) -> &'static str {
+        let delta = self.delta();
+        if delta.abs() < 1e-9 {
+            "unchanged"
+        } else if (delta > 0.0) == (self.better == Better::Higher) {
+            "improved"
+        } else {
+            "regressed"
+        }
+    }
+}
+
+/// A compared metric: its label, which direction is better, and how to read it from a run.
+type ComparedMetric = (&'static str, Better, fn(&BacktestResults) -> f64);
+
+/// Differences between a baseline run (`a`) and a candidate run (`b`).
+#[derive(Debug, Clone)]
+pub struct BacktestDiff {
+    pub metrics: Vec<MetricDelta>,
+    /// Trade counts per exit reason: (a, b)
+    pub exit_reasons: BTreeMap<String, (usize, usize)>,
+}
+
+impl BacktestDiff {
+    pub fn new(a: &BacktestResults, b: &BacktestResults) -> Self {
+        let compared: [ComparedMetric; 7] = [
+            ("Return (%)", Better::Higher, |r| r.total_return_pct.to_f64().unwrap_or(0.0) * 100.0),
+            ("Sharpe Ratio", Better::Higher, |r| r.sharpe_ratio),
+            ("Sortino Ratio", Better::Higher, |r| r.sortino_ratio),
+            ("Max Drawdown (%)", Better::Lower, |r| r.max_drawdown_pct * 100.0),
+            ("Win Rate (%)", Better::Higher, |r| r.win_rate * 100.0),
+            ("Profit Factor", Better::Higher, |r| r.profit_factor),
+            ("Fees Paid ($)", Better::Lower, |r| r.total_fees.to_f64().unwrap_or(0.0)),
+        ];
+        let metrics = compared
+            .into_iter()
+            .map(|(name, better, value)| MetricDelta { name, a: value(a), b: value(b), better })
+            .collect();
+
+        let mut exit_reasons: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+        for trade in &a.trades {
+            exit_reasons.entry(trade.exit_reason.clone()).or_default().0 += 1;
+        }
+        for trade in &b.trades {
+            exit_reasons.entry(trade.exit_reason.clone()).or_default().1 += 1;
+        }
+
+        Self { metrics, exit_reasons }
+    }
+
+    /// Load two results files saved with `backtest --save-results` and compare them.
+    pub fn from_files(a: &Path, b: &Path) -> Result<Self> {
+        Ok(Self::new(&BacktestResults::load(a)?, &BacktestResults::load(b)?))
+    }
+
+    /// Metric deltas and exit-reason counts as a report.
+    pub fn to_report(&self) -> Report {
+        let mut report = Report::new("Backtest Diff");
+        let metrics = self
+            .metrics
+            .iter()
+            .map(|m| {
+                vec![
+                    m.name.to_string(),
+                    format!("{:.2}", m.a),
+                    format!("{:.2}", m.b),
+                    format!("{:+.2}", m.delta()),
+                    m.verdict().to_string(),
+                ]
+            })
+            .collect();
+        report.table("Metrics", &["metric", "a", "b", "delta", "change"], metrics);
+
+        let reasons = self
+            .exit_reasons
+            .iter()
+            .map(|(reason, (a, b))| {
+                vec![reason.clone(), a.to_string(), b.to_string(), format!("{:+}", *b as i64 - *a as i64)]
+            })
+            .collect();
+        report.table("Trades by Exit Reason", &["exit_reason", "a", "b", "delta"], reasons);
+        report
+    }
+}
+
+impl BacktestResults {
+    /// Write the full results as JSON, for later comparison with `backtest-diff`.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let contents = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, contents + "\n")
+            .with_context(|| format!("Failed to write backtest results to {}", path.display()))
+    }
+
+    /// Read results written by [`BacktestResults::save`].
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read backtest results {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid backtest results file {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    use crate::backtest::BacktestTrade;
+    use crate::metrics::RatioBasis;
+    use crate::models::TradeSide;
+
+    fn fixture(return_pct: Decimal, sharpe: f64, max_dd: f64, win_rate: f64, reasons: &[&str]) -> BacktestResults {
+        let now = Utc::now();
+        let trades = reasons
+            .iter()
+            .map(|reason| BacktestTrade {
+                market_id: "0xm1".to_string(),
+                outcome: "Yes".to_string(),
+                side: TradeSide::Buy,
+                size: dec!(10),
+                entry_price: dec!(0.5),
+                exit_price: dec!(0.6),
+                entry_time: now,
+                exit_time: now,
+                pnl: dec!(1),
+                return_pct: dec!(0.2),
+                source_trader: "0xabc".to_string(),
+                exit_reason: reason.to_string(),
+            })
+            .collect();
+        BacktestResults {
+            initial_capital: dec!(1000),
+            final_capital: dec!(1000) * (Decimal::ONE + return_pct),
+            total_return_pct: return_pct,
+            total_trades: reasons.len(),
+            winning_trades: 0,
+            losing_trades: 0,
+            win_rate,
+            avg_win: Decimal::ZERO,
+            avg_loss: Decimal::ZERO,
+            profit_factor: 1.0,
+            max_drawdown_pct: max_dd,
+            sharpe_ratio: sharpe,
+            sortino_ratio: sharpe,
+            ratio_basis: RatioBasis::Annualized,
+            net_trade_returns: false,
+            avg_holding_hours: 0.0,
+            total_fees: dec!(5),
+            trades,
+            equity_curve: vec![(now, dec!(1000))],
+            skipped_trades: 0,
+            skip_reasons: HashMap::new(),
+            filtered_trades: 0,
+            start_time: now,
+            end_time: now,
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_deltas_between_saved_results() {
+        let dir = std::env::temp_dir().join(format!("polycopier-diff-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (path_a, path_b) = (dir.join("a.json"), dir.join("b.json"));
+
+        fixture(dec!(0.10), 1.2, 0.20, 0.50, &["Take Profit", "Stop Loss", "Stop Loss"]).save(&path_a).unwrap();
+        fixture(dec!(0.15), 1.0, 0.10, 0.50, &["Take Profit", "Take Profit", "Trader Exit"]).save(&path_b).unwrap();
+
+        let diff = BacktestDiff::from_files(&path_a, &path_b).unwrap();
+        let metric = |name: &str| diff.metrics.iter().find(|m| m.name == name).unwrap();
+
+        assert!((metric("Return (%)").delta() - 5.0).abs() < 1e-9);
+        assert_eq!(metric("Return (%)").verdict(), "improved");
+        assert!((metric("Sharpe Ratio").delta() + 0.2).abs() < 1e-9);
+        assert_eq!(metric("Sharpe Ratio").verdict(), "regressed");
+        assert!((metric("Max Drawdown (%)").delta() + 10.0).abs() < 1e-9);
+        assert_eq!(metric("Max Drawdown (%)").verdict(), "improved");
+        assert_eq!(metric("Win Rate (%)").verdict(), "unchanged");
+
+        assert_eq!(diff.exit_reasons["Take Profit"], (1, 2));
+        assert_eq!(diff.exit_reasons["Stop Loss"], (2, 0));
+        assert_eq!(diff.exit_reasons["Trader Exit"], (0, 1));
+
+        let report = diff.to_report();
+        assert_eq!(report.tables[1].rows[0], vec!["Stop Loss", "2", "0", "-2"]);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -10,23 +10,34 @@
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal_macros::dec;
 use tokio::sync::RwLock;
-use tokio::time::interval;
+use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 
-use crate::api::{ClobClient, DataClient, OrderResponse, OrderSide, TradeResponse};
-use crate::db::{Database, StoredCopyTrade, StoredPosition};
-use crate::models::{Trade, TradeSide};
+use crate::api::{
+    fetch_prices, round_to_lot, ClobClient, DataClient, MarketInfo, MarketStream, OrderBook, OrderExecutor,
+    OrderResponse, OrderSide, OrderStatus, PriceChain, PriceSource, Quote, TradeResponse, DEFAULT_PRICE_CONCURRENCY,
+    SHARE_LOT_SIZE,
+};
+use crate::db::{
+    self, CopyTradeStatus, Database, ResolvedMarket, StoredCopyTrade, StoredPosition, SETTLEMENT_EXIT_REASON,
+};
+use crate::attribution;
+use crate::clock::{Clock, SystemClock};
+use crate::display;
+use crate::doctor;
+use crate::telemetry::{self, StatsdEmitter};
+use crate::models::{Position, Trade, TradeSide};
 use crate::trading::{
-    CopyEngine, CopyTradeIntent, PortfolioState, Strategy, StrategyConfig, StrategyPosition,
-    TradingConfig,
+    CopyEngine, CopyTradeIntent, EntryQuote, ExitReason, ExitSignal, ExitUrgency, PortfolioState, PruneAction, Strategy,
+    StrategyConfig, StrategyPosition, TradingConfig, ZeroSizeReason,
 };
 
 /// Bot configuration.
@@ -38,9 +49,57 @@ pub struct BotConfig {
     /// Polling interval for new trades (seconds)
     pub poll_interval_secs: u64,
 
+    /// Random jitter applied to the poll interval (0.0 to 1.0, e.g. 0.1 = ±10%)
+    pub poll_jitter_pct: f64,
+
     /// Whether to actually execute trades or just simulate
     pub dry_run: bool,
 
+    /// Cancel all resting orders on shutdown (otherwise they stay on the book)
+    pub cancel_on_exit: bool,
+
+    /// Periodically refresh the sizing base from wallet balance + open position value
+    pub auto_sync_portfolio: bool,
+
+    /// Refuse to start live trading unless all critical doctor checks pass
+    pub require_doctor_pass: bool,
+
+    /// Maximum concurrent price requests when marking positions
+    pub price_fetch_concurrency: usize,
+
+    /// How stored positions are reconciled with wallet holdings on live startup
+    pub restart_policy: RestartPolicy,
+
+    /// Start the drawdown high-water mark over at current equity instead of restoring
+    /// the stored peak, clearing a drawdown halt
+    pub reset_peak_equity: bool,
+
+    /// Stop gracefully after running this long (None = run until interrupted)
+    pub max_runtime: Option<Duration>,
+
+    /// Estimated flat cost per order in USDC (gas, relayer fees)
+    pub order_cost_usdc: Decimal,
+
+    /// Estimated cost per order as a fraction of notional (e.g. 0.001 = 0.1%)
+    pub order_cost_rate: Decimal,
+
+    /// Commit each tick's database writes in one transaction. A failed tick is rolled
+    /// back, except for records of orders it placed, which are committed as each order
+    /// returns. The transaction holds SQLite's write lock through copy delays and
+    /// patient entries, so this is off by default.
+    pub batch_db_writes: bool,
+
+    /// Execute at most this many copy intents per tick, deferring the rest to later
+    /// ticks (None = no limit)
+    pub max_executions_per_tick: Option<usize>,
+
+    /// StatsD collector (host:port) to push run metrics to (None = send nothing)
+    pub statsd_addr: Option<String>,
+
+    /// How long to wait after a failed order submission before checking whether the
+    /// order reached the book anyway, and only then marking the copy trade failed
+    pub order_confirm_grace: Duration,
+
     /// Trading configuration
     pub trading_config: TradingConfig,
 
@@ -56,7 +115,21 @@ impl Default for BotConfig {
         Self {
             portfolio_value: dec!(1000),
             poll_interval_secs: 30,
+            poll_jitter_pct: 0.0,
             dry_run: true,
+            cancel_on_exit: false,
+            auto_sync_portfolio: false,
+            require_doctor_pass: true,
+            price_fetch_concurrency: DEFAULT_PRICE_CONCURRENCY,
+            restart_policy: RestartPolicy::TrustDb,
+            reset_peak_equity: false,
+            max_runtime: None,
+            order_cost_usdc: Decimal::ZERO,
+            order_cost_rate: Decimal::ZERO,
+            batch_db_writes: false,
+            max_executions_per_tick: None,
+            statsd_addr: None,
+            order_confirm_grace: Duration::from_secs(2),
             trading_config: TradingConfig::default(),
             strategy_config: StrategyConfig::default(),
             database_url: "sqlite:copybot.db?mode=rwc".to_string(),
@@ -64,6 +137,17 @@ impl Default for BotConfig {
     }
 }
 
+/// How to reconcile stored positions with the wallet's actual holdings on restart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum RestartPolicy {
+    /// Keep the database as-is
+    TrustDb,
+    /// Replace stored positions with the API's sizes and average prices
+    TrustApi,
+    /// Keep stored positions and fold in holdings the database is missing
+    Merge,
+}
+
 /// Main bot runner.
 pub struct Bot {
     config: BotConfig,
@@ -72,16 +156,37 @@ pub struct Bot {
     clob_client: Option<ClobClient>,
     copy_engine: CopyEngine,
     strategy: Strategy,
+    price_chain: PriceChain,
+    // Websocket mids, and the CLOB token of each "market:outcome" priced from it
+    market_stream: Option<MarketStream>,
+    stream_tokens: Arc<RwLock<HashMap<String, String>>>,
 
     // Runtime state
     portfolio_value: Arc<RwLock<Decimal>>,
+    // Capital that cash is derived from, rebased on each wallet sync
+    capital: Arc<RwLock<Decimal>>,
     cash_available: Arc<RwLock<Decimal>>,
     total_exposure: Arc<RwLock<Decimal>>,
+    // Signed market value of open positions: shorts, owed back at the current price, count negative
+    net_exposure: Arc<RwLock<Decimal>>,
     unrealized_pnl: Arc<RwLock<Decimal>>,
     realized_pnl: Arc<RwLock<Decimal>>,
+    total_costs: Arc<RwLock<Decimal>>,
     peak_equity: Arc<RwLock<Decimal>>,
     last_trade_at: Arc<RwLock<Option<chrono::DateTime<Utc>>>>,
     last_loss_at: Arc<RwLock<Option<chrono::DateTime<Utc>>>>,
+    last_portfolio_sync: Option<Instant>,
+    // Last resolution lookup per unresolved market, so open positions don't query every tick
+    resolution_checks: HashMap<String, Instant>,
+    // First live session recorded in bot state; the allocation ramp counts from here
+    live_started_at: Option<chrono::DateTime<Utc>>,
+
+    // Metrics export, and the (executed, failed) trade counts already reported
+    statsd: Option<StatsdEmitter>,
+    reported_trade_counts: Option<(i64, i64)>,
+
+    // Time source for time-based rules (the system clock outside tests)
+    clock: Arc<dyn Clock>,
 
     // Shutdown signal
     shutdown: Arc<AtomicBool>,
@@ -90,11 +195,6 @@ pub struct Bot {
 impl Bot {
     /// Create a new bot instance.
     pub async fn new(config: BotConfig) -> Result<Self> {
-        let db = Database::new(&config.database_url).await?;
-        let data_client = DataClient::new()?;
-        let copy_engine = CopyEngine::new(config.trading_config.clone())?;
-        let strategy = Strategy::new(config.strategy_config.clone());
-
         // Initialize CLOB client if not in dry-run mode
         let clob_client = if !config.dry_run {
             match ClobClient::from_env() {
@@ -111,25 +211,80 @@ impl Bot {
             None
         };
 
+        Self::with_clients(config, DataClient::new()?, clob_client).await
+    }
+
+    /// Create a dry-run bot that prices entries, exits and marks from the live
+    /// order book but never places orders.
+    pub async fn simulate_live(config: BotConfig) -> Result<Self> {
+        Self::simulate_live_with_clients(config, DataClient::new()?, ClobClient::read_only()?).await
+    }
+
+    /// Create a simulate-live bot pricing from `price_client`, which is never sent orders.
+    pub async fn simulate_live_with_clients(
+        config: BotConfig,
+        data_client: DataClient,
+        price_client: ClobClient,
+    ) -> Result<Self> {
+        let config = BotConfig { dry_run: true, cancel_on_exit: false, ..config };
+        Self::with_clients(config, data_client, Some(price_client)).await
+    }
+
+    /// Create a bot instance with explicitly provided API clients.
+    pub async fn with_clients(
+        config: BotConfig,
+        data_client: DataClient,
+        clob_client: Option<ClobClient>,
+    ) -> Result<Self> {
+        let db = Database::new(&config.database_url).await?;
+        let copy_engine = CopyEngine::with_data_client(config.trading_config.clone(), data_client.clone());
+        let strategy = Strategy::new(config.strategy_config.clone())
+            .with_min_trades_for_score(config.trading_config.min_trades_for_score);
+        let price_chain = config.trading_config.price_chain();
+        let statsd = match &config.statsd_addr {
+            Some(addr) => Some(StatsdEmitter::connect(addr).await?),
+            None => None,
+        };
+
         Ok(Self {
             config: config.clone(),
             db,
             data_client,
-            clob_client,
+            clob_client: clob_client.map(|c| c.with_confirm_grace(config.order_confirm_grace)),
             copy_engine,
             strategy,
+            price_chain,
+            market_stream: None,
+            stream_tokens: Arc::new(RwLock::new(HashMap::new())),
             portfolio_value: Arc::new(RwLock::new(config.portfolio_value)),
+            capital: Arc::new(RwLock::new(config.portfolio_value)),
             cash_available: Arc::new(RwLock::new(config.portfolio_value)),
             total_exposure: Arc::new(RwLock::new(Decimal::ZERO)),
+            net_exposure: Arc::new(RwLock::new(Decimal::ZERO)),
             unrealized_pnl: Arc::new(RwLock::new(Decimal::ZERO)),
             realized_pnl: Arc::new(RwLock::new(Decimal::ZERO)),
+            total_costs: Arc::new(RwLock::new(Decimal::ZERO)),
             peak_equity: Arc::new(RwLock::new(config.portfolio_value)),
             last_trade_at: Arc::new(RwLock::new(None)),
             last_loss_at: Arc::new(RwLock::new(None)),
+            last_portfolio_sync: None,
+            resolution_checks: HashMap::new(),
+            live_started_at: None,
+            statsd,
+            reported_trade_counts: None,
+            clock: Arc::new(SystemClock),
             shutdown: Arc::new(AtomicBool::new(false)),
         })
     }
 
+    /// Read the current time from `clock`, in the bot and its strategy, instead of the
+    /// system clock.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.strategy = self.strategy.with_clock(clock.clone());
+        self.clock = clock;
+        self
+    }
+
     /// Get shutdown signal for external control.
     pub fn shutdown_signal(&self) -> Arc<AtomicBool> {
         self.shutdown.clone()
@@ -141,18 +296,34 @@ impl Bot {
 
         // Initialize or restore bot state
         let portfolio_value = self.config.portfolio_value.to_f64().unwrap_or(1000.0);
-        let bot_state = self.db.init_bot_state(portfolio_value).await?;
+        let poll_interval = i64::try_from(self.config.poll_interval_secs).unwrap_or(i64::MAX);
+        let mut bot_state = self.db.init_bot_state(portfolio_value, poll_interval).await?;
+        if !self.config.dry_run && self.clob_client.is_some() {
+            bot_state = self.db.mark_live_started().await?;
+        }
+        self.live_started_at = bot_state.live_started_at.as_deref().and_then(db::parse_timestamp);
 
-        // Restore state if resuming
         if bot_state.total_trades > 0 {
             info!(
                 total_trades = bot_state.total_trades,
                 total_pnl = bot_state.total_pnl,
                 "Resuming from previous session"
             );
+        }
+
+        // Restore realized P&L: what closed positions booked, less the entry costs already
+        // charged on positions still open (their exits book them again at close)
+        let mut realized = Decimal::try_from(self.db.get_total_realized_pnl().await?)?;
+        for pos in self.db.get_open_positions().await? {
+            realized -= self.estimate_order_cost(Decimal::try_from(pos.size * pos.entry_price)?);
+        }
+        *self.realized_pnl.write().await = realized;
 
-            *self.realized_pnl.write().await = Decimal::try_from(bot_state.total_pnl)?;
-            *self.total_exposure.write().await = Decimal::try_from(bot_state.current_exposure)?;
+        // Keep the high-water mark from earlier sessions so drawdown isn't reset
+        let stored_peak = Decimal::try_from(bot_state.peak_equity).unwrap_or(Decimal::ZERO);
+        if stored_peak > self.config.portfolio_value && !self.config.reset_peak_equity {
+            info!(peak = %stored_peak, "Restored peak equity");
+            *self.peak_equity.write().await = stored_peak;
         }
 
         // Load tracked traders
@@ -163,30 +334,78 @@ impl Bot {
             if let Err(e) = self.copy_engine.add_trader(address.clone()).await {
                 warn!(address = %address, error = %e, "Failed to load trader");
             }
+
+            // Don't re-evaluate trades from before the last session's watermark
+            if let Some((trade_id, trade_at)) = self.db.get_trader_watermark(&address).await? {
+                self.copy_engine.restore_watermark(&address, trade_id, trade_at).await;
+            }
+
+            // Inactivity counts from the last trade seen in any session
+            let (tracking_since, last_trade_seen_at) = self.db.get_trader_activity(&address).await?;
+            self.copy_engine.restore_activity(&address, tracking_since, last_trade_seen_at).await;
         }
 
         // Update copy engine with portfolio value
         self.copy_engine.set_portfolio_value(self.config.portfolio_value).await;
 
+        // Bring stored positions in line with the wallet before restoring them
+        if let (Some(clob), false) = (&self.clob_client, self.config.dry_run) {
+            if self.config.restart_policy != RestartPolicy::TrustDb {
+                let held = self.data_client.get_positions(&clob.funder_address().to_string(), Some(500)).await?;
+                let changed = reconcile_positions(&self.db, self.config.restart_policy, &held).await?;
+                info!(policy = ?self.config.restart_policy, changed, "Reconciled positions with wallet");
+            }
+        }
+
+        // Positions in markets already known to have resolved settle without any API call
+        let settled = self.settle_resolved_positions(true).await?;
+        if settled > 0 {
+            info!(settled, "Settled positions in resolved markets");
+        }
+
         // Restore positions from database
-        let positions = self.db.get_open_positions().await?;
-        let exposure: Decimal = positions
-            .iter()
-            .map(|p| Decimal::try_from(p.size * p.current_price).unwrap_or(Decimal::ZERO))
-            .sum();
-        *self.total_exposure.write().await = exposure;
-        *self.cash_available.write().await = self.config.portfolio_value - exposure;
+        self.recompute_portfolio().await?;
+
+        if self.config.reset_peak_equity {
+            let equity = self.build_portfolio_state().await.equity();
+            *self.peak_equity.write().await = equity;
+            self.db.reset_peak_equity(equity.to_f64().unwrap_or(0.0)).await?;
+            info!(peak = %equity, "Reset peak equity to current equity");
+        }
 
         info!(
             portfolio = %self.config.portfolio_value,
-            exposure = %exposure,
-            positions = positions.len(),
+            exposure = %*self.total_exposure.read().await,
+            positions = self.db.get_open_positions().await?.len(),
             "Bot initialized"
         );
 
         Ok(())
     }
 
+    /// Run doctor checks before live trading, refusing to start if a critical one fails.
+    ///
+    /// Dry runs never place orders, so they skip the checks.
+    pub async fn preflight(&self) -> Result<()> {
+        if self.config.dry_run || !self.config.require_doctor_pass {
+            return Ok(());
+        }
+
+        let max_exposure = *self.portfolio_value.read().await * self.config.trading_config.max_portfolio_allocation;
+        let checks = doctor::run_checks(&self.db, &self.data_client, self.clob_client.as_ref(), max_exposure).await;
+        let failures = doctor::critical_failures(&checks);
+        if !failures.is_empty() {
+            let lines: Vec<String> = failures.iter().map(|c| c.to_string()).collect();
+            anyhow::bail!(
+                "Doctor checks failed, refusing to start live trading:\n  {}",
+                lines.join("\n  ")
+            );
+        }
+
+        info!("Doctor checks passed");
+        Ok(())
+    }
+
     /// Main run loop.
     pub async fn run(&mut self) -> Result<()> {
         info!(
@@ -195,7 +414,21 @@ impl Bot {
             "Starting bot run loop"
         );
 
-        let mut poll_interval = interval(Duration::from_secs(self.config.poll_interval_secs));
+        let base_interval = Duration::from_secs(self.config.poll_interval_secs);
+
+        // Spread per-trader requests across the jitter window
+        if self.config.poll_jitter_pct > 0.0 {
+            let traders = self.copy_engine.get_stats().await.tracked_traders.max(1) as f64;
+            let window = base_interval.as_secs_f64() * self.config.poll_jitter_pct;
+            let stagger = Duration::from_secs_f64((window / traders).min(1.0));
+            self.copy_engine.set_poll_stagger(stagger).await;
+        }
+
+        if let Some(clob) = &self.clob_client {
+            if self.market_stream.is_none() && self.config.trading_config.price_sources.contains(&PriceSource::Stream) {
+                self.market_stream = Some(MarketStream::spawn(clob.market_ws_url()));
+            }
+        }
 
         // Register shutdown handler
         let shutdown = self.shutdown.clone();
@@ -205,13 +438,28 @@ impl Bot {
             shutdown.store(true, Ordering::SeqCst);
         });
 
-        while !self.shutdown.load(Ordering::SeqCst) {
-            poll_interval.tick().await;
+        let deadline = self.config.max_runtime.map(|runtime| Instant::now() + runtime);
 
+        while !self.shutdown.load(Ordering::SeqCst) {
+            let tick_started = Instant::now();
             if let Err(e) = self.tick().await {
                 error!(error = %e, "Error in bot tick");
                 // Continue running unless it's a critical error
             }
+            self.report_metrics(tick_started.elapsed()).await;
+
+            let mut delay = jittered_interval(base_interval, self.config.poll_jitter_pct, random_unit());
+            if let Some(deadline) = deadline {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    info!("Max runtime reached, stopping");
+                    self.shutdown.store(true, Ordering::SeqCst);
+                    break;
+                }
+                delay = delay.min(remaining);
+            }
+            debug!(delay_ms = delay.as_millis() as u64, "Sleeping until next poll");
+            sleep(delay).await;
         }
 
         // Graceful shutdown
@@ -220,31 +468,66 @@ impl Bot {
         Ok(())
     }
 
-    /// Single iteration of the main loop.
+    /// Record that the bot is alive from within a tick, after a step that may have waited.
+    async fn heartbeat(&self) {
+        if let Err(e) = self.db.record_heartbeat().await {
+            warn!(error = %e, "Failed to record heartbeat");
+        }
+    }
+
+    /// Single iteration of the main loop, its writes batched into one transaction if configured.
     async fn tick(&mut self) -> Result<()> {
         debug!("Bot tick");
+        self.db.record_heartbeat().await?;
+
+        if !self.config.batch_db_writes {
+            return self.run_tick().await;
+        }
+        self.db.begin_batch().await?;
+        match self.run_tick().await {
+            Ok(()) => self.db.commit_batch().await,
+            Err(e) => {
+                if let Err(rollback) = self.db.rollback_batch().await {
+                    error!(error = %rollback, "Failed to roll back tick writes");
+                }
+                Err(e)
+            }
+        }
+    }
 
-        // 1. Check portfolio risk - halt if necessary
+    async fn run_tick(&mut self) -> Result<()> {
+        // 1. Check portfolio risk - bank gains at the take-profit target, halt if necessary
         let portfolio = self.build_portfolio_state().await;
+        if self.strategy.portfolio_take_profit_hit(&portfolio).is_some() && portfolio.position_count > 0 {
+            self.liquidate_all().await?;
+        }
         let (should_halt, halt_reason) = self.strategy.should_halt_trading(&portfolio);
         if should_halt {
             warn!(reason = %halt_reason, "Trading halted due to risk limits");
             return Ok(());
         }
 
-        // 2. Update position prices and check exits
+        // 2. Settle resolved markets, update position prices and check exits
+        self.settle_resolved_positions(false).await?;
         self.update_positions().await?;
+        if self.config.auto_sync_portfolio {
+            if let Err(e) = self.sync_portfolio_value().await {
+                warn!(error = %e, "Failed to sync portfolio value from wallet");
+            }
+        }
         self.check_exits().await?;
 
-        // 3. Poll for new trades
+        // 3. Poll for new trades, picking up pauses made while running
+        self.prune_losing_traders().await?;
+        let paused = self.db.get_paused_addresses().await?;
+        self.copy_engine.set_paused_traders(&paused).await;
+        self.copy_engine.set_allocation_cap(self.allocation_cap(self.clock.now())).await;
         let new_intents = self.copy_engine.poll_for_trades().await?;
+        self.persist_watermarks().await?;
+        self.check_inactive_traders().await?;
 
-        // 4. Validate and execute new trades
-        for intent in new_intents {
-            if let Err(e) = self.process_trade_intent(intent).await {
-                warn!(error = %e, "Failed to process trade intent");
-            }
-        }
+        // 4. Validate and execute new trades, after any deferred from earlier ticks
+        self.process_intents(new_intents).await?;
 
         // 5. Process any pending trades from database
         self.process_pending_trades().await?;
@@ -258,31 +541,210 @@ impl Bot {
         Ok(())
     }
 
+    /// Process deferred intents then `new_intents`, up to `max_executions_per_tick`;
+    /// the remainder is persisted for the next tick.
+    async fn process_intents(&mut self, new_intents: Vec<CopyTradeIntent>) -> Result<()> {
+        let mut intents = Vec::new();
+        for json in self.db.take_pending_intents().await? {
+            match serde_json::from_str::<CopyTradeIntent>(&json) {
+                Ok(intent) => intents.push(intent),
+                Err(e) => warn!(error = %e, "Dropping unreadable deferred intent"),
+            }
+        }
+        intents.extend(new_intents);
+
+        let limit = self.config.max_executions_per_tick.unwrap_or(usize::MAX);
+        let deferred = intents.split_off(limit.min(intents.len()));
+        if !deferred.is_empty() {
+            info!(deferred = deferred.len(), limit = limit, "Execution limit reached, deferring intents");
+            for intent in &deferred {
+                self.db.push_pending_intent(&serde_json::to_string(intent)?).await?;
+            }
+        }
+
+        let mut decisions: HashMap<&'static str, usize> = HashMap::new();
+        for intent in intents {
+            match self.process_trade_intent(intent).await {
+                Ok(decision) => {
+                    debug!(decision = %decision, "Trade intent processed");
+                    *decisions.entry(decision.label()).or_default() += 1;
+                }
+                Err(e) => warn!(error = %e, "Failed to process trade intent"),
+            }
+        }
+        if !decisions.is_empty() {
+            debug!(decisions = ?decisions, "Processed trade intents");
+        }
+        Ok(())
+    }
+
+    /// Warn about traders who stopped trading, pausing them under `auto_pause_inactive`.
+    async fn check_inactive_traders(&self) -> Result<()> {
+        let days = self.config.trading_config.inactive_after_days;
+        for address in self.copy_engine.detect_inactive_traders(self.clock.now()).await {
+            if self.config.trading_config.auto_pause_inactive {
+                self.db.set_trader_paused(&address, true).await?;
+                warn!(trader = %address, days, "Trader inactive, pausing");
+            } else {
+                warn!(trader = %address, days, "Trader inactive: no trades seen in the inactivity window");
+            }
+        }
+        Ok(())
+    }
+
+    /// Pause or untrack traders whose copied positions lost too much over the prune
+    /// window, under `auto_prune_losers`.
+    async fn prune_losing_traders(&self) -> Result<()> {
+        let config = self.strategy.config();
+        if !config.auto_prune_losers {
+            return Ok(());
+        }
+
+        let since = self.clock.now() - chrono::Duration::days(config.prune_window_days);
+        let closed: Vec<StoredPosition> = self
+            .db
+            .get_closed_positions()
+            .await?
+            .into_iter()
+            .filter(|p| p.source_trader.is_some())
+            .filter(|p| p.closed_at.as_deref().and_then(db::parse_timestamp).is_some_and(|at| at >= since))
+            .collect();
+        let tracked = self.db.get_tracked_addresses().await?;
+        let paused = self.db.get_paused_addresses().await?;
+
+        for row in attribution::attribute(&closed, |p| p.source_trader.clone().unwrap_or_default()) {
+            let realized = Decimal::try_from(row.realized_pnl).unwrap_or_default();
+            if !tracked.contains(&row.key) || !self.strategy.should_prune(realized) {
+                continue;
+            }
+            match config.prune_action {
+                PruneAction::Pause if paused.contains(&row.key) => continue,
+                PruneAction::Pause => {
+                    self.db.set_trader_paused(&row.key, true).await?;
+                }
+                PruneAction::Untrack => {
+                    self.copy_engine.remove_trader(&row.key).await;
+                    self.db.remove_trader(&row.key).await?;
+                }
+            }
+            warn!(
+                trader = %row.key,
+                realized_pnl = %realized,
+                trades = row.trades,
+                days = config.prune_window_days,
+                action = %config.prune_action,
+                "Copied trades from this trader keep losing, pruning"
+            );
+        }
+        Ok(())
+    }
+
+    /// Persist per-trader watermarks so a restart never re-evaluates older trades, and
+    /// each trader's last trade so inactivity survives one.
+    async fn persist_watermarks(&self) -> Result<()> {
+        for (address, trade_at) in self.copy_engine.last_trades_seen().await {
+            self.db.update_trader_last_trade(&address, trade_at).await?;
+        }
+        for (address, (trade_id, trade_at)) in self.copy_engine.watermarks().await {
+            self.db.update_trader_watermark(&address, &trade_id, trade_at).await?;
+        }
+        Ok(())
+    }
+
     /// Process a new copy trade intent.
-    async fn process_trade_intent(&mut self, intent: CopyTradeIntent) -> Result<()> {
+    async fn process_trade_intent(&mut self, intent: CopyTradeIntent) -> Result<TradeDecision> {
         let trade = &intent.source_trade;
 
-        // Check if we've already seen this trade
-        let trade_id = format!(
+        // Check if we've already seen this trade. Keyed by trade ID so fills sharing a
+        // transaction stay distinct; the old trader-market-time key still counts as seen.
+        let trade_id = format!("{}-{}", trade.trader_address, trade.id);
+        let legacy_id = format!(
             "{}-{}-{}",
             trade.trader_address, trade.market_id, trade.timestamp.timestamp()
         );
-        if self.db.has_seen_trade(&trade_id).await? {
+        if self.db.has_seen_trade(&trade_id).await? || self.db.has_seen_trade(&legacy_id).await? {
             debug!(trade_id = %trade_id, "Trade already seen, skipping");
-            return Ok(());
+            return Ok(TradeDecision::Deduped);
+        }
+
+        // Skip dust trades before doing any further work
+        if !self.strategy.meets_min_source_size(trade.amount_usdc) {
+            debug!(
+                market = %trade.market_id,
+                amount = %trade.amount_usdc,
+                "Source trade below minimum notional, skipping"
+            );
+            self.db.mark_trade_seen(&trade_id, &trade.trader_address, &trade.market_id).await?;
+            return Ok(TradeDecision::Rejected { reason: "Source trade below minimum".to_string() });
+        }
+
+        // Optionally follow only brand-new positions, not adds to existing ones
+        if matches!(trade.side, TradeSide::Buy) {
+            if !self.strategy.allows_source_entry(intent.source_held_market) {
+                info!(
+                    market = %trade.market_id,
+                    reason = "Source already held market",
+                    "Trade rejected by strategy"
+                );
+                self.db.mark_trade_seen(&trade_id, &trade.trader_address, &trade.market_id).await?;
+                return Ok(TradeDecision::Rejected { reason: "Source already held market".to_string() });
+            }
+
+            let source_pnl = intent.source_position_pnl;
+            if !self.strategy.allows_source_pnl(source_pnl) {
+                info!(
+                    market = %trade.market_id,
+                    source_pnl = ?source_pnl,
+                    reason = "Source position P&L filtered",
+                    "Trade rejected by strategy"
+                );
+                self.db.mark_trade_seen(&trade_id, &trade.trader_address, &trade.market_id).await?;
+                return Ok(TradeDecision::Rejected { reason: "Source position P&L filtered".to_string() });
+            }
+        }
+
+        // Never trade into a market that has already settled. A failed status lookup
+        // leaves the trade unseen so it's retried next tick; without a CLOB client only
+        // resolutions already recorded are known.
+        let market_info = match &self.clob_client {
+            Some(clob) => Some(
+                clob.get_market(&trade.market_id)
+                    .await
+                    .with_context(|| format!("Market status unavailable for {}", trade.market_id))?,
+            ),
+            None => None,
+        };
+        let resolved = match &market_info {
+            Some(market) => self.record_resolution(&trade.market_id, market).await?,
+            None => self.db.get_resolved_market(&trade.market_id).await?,
+        };
+        if resolved.is_some() {
+            info!(
+                market = %trade.market_id,
+                reason = "Market resolved",
+                "Trade rejected by strategy"
+            );
+            self.db.mark_trade_seen(&trade_id, &trade.trader_address, &trade.market_id).await?;
+            return Ok(TradeDecision::Rejected { reason: "Market resolved".to_string() });
         }
 
         // Get current market price
-        let current_price = self.get_current_price(&trade.market_id, &trade.outcome).await?;
+        let current_price = self.get_copy_price(trade).await?;
+
+        let order_side = match trade.side {
+            TradeSide::Buy => OrderSide::Buy,
+            TradeSide::Sell => OrderSide::Sell,
+        };
+        let book = self.get_order_book(&trade.market_id, &trade.outcome).await;
+        let book_depth = book.as_ref().map(|b| b.depth_usdc(order_side));
 
         // Validate entry
         let portfolio = self.build_portfolio_state().await;
         let market_positions = self.get_market_positions(&trade.market_id).await?;
+        let prior_entries: usize = market_positions.iter().map(|p| p.entries as usize).sum();
 
         let validation = self.strategy.validate_entry(
-            trade.timestamp,
-            current_price,
-            trade.price,
+            &EntryQuote { book_depth, ..EntryQuote::new(trade.timestamp, current_price, trade.price) },
             intent.calculated_size,
             None, // Would fetch trader metrics here
             &portfolio,
@@ -297,11 +759,93 @@ impl Bot {
                 "Trade rejected by strategy"
             );
             self.db.mark_trade_seen(&trade_id, &trade.trader_address, &trade.market_id).await?;
-            return Ok(());
+            return Ok(TradeDecision::Rejected { reason: validation.reason });
         }
 
         let size = validation.adjusted_size.unwrap_or(intent.calculated_size);
 
+        // Optionally let the source's own impact settle, then make sure the price still
+        // works against the book as it stands after the delay
+        let (current_price, book, book_depth) = match self.strategy.copy_delay() {
+            Some(delay) => {
+                debug!(market = %trade.market_id, delay = ?delay, "Delaying copy");
+                tokio::time::sleep(delay).await;
+                self.heartbeat().await;
+                let price = self.get_copy_price(trade).await?;
+                let book = self.get_order_book(&trade.market_id, &trade.outcome).await;
+                let book_depth = book.as_ref().map(|b| b.depth_usdc(order_side));
+                if let Some(denied) =
+                    self.strategy.check_entry_price(price, trade.price, book_depth, prior_entries)
+                {
+                    info!(
+                        market = %trade.market_id,
+                        price = %price,
+                        reason = %denied.reason,
+                        "Price moved during copy delay, cancelling copy"
+                    );
+                    self.db.mark_trade_seen(&trade_id, &trade.trader_address, &trade.market_id).await?;
+                    return Ok(TradeDecision::Rejected { reason: denied.reason });
+                }
+                (price, book, book_depth)
+            }
+            None => (current_price, book, book_depth),
+        };
+
+        // Never take more than our share of what the book holds within the slippage band
+        let size = match &book {
+            Some(book) => {
+                let band = self.strategy.slippage_budget(book_depth, prior_entries);
+                let capped = self.strategy.cap_to_book_depth(size, book.depth_within_usdc(order_side, band));
+                if capped < size {
+                    info!(
+                        market = %trade.market_id,
+                        proposed = %size,
+                        allowed = %capped,
+                        "Reducing size to fit book liquidity"
+                    );
+                }
+                capped
+            }
+            None => size,
+        };
+
+        // Stay a small part of the market's daily flow
+        let size = match market_info.as_ref().and_then(|m| m.volume_24h) {
+            Some(volume) => {
+                let capped = self.strategy.cap_to_volume(size, volume);
+                if capped < size {
+                    info!(
+                        market = %trade.market_id,
+                        proposed = %size,
+                        allowed = %capped,
+                        volume_24h = %volume,
+                        "Reducing size to fit 24h volume"
+                    );
+                }
+                capped
+            }
+            None => size,
+        };
+
+        // Quantize to the CLOB's share granularity, rounding down to avoid over-buying.
+        // The size is in USDC; orders and positions are in shares, so round the share
+        // count it buys and keep the USDC it costs for the copy trade record
+        let shares = size.checked_div(current_price).unwrap_or(Decimal::ZERO);
+        let shares = match &market_info {
+            Some(market) => market.round_shares(shares),
+            None => round_to_lot(shares, SHARE_LOT_SIZE),
+        };
+        let size = shares * current_price;
+        if shares <= Decimal::ZERO {
+            info!(
+                market = %trade.market_id,
+                reason = "Size rounds to zero",
+                "Trade rejected by strategy"
+            );
+            self.db.mark_trade_seen(&trade_id, &trade.trader_address, &trade.market_id).await?;
+            return Ok(TradeDecision::Rejected { reason: "Size rounds to zero".to_string() });
+        }
+
         // Create copy trade record
         let copy_trade_id = uuid::Uuid::new_v4().to_string();
         self.db.save_copy_trade(
@@ -321,21 +865,27 @@ impl Bot {
         self.db.mark_trade_seen(&trade_id, &trade.trader_address, &trade.market_id).await?;
 
         // Execute the trade
-        if self.config.dry_run || self.clob_client.is_none() {
+        let decision = if self.config.dry_run || self.clob_client.is_none() {
+            // Fill against the side of the book a real order would cross
+            let fill_price = book
+                .as_ref()
+                .and_then(|b| b.taker_fill_price(order_side, shares))
+                .unwrap_or(current_price);
+            let size = shares * fill_price;
             info!(
                 market = %trade.market_id,
                 side = ?trade.side,
                 size = %size,
-                price = %current_price,
+                price = %fill_price,
                 "[DRY RUN] Would execute trade"
             );
 
             // Simulate execution
             self.db.update_copy_trade_status(
                 &copy_trade_id,
-                "simulated",
+                CopyTradeStatus::Simulated,
                 None,
-                Some(current_price.to_f64().unwrap_or(0.0)),
+                Some(fill_price.to_f64().unwrap_or(0.0)),
                 None,
                 None,
             ).await?;
@@ -345,20 +895,30 @@ impl Bot {
                 &trade.market_id,
                 &trade.outcome,
                 &trade.side,
-                size,
-                current_price,
+                shares,
+                fill_price,
                 Some(&intent.source_trader),
             ).await?;
+
+            TradeDecision::Simulated { price: fill_price, size }
         } else {
-            // Real execution
+            // Real execution, keyed by the source trade so that processing it again, even
+            // after a restart, re-signs the same order rather than placing a second one
             let result = self.execute_trade(
                 &trade.market_id,
                 &trade.outcome,
                 &trade.side,
-                size,
+                shares,
+                current_price,
+                self.strategy.entry_patience(ExitUrgency::Normal),
+                Some((&trade_id, trade.timestamp)),
             ).await;
+            // A patient entry can rest for most of a minute
+            self.heartbeat().await;
+            // The order can't be taken back, so neither can its record
+            self.db.checkpoint_batch().await?;
 
-            match result {
+            let decision = match result {
                 Ok(response) => {
                     info!(
                         order_id = ?response.order_id,
@@ -366,66 +926,108 @@ impl Bot {
                         "Trade executed"
                     );
 
+                    let review = match (&self.clob_client, &response.order_id) {
+                        (Some(clob), Some(order_id)) if self.strategy.config().max_realized_slippage.is_some() => {
+                            let token_id = self.resolve_token_id(&trade.market_id, &trade.outcome).await;
+                            review_fill(clob, &self.strategy, order_id, &token_id, &trade.side, shares, current_price).await
+                        }
+                        _ => FillReview::Unknown,
+                    };
+                    let (fill_price, flag) = match &review {
+                        FillReview::Ok { fill_price } => (*fill_price, None),
+                        FillReview::BadFill { fill_price, slippage, .. } => (
+                            *fill_price,
+                            Some(format!("Realized slippage {}% exceeds limit", display::pct(*slippage))),
+                        ),
+                        FillReview::Unknown => (current_price, None),
+                    };
+
                     self.db.update_copy_trade_status(
                         &copy_trade_id,
-                        "executed",
+                        CopyTradeStatus::Executed,
                         response.order_id.as_deref(),
-                        Some(current_price.to_f64().unwrap_or(0.0)),
+                        Some(fill_price.to_f64().unwrap_or(0.0)),
                         response.transaction_hash.as_deref(),
-                        None,
+                        flag.as_deref(),
                     ).await?;
 
-                    // Update position
-                    self.update_position_after_trade(
-                        &trade.market_id,
-                        &trade.outcome,
-                        &trade.side,
-                        size,
-                        current_price,
-                        Some(&intent.source_trader),
-                    ).await?;
+                    // Update position, unless a bad fill was already unwound
+                    if !matches!(review, FillReview::BadFill { closed: true, .. }) {
+                        self.update_position_after_trade(
+                            &trade.market_id,
+                            &trade.outcome,
+                            &trade.side,
+                            shares,
+                            fill_price,
+                            Some(&intent.source_trader),
+                        ).await?;
+                    }
+
+                    TradeDecision::Executed { order_id: response.order_id }
                 }
                 Err(e) => {
                     error!(error = %e, "Trade execution failed");
                     self.db.update_copy_trade_status(
                         &copy_trade_id,
-                        "failed",
+                        CopyTradeStatus::Failed,
                         None,
                         None,
                         None,
                         Some(&e.to_string()),
                     ).await?;
+
+                    TradeDecision::Failed { error: e.to_string() }
                 }
-            }
-        }
+            };
+            // Likewise the fill's status and position updates
+            self.db.checkpoint_batch().await?;
+            decision
+        };
 
         // Update last trade time
-        *self.last_trade_at.write().await = Some(Utc::now());
+        *self.last_trade_at.write().await = Some(self.clock.now());
 
-        Ok(())
+        Ok(decision)
     }
 
     /// Execute a trade via CLOB.
+    ///
+    /// With a `patience` window, a limit order is rested at `price` first and only
+    /// escalated to a market order if it hasn't filled by the end of the window.
+    /// A `client_order` (id and decision time) keeps the order id stable across retries,
+    /// so both must be derived from the source trade rather than generated per attempt.
+    #[allow(clippy::too_many_arguments)]
     async fn execute_trade(
         &self,
         market_id: &str,
         outcome: &str,
         side: &TradeSide,
         size: Decimal,
+        price: Decimal,
+        patience: Option<Duration>,
+        client_order: Option<(&str, DateTime<Utc>)>,
     ) -> Result<OrderResponse> {
         let clob = self.clob_client.as_ref()
             .context("CLOB client not configured")?;
 
-        // Get token ID for this outcome
-        // In production, this would come from the market info
-        let token_id = format!("{}:{}", market_id, outcome);
+        let token_id = self.resolve_token_id(market_id, outcome).await;
 
         let order_side = match side {
             TradeSide::Buy => OrderSide::Buy,
             TradeSide::Sell => OrderSide::Sell,
         };
 
-        clob.market_order(&token_id, order_side, size).await
+        match patience {
+            Some(window) => {
+                place_with_patience(clob, &token_id, order_side, size, price, window, PATIENCE_POLL).await
+            }
+            None => match client_order {
+                Some((id, created_at)) => {
+                    clob.market_order_with_client_id(&token_id, order_side, size, id, created_at).await
+                }
+                None => clob.market_order(&token_id, order_side, size).await,
+            },
+        }
     }
 
     /// Update positions after a trade.
@@ -452,22 +1054,170 @@ impl Bot {
             price.to_f64().unwrap_or(0.0),
             source_trader,
         ).await?;
+        // Mark at the fill so exposure counts the position before the next price update
+        self.db.update_position_price(market_id, outcome, price.to_f64().unwrap_or(0.0)).await?;
 
-        // Update portfolio state
-        let cost = size * price;
-        if matches!(side, TradeSide::Buy) {
-            *self.cash_available.write().await -= cost;
-            *self.total_exposure.write().await += cost;
-        }
+        self.charge_order_cost(size * price).await;
+        self.recompute_portfolio().await
+    }
 
-        Ok(())
+    /// Estimated trading cost of one order with the given notional.
+    fn estimate_order_cost(&self, notional: Decimal) -> Decimal {
+        self.config.order_cost_usdc + notional.abs() * self.config.order_cost_rate
+    }
+
+    /// Book the estimated cost of an order against realized P&L.
+    async fn charge_order_cost(&self, notional: Decimal) -> Decimal {
+        let cost = self.estimate_order_cost(notional);
+        if cost > Decimal::ZERO {
+            *self.realized_pnl.write().await -= cost;
+            *self.total_costs.write().await += cost;
+        }
+        cost
     }
 
-    /// Get current price for a market outcome.
+    /// Get current price for a market outcome, to mark a position. Any configured source
+    /// may serve it, stale if nothing fresher is available.
     async fn get_current_price(&self, market_id: &str, outcome: &str) -> Result<Decimal> {
-        // In production, this would query the order book
-        // For now, return a placeholder
-        Ok(dec!(0.50))
+        let quote = self.quote(&self.price_chain, market_id, outcome).await?;
+        if quote.stale {
+            warn!(market = %market_id, outcome = %outcome, source = %quote.source, "Using stale price");
+        }
+        Ok(quote.price)
+    }
+
+    /// Get the price to enter a market outcome at: a fresh price from the market itself.
+    async fn get_entry_price(&self, market_id: &str, outcome: &str) -> Result<Decimal> {
+        let quote = self.quote(&self.price_chain.market_only(), market_id, outcome).await?;
+        if quote.stale {
+            anyhow::bail!("Only a stale {} price for {}:{}", quote.source, market_id, outcome);
+        }
+        Ok(quote.price)
+    }
+
+    /// Get the price to copy a trade at: a fresh market price, or in a dry run without a
+    /// CLOB client to quote it, the source's own fill price.
+    async fn get_copy_price(&self, trade: &Trade) -> Result<Decimal> {
+        if self.clob_client.is_none() && self.config.dry_run {
+            return Ok(trade.price);
+        }
+        self.get_entry_price(&trade.market_id, &trade.outcome).await
+    }
+
+    async fn quote(&self, chain: &PriceChain, market_id: &str, outcome: &str) -> Result<Quote> {
+        // Without a CLOB client there's no price source, so callers fall back themselves
+        let clob = self
+            .clob_client
+            .as_ref()
+            .with_context(|| format!("No CLOB client to price {}:{}", market_id, outcome))?;
+
+        let key = format!("{}:{}", market_id, outcome);
+        chain.quote(&key, |source| self.price_from(clob, source, market_id, outcome)).await
+    }
+
+    /// Price of a market outcome from one source, and when it was observed.
+    async fn price_from(
+        &self,
+        clob: &ClobClient,
+        source: PriceSource,
+        market_id: &str,
+        outcome: &str,
+    ) -> Result<(Decimal, chrono::DateTime<Utc>)> {
+        match source {
+            PriceSource::Stream => {
+                let stream = self.market_stream.as_ref().context("No market stream")?;
+                let token_id = self.stream_token(market_id, outcome).await?;
+                // Until the first update arrives the chain falls back to the book
+                stream.subscribe([token_id.clone()]).await;
+                stream.mid_price(&token_id).await.context("No streamed price yet")
+            }
+            PriceSource::ClobBook => Ok((clob.outcome_mid_price(market_id, outcome).await?, Utc::now())),
+            PriceSource::DataApi => self
+                .copy_engine
+                .trader_quote(market_id, outcome)
+                .await
+                .context("No tracked trader holds this outcome"),
+            PriceSource::LastStored => {
+                let (price, at) = self
+                    .db
+                    .last_price(market_id, outcome)
+                    .await?
+                    .context("No stored price for this outcome")?;
+                Ok((Decimal::try_from(price)?, at))
+            }
+        }
+    }
+
+    /// CLOB token ID for a market outcome, looked up once per outcome.
+    async fn stream_token(&self, market_id: &str, outcome: &str) -> Result<String> {
+        let key = format!("{}:{}", market_id, outcome);
+        if let Some(token_id) = self.stream_tokens.read().await.get(&key) {
+            return Ok(token_id.clone());
+        }
+        let token_id = self
+            .get_market_info(market_id)
+            .await
+            .and_then(|m| m.token_id(outcome).map(str::to_string))
+            .context("No CLOB token for this outcome")?;
+        self.stream_tokens.write().await.insert(key, token_id.clone());
+        Ok(token_id)
+    }
+
+    /// CLOB token ID for a market outcome, from Gamma market info when available.
+    async fn resolve_token_id(&self, market_id: &str, outcome: &str) -> String {
+        self.get_market_info(market_id)
+            .await
+            .and_then(|m| m.token_id(outcome).map(str::to_string))
+            .unwrap_or_else(|| format!("{}:{}", market_id, outcome))
+    }
+
+    /// Get market info, if a CLOB client is available.
+    async fn get_market_info(&self, market_id: &str) -> Option<MarketInfo> {
+        let clob = self.clob_client.as_ref()?;
+        match clob.get_market(market_id).await {
+            Ok(market) => Some(market),
+            Err(e) => {
+                debug!(market = %market_id, error = %e, "Could not fetch market info");
+                None
+            }
+        }
+    }
+
+    /// A market's resolution, from the database or else the market API, caching it once
+    /// the market has resolved.
+    async fn market_resolution(&self, market_id: &str) -> Result<Option<ResolvedMarket>> {
+        if let Some(resolved) = self.db.get_resolved_market(market_id).await? {
+            return Ok(Some(resolved));
+        }
+        match self.get_market_info(market_id).await {
+            Some(market) => self.record_resolution(market_id, &market).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Cache a market's resolution if it has one, timed at the market's close or, if the
+    /// API doesn't report one, when it was first observed.
+    async fn record_resolution(&self, market_id: &str, market: &MarketInfo) -> Result<Option<ResolvedMarket>> {
+        let Some(winner) = market.winning_outcome() else {
+            return Ok(None);
+        };
+        let resolved_at = market.closed_time.unwrap_or_else(|| self.clock.now());
+        self.db.save_resolved_market(market_id, winner, resolved_at).await?;
+        self.db.get_resolved_market(market_id).await
+    }
+
+    /// Get the order book for a market outcome, if a CLOB client is available.
+    async fn get_order_book(&self, market_id: &str, outcome: &str) -> Option<OrderBook> {
+        let clob = self.clob_client.as_ref()?;
+        let token_id = self.resolve_token_id(market_id, outcome).await;
+
+        match clob.get_order_book(&token_id).await {
+            Ok(book) => Some(book),
+            Err(e) => {
+                debug!(market = %market_id, error = %e, "Could not fetch order book depth");
+                None
+            }
+        }
     }
 
     /// Get positions for a specific market.
@@ -476,24 +1226,31 @@ impl Bot {
         Ok(positions
             .iter()
             .filter(|p| p.market_id == market_id)
-            .map(|p| self.convert_position(p))
+            .map(Self::convert_position)
             .collect())
     }
 
     /// Convert stored position to strategy position.
-    fn convert_position(&self, stored: &StoredPosition) -> StrategyPosition {
+    pub fn convert_position(stored: &StoredPosition) -> StrategyPosition {
+        let entry_price = Decimal::try_from(stored.entry_price).unwrap_or(Decimal::ZERO);
+        let current_price = Decimal::try_from(stored.current_price).unwrap_or(Decimal::ZERO);
+        let size = Decimal::try_from(stored.size).unwrap_or(Decimal::ZERO);
         StrategyPosition {
             market_id: stored.market_id.clone(),
             outcome: stored.outcome.clone(),
             side: stored.side.clone(),
-            entry_price: Decimal::try_from(stored.entry_price).unwrap_or(Decimal::ZERO),
-            current_price: Decimal::try_from(stored.current_price).unwrap_or(Decimal::ZERO),
-            size: Decimal::try_from(stored.size).unwrap_or(Decimal::ZERO),
-            unrealized_pnl: Decimal::try_from(stored.unrealized_pnl).unwrap_or(Decimal::ZERO),
-            opened_at: chrono::DateTime::parse_from_rfc3339(&stored.opened_at)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(|_| Utc::now()),
+            entry_price,
+            current_price,
+            size,
+            // Recomputed rather than read back: the stored value carries float rounding
+            unrealized_pnl: signed(&stored.side, (current_price - entry_price) * size),
+            peak_price: Decimal::try_from(stored.peak_price.unwrap_or(stored.entry_price)).unwrap_or(Decimal::ZERO),
+            opened_at: db::parse_timestamp(&stored.opened_at).unwrap_or_else(|| {
+                warn!(market = %stored.market_id, opened_at = %stored.opened_at, "Unparseable position timestamp");
+                Utc::now()
+            }),
             source_trader: stored.source_trader.clone(),
+            entries: u32::try_from(stored.entries).unwrap_or(1),
         }
     }
 
@@ -501,8 +1258,18 @@ impl Bot {
     async fn update_positions(&mut self) -> Result<()> {
         let positions = self.db.get_open_positions().await?;
 
+        let keys = positions.iter().map(|p| (p.market_id.clone(), p.outcome.clone())).collect();
+        let bot = &*self;
+        let prices = fetch_prices(keys, self.config.price_fetch_concurrency, |(market_id, outcome)| async move {
+            bot.get_current_price(&market_id, &outcome).await
+        })
+        .await;
+
         for pos in positions {
-            let price = self.get_current_price(&pos.market_id, &pos.outcome).await?;
+            let Some(&price) = prices.get(&(pos.market_id.clone(), pos.outcome.clone())) else {
+                debug!(market = %pos.market_id, "No price for position, keeping last price");
+                continue;
+            };
             self.db.update_position_price(
                 &pos.market_id,
                 &pos.outcome,
@@ -510,76 +1277,276 @@ impl Bot {
             ).await?;
         }
 
-        // Recalculate portfolio state
+        self.recompute_portfolio().await
+    }
+
+    /// Recompute exposure, unrealized P&L and cash from the open positions in the database,
+    /// their only source of truth. Call after anything that opens or closes a position.
+    async fn recompute_portfolio(&self) -> Result<()> {
         let positions = self.db.get_open_positions().await?;
         let mut total_exposure = Decimal::ZERO;
+        let mut net_exposure = Decimal::ZERO;
+        let mut cost_basis = Decimal::ZERO;
         let mut total_unrealized = Decimal::ZERO;
 
+        // A short is opened by selling, so it brings in its cost basis and owes its market value
         for pos in &positions {
             let size = Decimal::try_from(pos.size)?;
             let current = Decimal::try_from(pos.current_price)?;
             let entry = Decimal::try_from(pos.entry_price)?;
 
             total_exposure += size * current;
-            total_unrealized += (current - entry) * size;
+            net_exposure += signed(&pos.side, size * current);
+            cost_basis += signed(&pos.side, size * entry);
+            total_unrealized += signed(&pos.side, (current - entry) * size);
         }
 
+        // Offsetting positions don't add directional risk, so sizing caps use net exposure
+        self.copy_engine.set_current_exposure(net_exposure.abs()).await;
+
         *self.total_exposure.write().await = total_exposure;
+        *self.net_exposure.write().await = net_exposure;
         *self.unrealized_pnl.write().await = total_unrealized;
-        *self.cash_available.write().await = self.config.portfolio_value - total_exposure + *self.realized_pnl.read().await;
+        // Cash paid for open positions is their cost basis; their mark-to-market moves only
+        // unrealized P&L. Losses can push this below zero, which leaves no cash to enter with.
+        let cash = *self.capital.read().await - cost_basis + *self.realized_pnl.read().await;
+        if cash < Decimal::ZERO {
+            debug!(cash = %cash, "Computed cash is negative");
+        }
+        *self.cash_available.write().await = cash;
 
         Ok(())
     }
 
-    /// Check exits for all positions.
-    async fn check_exits(&mut self) -> Result<()> {
-        let positions = self.db.get_open_positions().await?;
-        let portfolio = self.build_portfolio_state().await;
-
-        // Get trader holdings (simplified - would need to fetch from API)
-        let trader_holdings: HashMap<String, Vec<String>> = HashMap::new();
+    /// Refresh the sizing base from the wallet's USDC balance plus open position value.
+    async fn sync_portfolio_value(&mut self) -> Result<()> {
+        let Some(clob) = &self.clob_client else {
+            return Ok(());
+        };
+        if matches!(self.last_portfolio_sync, Some(at) if at.elapsed() < PORTFOLIO_SYNC_INTERVAL) {
+            return Ok(());
+        }
 
-        let strategy_positions: Vec<_> = positions.iter().map(|p| self.convert_position(p)).collect();
+        let cash = clob.get_collateral_balance().await?;
+        let positions_value = self.data_client.get_portfolio_value(&clob.funder_address().to_string()).await?;
+        self.last_portfolio_sync = Some(Instant::now());
 
-        let exits = self.strategy.evaluate_exits(&strategy_positions, &portfolio, &trader_holdings);
+        let synced = cash + positions_value;
+        if synced <= Decimal::ZERO {
+            warn!("Wallet reports no balance, keeping previous portfolio value");
+            return Ok(());
+        }
 
-        for (pos, signal) in exits {
-            info!(
+        let previous = self.copy_engine.portfolio_value().await;
+        if synced != previous {
+            info!(previous = %previous, synced = %synced, "Portfolio value synced from wallet");
+        }
+        self.copy_engine.set_portfolio_value(synced).await;
+        *self.portfolio_value.write().await = synced;
+
+        // Rebase capital so the cash recompute_portfolio derives matches the wallet
+        let cost_basis: Decimal = self
+            .db
+            .get_open_positions()
+            .await?
+            .iter()
+            .map(|p| signed(&p.side, Decimal::try_from(p.size * p.entry_price).unwrap_or(Decimal::ZERO)))
+            .sum();
+        *self.capital.write().await = cash + cost_basis - *self.realized_pnl.read().await;
+        self.recompute_portfolio().await
+    }
+
+    /// Check exits for all positions.
+    async fn check_exits(&mut self) -> Result<()> {
+        let positions = self.db.get_open_positions().await?;
+        let portfolio = self.build_portfolio_state().await;
+
+        // Trader holdings, deferring trader exits if the snapshot is stale
+        if self.strategy.config().follow_trader_exits {
+            self.copy_engine.refresh_holdings().await;
+        }
+        let (trader_holdings, holdings_as_of) = self.copy_engine.holdings_snapshot().await;
+
+        let strategy_positions: Vec<_> = positions.iter().map(Self::convert_position).collect();
+
+        let exits = self.strategy.evaluate_exits(&strategy_positions, &portfolio, &trader_holdings, holdings_as_of);
+
+        for (pos, signal) in exits {
+            self.exit_position(&pos, &signal).await?;
+        }
+
+        // Free the closed positions' cash for entries later in this tick
+        self.recompute_portfolio().await
+    }
+
+    /// Close every open position after the portfolio take-profit target is reached.
+    async fn liquidate_all(&mut self) -> Result<()> {
+        let positions = self.db.get_open_positions().await?;
+        info!(positions = positions.len(), "Portfolio take-profit reached, closing all positions");
+
+        let signal = ExitSignal {
+            should_exit: true,
+            reason: ExitReason::PortfolioTakeProfit,
+            urgency: ExitUrgency::Normal,
+        };
+        for pos in positions.iter().map(Self::convert_position) {
+            self.exit_position(&pos, &signal).await?;
+        }
+
+        // Move the closed positions' P&L out of unrealized
+        self.recompute_portfolio().await
+    }
+
+    /// Close one open position now, at the current price, e.g. from the CLI.
+    ///
+    /// Returns the realized P&L net of estimated costs.
+    pub async fn close_manually(&mut self, market_id: &str, outcome: &str) -> Result<Decimal> {
+        if !self.config.dry_run && self.clob_client.is_none() {
+            anyhow::bail!("CLOB client not configured; can't place the exit order");
+        }
+
+        // Mark to the current price first so the exit and its P&L use it
+        let pos = self.position_at_market(market_id, outcome).await?;
+        self.db.update_position_price(&pos.market_id, &pos.outcome, pos.current_price.to_f64().unwrap_or(0.0)).await?;
+
+        let signal = ExitSignal {
+            should_exit: true,
+            reason: ExitReason::ManualClose,
+            urgency: ExitUrgency::Immediate,
+        };
+        self.exit_position(&pos, &signal)
+            .await?
+            .context("Exit order failed; the position is still open")
+    }
+
+    /// Realized P&L, net of estimated costs, that closing the position now would book.
+    /// Nothing is written.
+    pub async fn preview_close(&self, market_id: &str, outcome: &str) -> Result<Decimal> {
+        let pos = self.position_at_market(market_id, outcome).await?;
+        let costs = self.estimate_order_cost(pos.size * pos.entry_price)
+            + self.estimate_order_cost(pos.size * pos.current_price);
+        Ok(pos.unrealized_pnl - costs)
+    }
+
+    /// The open position in a market outcome, marked at the current price.
+    async fn position_at_market(&self, market_id: &str, outcome: &str) -> Result<StrategyPosition> {
+        let stored = self
+            .db
+            .get_open_positions()
+            .await?
+            .into_iter()
+            .find(|p| p.market_id == market_id && p.outcome.eq_ignore_ascii_case(outcome))
+            .with_context(|| format!("No open position in {} {}", market_id, outcome))?;
+        let mut pos = Self::convert_position(&stored);
+
+        // Without a CLOB client to quote it, the last stored mark is the current price
+        if self.clob_client.is_some() {
+            let price = self.get_current_price(&stored.market_id, &stored.outcome).await?;
+            pos.current_price = price;
+            pos.unrealized_pnl = signed(&pos.side, (price - pos.entry_price) * pos.size);
+        }
+        Ok(pos)
+    }
+
+    /// Exit one position, booking its realized P&L and closing it in the database.
+    ///
+    /// Returns the realized P&L net of costs, or None if the exit order failed.
+    async fn exit_position(&mut self, pos: &StrategyPosition, signal: &ExitSignal) -> Result<Option<Decimal>> {
+        info!(
+            market = %pos.market_id,
+            reason = ?signal.reason,
+            urgency = ?signal.urgency,
+            "Exit signal triggered"
+        );
+
+        if self.config.dry_run {
+            info!(
                 market = %pos.market_id,
-                reason = ?signal.reason,
-                urgency = ?signal.urgency,
-                "Exit signal triggered"
+                size = %pos.size,
+                pnl = %pos.unrealized_pnl,
+                "[DRY RUN] Would exit position"
             );
-
-            if self.config.dry_run {
-                info!(
-                    market = %pos.market_id,
-                    size = %pos.size,
-                    pnl = %pos.unrealized_pnl,
-                    "[DRY RUN] Would exit position"
-                );
-            } else {
-                // Execute exit trade
-                let side = if pos.side == "BUY" { TradeSide::Sell } else { TradeSide::Buy };
-                if let Err(e) = self.execute_trade(&pos.market_id, &pos.outcome, &side, pos.size).await {
-                    error!(error = %e, "Failed to exit position");
-                    continue;
-                }
+        } else {
+            // Execute exit trade
+            let side = if pos.side == "BUY" { TradeSide::Sell } else { TradeSide::Buy };
+            if let Err(e) = self.execute_trade(&pos.market_id, &pos.outcome, &side, pos.size, pos.current_price, None, None).await {
+                error!(error = %e, "Failed to exit position");
+                return Ok(None);
             }
+        }
 
-            // Update realized P&L
-            let realized = pos.unrealized_pnl;
-            *self.realized_pnl.write().await += realized;
+        // Update realized P&L, net of the exit order's cost (entry cost was booked on fill)
+        let exit_cost = self.charge_order_cost(pos.size * pos.current_price).await;
+        let entry_cost = self.estimate_order_cost(pos.size * pos.entry_price);
+        *self.realized_pnl.write().await += pos.unrealized_pnl;
 
-            if realized < Decimal::ZERO {
-                *self.last_loss_at.write().await = Some(Utc::now());
+        let realized = pos.unrealized_pnl - entry_cost - exit_cost;
+        if realized < Decimal::ZERO {
+            *self.last_loss_at.write().await = Some(self.clock.now());
+        }
+
+        // Close position in DB
+        let costs = (entry_cost + exit_cost).to_f64().unwrap_or(0.0);
+        self.db.close_position(&pos.market_id, &pos.outcome, costs, signal.reason.as_str()).await?;
+
+        Ok(Some(realized))
+    }
+
+    /// Settle open positions in resolved markets at 1.0 for the winning outcome and 0.0
+    /// otherwise. Redemption places no order, so only the entry cost is charged.
+    ///
+    /// With `cached_only`, only resolutions already in the database are used. Otherwise a
+    /// market not yet known to have resolved is looked up at most once per
+    /// [`RESOLUTION_CHECK_INTERVAL`].
+    async fn settle_resolved_positions(&mut self, cached_only: bool) -> Result<usize> {
+        let mut settled = 0;
+        for stored in self.db.get_open_positions().await? {
+            let resolution = if cached_only || !self.resolution_check_due(&stored.market_id) {
+                self.db.get_resolved_market(&stored.market_id).await?
+            } else {
+                self.market_resolution(&stored.market_id).await?
+            };
+            let Some(resolution) = resolution else {
+                continue;
+            };
+
+            let won = resolution.winning_outcome.eq_ignore_ascii_case(&stored.outcome);
+            let payout = if won { Decimal::ONE } else { Decimal::ZERO };
+            let pos = Self::convert_position(&stored);
+            let pnl = signed(&pos.side, (payout - pos.entry_price) * pos.size);
+            let entry_cost = self.estimate_order_cost(pos.size * pos.entry_price);
+            *self.realized_pnl.write().await += pnl;
+            if pnl - entry_cost < Decimal::ZERO {
+                *self.last_loss_at.write().await = Some(self.clock.now());
             }
 
-            // Close position in DB
-            self.db.close_position(&pos.market_id, &pos.outcome).await?;
+            self.db.update_position_price(&stored.market_id, &stored.outcome, payout.to_f64().unwrap_or(0.0)).await?;
+            self.db.close_position(
+                &stored.market_id,
+                &stored.outcome,
+                entry_cost.to_f64().unwrap_or(0.0),
+                SETTLEMENT_EXIT_REASON,
+            ).await?;
+            info!(
+                market = %resolution.condition_id,
+                outcome = %stored.outcome,
+                winner = %resolution.winning_outcome,
+                resolved_at = %resolution.resolved_at,
+                pnl = %pnl,
+                "Settled position in resolved market"
+            );
+            settled += 1;
         }
+        Ok(settled)
+    }
 
-        Ok(())
+    /// Whether a market's resolution may be looked up now, recording the lookup if so.
+    fn resolution_check_due(&mut self, market_id: &str) -> bool {
+        if matches!(self.resolution_checks.get(market_id), Some(at) if at.elapsed() < RESOLUTION_CHECK_INTERVAL) {
+            return false;
+        }
+        self.resolution_checks.insert(market_id.to_string(), Instant::now());
+        true
     }
 
     /// Build current portfolio state.
@@ -591,28 +1558,32 @@ impl Bot {
         let realized = *self.realized_pnl.read().await;
         let peak = *self.peak_equity.read().await;
 
-        let current_equity = total_value + realized + unrealized;
-        let drawdown = if peak > Decimal::ZERO {
-            (peak - current_equity).max(Decimal::ZERO) / peak
-        } else {
-            Decimal::ZERO
-        };
-
         let position_count = self.db.get_open_positions().await
             .map(|p| p.len())
             .unwrap_or(0);
 
-        PortfolioState {
+        let mut state = PortfolioState {
             total_value,
             cash_available: cash,
             total_exposure: exposure,
             unrealized_pnl: unrealized,
             realized_pnl: realized,
-            current_drawdown: drawdown,
+            current_drawdown: Decimal::ZERO,
             position_count,
             last_trade_at: *self.last_trade_at.read().await,
             last_loss_at: *self.last_loss_at.read().await,
+        };
+        if peak > Decimal::ZERO {
+            state.current_drawdown = (peak - state.equity()).max(Decimal::ZERO) / peak;
         }
+        state
+    }
+
+    /// Allocation cap for new positions, ramped from the first live session. Dry runs
+    /// before it don't count, and a bot that has never traded live sits at the ramp start.
+    fn allocation_cap(&self, now: chrono::DateTime<Utc>) -> Decimal {
+        let elapsed = self.live_started_at.map_or(chrono::Duration::zero(), |at| now - at);
+        self.config.trading_config.allocation_cap(elapsed)
     }
 
     /// Process pending trades from database.
@@ -630,10 +1601,8 @@ impl Bot {
 
     /// Record equity curve point.
     async fn record_equity(&self) -> Result<()> {
-        let portfolio = self.build_portfolio_state();
-        let portfolio = portfolio.await;
-
-        let equity = portfolio.total_value + portfolio.realized_pnl + portfolio.unrealized_pnl;
+        let portfolio = self.build_portfolio_state().await;
+        let equity = portfolio.equity();
 
         // Update peak
         let mut peak = self.peak_equity.write().await;
@@ -659,19 +1628,82 @@ impl Bot {
 
         let (total, executed, _failed) = self.db.get_copy_trade_stats().await?;
 
+        let peak = self.peak_equity.read().await.to_f64().unwrap_or(0.0);
+
         self.db.update_bot_state(
             exposure,
             realized + unrealized,
             executed,
+            peak,
         ).await?;
 
         Ok(())
     }
 
+    /// Push run metrics to StatsD, if configured. Trade counters report the change
+    /// since the last push, starting from the counts at the first one.
+    async fn report_metrics(&mut self, tick_latency: Duration) {
+        let Some(statsd) = &self.statsd else {
+            return;
+        };
+
+        let value = self.portfolio_value.read().await.to_f64().unwrap_or(0.0);
+        let exposure = self.total_exposure.read().await.to_f64().unwrap_or(0.0);
+        let pnl = (*self.realized_pnl.read().await + *self.unrealized_pnl.read().await).to_f64().unwrap_or(0.0);
+        let positions = self.db.get_open_positions().await.map(|p| p.len()).unwrap_or(0);
+        let (_total, executed, failed) = self.db.get_copy_trade_stats().await.unwrap_or((0, 0, 0));
+        let (prev_executed, prev_failed) = self.reported_trade_counts.unwrap_or((executed, failed));
+
+        statsd.send(&[
+            (telemetry::PORTFOLIO_VALUE, value),
+            (telemetry::EXPOSURE, exposure),
+            (telemetry::TOTAL_PNL, pnl),
+            (telemetry::OPEN_POSITIONS, positions as f64),
+            (telemetry::TRADES_EXECUTED, (executed - prev_executed).max(0) as f64),
+            (telemetry::TRADES_FAILED, (failed - prev_failed).max(0) as f64),
+            (telemetry::TICK_LATENCY, tick_latency.as_secs_f64() * 1000.0),
+        ]).await;
+        self.reported_trade_counts = Some((executed, failed));
+    }
+
     /// Graceful shutdown.
-    async fn shutdown(&self) -> Result<()> {
+    async fn shutdown(&mut self) -> Result<()> {
         info!("Shutting down bot...");
 
+        // Snapshot resting orders first; once cancelled they drop off the open list
+        let resting = self.resting_orders().await;
+
+        // Don't leave orphan orders behind unless asked to
+        if self.config.cancel_on_exit && !self.config.dry_run {
+            if let Some(clob) = &self.clob_client {
+                match clob.cancel_all_orders().await {
+                    Ok(true) => info!("Cancelled all resting orders"),
+                    Ok(false) => warn!("Cancel-all request was rejected"),
+                    Err(e) => error!(error = %e, "Failed to cancel resting orders"),
+                }
+            }
+        }
+
+        // Every order the bot books settles within its own tick, so anything that
+        // matched while resting is outside the books and needs an operator's eye
+        for order in self.final_fills(resting).await {
+            warn!(
+                order_id = %order.id,
+                token_id = %order.token_id,
+                side = %order.side,
+                matched = %order.matched_size(),
+                avg_price = ?order.average_fill_price(),
+                status = %order.status,
+                "Resting order matched before shutdown and is not on the books"
+            );
+        }
+
+        // Re-mark positions at final prices and record a closing equity point
+        if let Err(e) = self.update_positions().await {
+            warn!(error = %e, "Failed to re-mark positions on shutdown");
+        }
+        self.record_equity().await?;
+
         // Mark bot as stopped
         self.db.mark_bot_stopped().await?;
 
@@ -682,6 +1714,39 @@ impl Bot {
         Ok(())
     }
 
+    /// Orders resting on the book for this wallet (empty in dry run).
+    async fn resting_orders(&self) -> Vec<OrderStatus> {
+        let Some(clob) = self.clob_client.as_ref().filter(|_| !self.config.dry_run) else {
+            return Vec::new();
+        };
+        clob.get_open_orders().await.unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to fetch resting orders on shutdown");
+            Vec::new()
+        })
+    }
+
+    /// Final state of each `resting` order that matched any size, re-fetched so
+    /// fills landing up to the cancel are counted.
+    async fn final_fills(&self, resting: Vec<OrderStatus>) -> Vec<OrderStatus> {
+        let Some(clob) = &self.clob_client else {
+            return Vec::new();
+        };
+        let mut filled = Vec::new();
+        for order in resting {
+            let order = match clob.get_order(&order.id).await {
+                Ok(order) => order,
+                Err(e) => {
+                    warn!(order_id = %order.id, error = %e, "Failed to fetch final order state");
+                    order
+                }
+            };
+            if order.matched_size() > Decimal::ZERO {
+                filled.push(order);
+            }
+        }
+        filled
+    }
+
     /// Add a trader to track.
     pub async fn add_trader(&mut self, address: &str) -> Result<()> {
         self.copy_engine.add_trader(address.to_string()).await?;
@@ -708,8 +1773,10 @@ impl Bot {
             total_exposure: *self.total_exposure.read().await,
             unrealized_pnl: *self.unrealized_pnl.read().await,
             realized_pnl: *self.realized_pnl.read().await,
+            total_costs: *self.total_costs.read().await,
             max_drawdown: Decimal::try_from(max_dd).unwrap_or(Decimal::ZERO),
             tracked_traders: engine_stats.tracked_traders,
+            zero_size_reasons: engine_stats.zero_size_reasons,
             total_trades,
             executed_trades: executed,
             failed_trades: failed,
@@ -719,6 +1786,46 @@ impl Bot {
     }
 }
 
+/// Outcome of processing a copy trade intent.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TradeDecision {
+    /// Order placed on the CLOB
+    Executed { order_id: Option<String> },
+    /// Dry-run fill at the given price and size
+    Simulated { price: Decimal, size: Decimal },
+    /// Rejected before execution
+    Rejected { reason: String },
+    /// Order placement failed
+    Failed { error: String },
+    /// Already processed earlier
+    Deduped,
+}
+
+impl TradeDecision {
+    /// Short label for logging and counters.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Executed { .. } => "executed",
+            Self::Simulated { .. } => "simulated",
+            Self::Rejected { .. } => "rejected",
+            Self::Failed { .. } => "failed",
+            Self::Deduped => "deduped",
+        }
+    }
+}
+
+impl std::fmt::Display for TradeDecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Executed { order_id } => write!(f, "executed (order {})", order_id.as_deref().unwrap_or("unknown")),
+            Self::Simulated { price, size } => write!(f, "simulated ${} @ {}", size, price),
+            Self::Rejected { reason } => write!(f, "rejected: {}", reason),
+            Self::Failed { error } => write!(f, "failed: {}", error),
+            Self::Deduped => write!(f, "deduped"),
+        }
+    }
+}
+
 /// Bot statistics.
 #[derive(Debug, Clone)]
 pub struct BotStats {
@@ -727,8 +1834,11 @@ pub struct BotStats {
     pub total_exposure: Decimal,
     pub unrealized_pnl: Decimal,
     pub realized_pnl: Decimal,
+    pub total_costs: Decimal,
     pub max_drawdown: Decimal,
     pub tracked_traders: usize,
+    /// Source trades not copied because sizing produced nothing, by reason
+    pub zero_size_reasons: HashMap<ZeroSizeReason, usize>,
     pub total_trades: i64,
     pub executed_trades: i64,
     pub failed_trades: i64,
@@ -739,18 +1849,1722 @@ pub struct BotStats {
 impl std::fmt::Display for BotStats {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "=== Bot Statistics ===")?;
-        writeln!(f, "Portfolio Value: ${:.2}", self.portfolio_value)?;
-        writeln!(f, "Cash Available:  ${:.2}", self.cash_available)?;
-        writeln!(f, "Total Exposure:  ${:.2}", self.total_exposure)?;
-        writeln!(f, "Unrealized P&L:  ${:.2}", self.unrealized_pnl)?;
-        writeln!(f, "Realized P&L:    ${:.2}", self.realized_pnl)?;
-        writeln!(f, "Max Drawdown:    {:.2}%", self.max_drawdown * dec!(100))?;
+        writeln!(f, "Portfolio Value: ${}", display::money(self.portfolio_value))?;
+        writeln!(f, "Cash Available:  ${}", display::money(self.cash_available))?;
+        writeln!(f, "Total Exposure:  ${}", display::money(self.total_exposure))?;
+        writeln!(f, "Unrealized P&L:  ${}", display::money(self.unrealized_pnl))?;
+        writeln!(f, "Realized P&L:    ${}", display::money(self.realized_pnl))?;
+        writeln!(f, "Trading Costs:   ${}", display::money(self.total_costs))?;
+        writeln!(f, "Max Drawdown:    {}%", display::pct(self.max_drawdown))?;
         writeln!(f, "Tracked Traders: {}", self.tracked_traders)?;
         writeln!(f, "Total Trades:    {} (Executed: {}, Failed: {})",
             self.total_trades, self.executed_trades, self.failed_trades)?;
+        if !self.zero_size_reasons.is_empty() {
+            let mut reasons: Vec<_> = self.zero_size_reasons.iter().collect();
+            reasons.sort_by_key(|(reason, count)| (std::cmp::Reverse(**count), reason.to_string()));
+            let reasons: Vec<_> = reasons.iter().map(|(reason, count)| format!("{}: {}", reason, count)).collect();
+            writeln!(f, "Sized to Zero:   {} ({})",
+                self.zero_size_reasons.values().sum::<usize>(), reasons.join(", "))?;
+        }
         writeln!(f, "Status:          {} {}",
             if self.is_running { "Running" } else { "Stopped" },
             if self.dry_run { "(Dry Run)" } else { "" })?;
         Ok(())
     }
 }
+
+/// Reconcile stored positions with the wallet's `held` positions; returns how many rows changed.
+///
+/// The wallet reports long balances, so holdings are matched against BUY rows. Under
+/// `Merge`, shares the database is missing are added at the price implied by the
+/// wallet's average, so the weighted-average upsert lands on that average. Under
+/// `TrustApi`, rows the wallet doesn't back, shorts included, are closed as reconciled
+/// without booking P&L, since nothing says what price they left at.
+async fn reconcile_positions(db: &Database, policy: RestartPolicy, held: &[Position]) -> Result<usize> {
+    if policy == RestartPolicy::TrustDb {
+        return Ok(0);
+    }
+
+    let stored = db.get_open_positions().await?;
+    let find = |market_id: &str, outcome: &str| {
+        stored
+            .iter()
+            .find(|p| p.side == "BUY" && p.market_id == market_id && p.outcome == outcome)
+    };
+    let mut changed = 0;
+
+    for pos in held.iter().filter(|p| p.size > Decimal::ZERO) {
+        let size = pos.size.to_f64().unwrap_or(0.0);
+        let avg = pos.average_price.to_f64().unwrap_or(0.0);
+
+        match (find(&pos.market_id, &pos.outcome), policy) {
+            (None, _) => {
+                db.save_position(&pos.market_id, &pos.market_title, &pos.outcome, "BUY", size, avg, None).await?;
+            }
+            (Some(s), RestartPolicy::TrustApi) if s.size != size || s.entry_price != avg => {
+                db.set_position(&pos.market_id, &pos.outcome, "BUY", size, avg).await?;
+            }
+            (Some(s), RestartPolicy::Merge) if size > s.size => {
+                let missing = size - s.size;
+                let implied = (avg * size - s.entry_price * s.size) / missing;
+                let price = if implied > 0.0 && implied <= 1.0 { implied } else { avg };
+                db.save_position(&pos.market_id, &pos.market_title, &pos.outcome, "BUY", missing, price, None).await?;
+            }
+            _ => continue,
+        }
+        changed += 1;
+    }
+
+    if policy == RestartPolicy::TrustApi {
+        for s in &stored {
+            let backed = s.side == "BUY"
+                && held.iter().any(|p| p.market_id == s.market_id && p.outcome == s.outcome && p.size > Decimal::ZERO);
+            if !backed {
+                db.mark_position_reconciled(&s.market_id, &s.outcome, &s.side).await?;
+                changed += 1;
+            }
+        }
+    }
+
+    Ok(changed)
+}
+
+/// `amount` as seen by a position on `side`: negated for a short, which gains as prices fall.
+fn signed(side: &str, amount: Decimal) -> Decimal {
+    if side.eq_ignore_ascii_case("sell") {
+        -amount
+    } else {
+        amount
+    }
+}
+
+/// Minimum time between wallet balance syncs.
+const PORTFOLIO_SYNC_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Minimum time between resolution lookups for a market with open positions.
+const RESOLUTION_CHECK_INTERVAL: Duration = Duration::from_secs(300);
+
+/// How often a resting entry order is checked during its patience window.
+const PATIENCE_POLL: Duration = Duration::from_millis(500);
+
+/// Result of checking an order's realized fill against the validated price.
+#[derive(Debug, Clone, PartialEq)]
+enum FillReview {
+    /// Filled within the realized-slippage limit
+    Ok { fill_price: Decimal },
+    /// Filled beyond the limit; `closed` if the position was immediately unwound
+    BadFill { fill_price: Decimal, slippage: Decimal, closed: bool },
+    /// No fill information available
+    Unknown,
+}
+
+/// Compare an order's average fill to the price we validated against, alerting
+/// (and optionally unwinding the filled size with an opposite market order) if it
+/// slipped too far. The order has already filled, so failures are logged, not returned.
+async fn review_fill<E: OrderExecutor>(
+    exec: &E,
+    strategy: &Strategy,
+    order_id: &str,
+    token_id: &str,
+    side: &TradeSide,
+    size: Decimal,
+    validated_price: Decimal,
+) -> FillReview {
+    let (fill_price, filled) = match exec.get_order(order_id).await {
+        Ok(status) => (status.average_fill_price(), status.matched_size()),
+        Err(e) => {
+            debug!(order_id = %order_id, error = %e, "Failed to fetch fills for slippage check");
+            (None, Decimal::ZERO)
+        }
+    };
+    let Some(fill_price) = fill_price else {
+        return FillReview::Unknown;
+    };
+
+    let is_buy = matches!(side, TradeSide::Buy);
+    if !strategy.is_bad_fill(is_buy, validated_price, fill_price) {
+        return FillReview::Ok { fill_price };
+    }
+
+    let slippage = strategy.realized_slippage(is_buy, validated_price, fill_price);
+    error!(
+        order_id = %order_id,
+        validated = %validated_price,
+        filled = %fill_price,
+        slippage = %slippage,
+        "Fill slipped beyond realized-slippage limit"
+    );
+
+    let closed = if strategy.config().close_on_bad_fill {
+        let exit_side = if is_buy { OrderSide::Sell } else { OrderSide::Buy };
+        let unwind = if filled > Decimal::ZERO { filled } else { size };
+        match exec.market_order(token_id, exit_side, unwind).await {
+            Ok(_) => {
+                warn!(order_id = %order_id, size = %unwind, "Closed position after bad fill");
+                true
+            }
+            Err(e) => {
+                error!(order_id = %order_id, error = %e, "Failed to close position after bad fill");
+                false
+            }
+        }
+    } else {
+        false
+    };
+
+    FillReview::BadFill { fill_price, slippage, closed }
+}
+
+/// Rest a limit order at `price` for up to `patience`, then cancel it and
+/// market-order whatever remains unfilled.
+async fn place_with_patience<E: OrderExecutor>(
+    exec: &E,
+    token_id: &str,
+    side: OrderSide,
+    size: Decimal,
+    price: Decimal,
+    patience: Duration,
+    poll: Duration,
+) -> Result<OrderResponse> {
+    let resting = exec.limit_order(token_id, side, size, price).await?;
+    let Some(order_id) = resting.order_id.clone() else {
+        return exec.market_order(token_id, side, size).await;
+    };
+
+    let deadline = tokio::time::Instant::now() + patience;
+    let mut matched = Decimal::ZERO;
+    loop {
+        match exec.get_order(&order_id).await {
+            Ok(status) if status.is_filled() => {
+                debug!(order_id = %order_id, "Entry filled within patience window");
+                return Ok(resting);
+            }
+            Ok(status) => matched = status.matched_size(),
+            Err(e) => debug!(order_id = %order_id, error = %e, "Failed to check resting order"),
+        }
+
+        if tokio::time::Instant::now() >= deadline {
+            break;
+        }
+        sleep(poll).await;
+    }
+
+    exec.cancel_order(&order_id).await?;
+    // Fills can land between the last check and the cancel, so only cross what's left after it
+    match exec.get_order(&order_id).await {
+        Ok(status) if status.is_filled() => {
+            debug!(order_id = %order_id, "Entry filled before cancel took effect");
+            return Ok(resting);
+        }
+        Ok(status) => matched = status.matched_size(),
+        Err(e) => warn!(order_id = %order_id, error = %e, "Failed to recheck canceled order, using last matched size"),
+    }
+    let remaining = size - matched;
+    if remaining <= Decimal::ZERO {
+        return Ok(resting);
+    }
+
+    info!(order_id = %order_id, remaining = %remaining, "Entry unfilled after patience window, crossing spread");
+    exec.market_order(token_id, side, remaining).await
+}
+
+/// Apply random jitter to a poll interval.
+///
+/// `sample` is a uniform value in [0, 1) mapped onto the band
+/// `[base * (1 - jitter_pct), base * (1 + jitter_pct)]`.
+fn jittered_interval(base: Duration, jitter_pct: f64, sample: f64) -> Duration {
+    if jitter_pct <= 0.0 {
+        return base;
+    }
+    let jitter = jitter_pct.min(1.0);
+    let factor = 1.0 - jitter + 2.0 * jitter * sample.clamp(0.0, 1.0);
+    Duration::from_secs_f64(base.as_secs_f64() * factor)
+}
+
+/// Uniform random value in [0, 1), drawn from the random (non-version) bits of a v4 UUID.
+fn random_unit() -> f64 {
+    (uuid::Uuid::new_v4().as_u128() >> 80) as f64 / (1u64 << 48) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    use crate::api::{AssociateTrade, OrderStatus};
+    use crate::trading::{SourcePnlFilter, DEFAULT_LOAD_CONCURRENCY};
+
+    /// Order executor that fills resting orders after a set number of status checks.
+    struct MockExecutor {
+        fill_after_checks: usize,
+        fill_price: Option<&'static str>,
+        /// Fill the resting order as it's canceled
+        fill_on_cancel: bool,
+        checks: AtomicUsize,
+        market_orders: AtomicUsize,
+        market_sizes: std::sync::Mutex<Vec<Decimal>>,
+        cancels: AtomicUsize,
+    }
+
+    impl MockExecutor {
+        fn new(fill_after_checks: usize) -> Self {
+            Self {
+                fill_after_checks,
+                fill_price: None,
+                fill_on_cancel: false,
+                checks: AtomicUsize::new(0),
+                market_orders: AtomicUsize::new(0),
+                market_sizes: std::sync::Mutex::new(Vec::new()),
+                cancels: AtomicUsize::new(0),
+            }
+        }
+
+        fn response(order_id: &str) -> OrderResponse {
+            OrderResponse {
+                order_id: Some(order_id.to_string()),
+                success: true,
+                error_msg: String::new(),
+                status: None,
+                transaction_hash: None,
+            }
+        }
+    }
+
+    impl OrderExecutor for MockExecutor {
+        async fn market_order(&self, _token_id: &str, _side: OrderSide, size: Decimal) -> Result<OrderResponse> {
+            self.market_orders.fetch_add(1, Ordering::SeqCst);
+            self.market_sizes.lock().unwrap().push(size);
+            Ok(Self::response("market"))
+        }
+
+        async fn limit_order(
+            &self,
+            _token_id: &str,
+            _side: OrderSide,
+            _size: Decimal,
+            _price: Decimal,
+        ) -> Result<OrderResponse> {
+            Ok(Self::response("limit"))
+        }
+
+        async fn get_order(&self, order_id: &str) -> Result<OrderStatus> {
+            let checks = self.checks.fetch_add(1, Ordering::SeqCst) + 1;
+            let filled = checks >= self.fill_after_checks
+                || (self.fill_on_cancel && self.cancels.load(Ordering::SeqCst) > 0);
+            Ok(OrderStatus {
+                id: order_id.to_string(),
+                status: if filled { "MATCHED" } else { "LIVE" }.to_string(),
+                side: "BUY".to_string(),
+                token_id: "token".to_string(),
+                original_size: "10".to_string(),
+                size_matched: if filled { "10" } else { "0" }.to_string(),
+                associate_trades: self.fill_price.filter(|_| filled).map(|price| {
+                    vec![AssociateTrade { price: price.to_string(), size: "10".to_string() }]
+                }),
+            })
+        }
+
+        async fn cancel_order(&self, _order_id: &str) -> Result<bool> {
+            self.cancels.fetch_add(1, Ordering::SeqCst);
+            Ok(true)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stored_position_round_trips_opened_at() {
+        let db = Database::new(&crate::testing::temp_database_url()).await.unwrap();
+        db.save_position("0xm1", "", "Yes", "BUY", 10.0, 0.50, None).await.unwrap();
+        sqlx::query("UPDATE positions SET opened_at = datetime('now', '-30 hours')")
+            .execute(db.pool())
+            .await
+            .unwrap();
+
+        let stored = db.get_open_positions().await.unwrap();
+        let position = Bot::convert_position(&stored[0]);
+        assert_eq!(position.holding_duration(Utc::now()).num_hours(), 30);
+
+        // Timestamps written as RFC 3339 still parse
+        assert!(db::parse_timestamp("2024-05-01T12:00:00Z").is_some());
+        assert_eq!(
+            db::parse_timestamp("2024-05-01T12:00:00Z"),
+            db::parse_timestamp("2024-05-01 12:00:00"),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_peak_price_tracks_best_mark_since_entry() {
+        let db = Database::new(&crate::testing::temp_database_url()).await.unwrap();
+        db.save_position("0xm1", "", "Yes", "BUY", 10.0, 0.50, None).await.unwrap();
+        let peak = |stored: &[StoredPosition]| Bot::convert_position(&stored[0]).peak_price;
+        assert_eq!(peak(&db.get_open_positions().await.unwrap()), dec!(0.50));
+
+        for price in [0.58, 0.65, 0.56] {
+            db.update_position_price("0xm1", "Yes", price).await.unwrap();
+        }
+        assert_eq!(peak(&db.get_open_positions().await.unwrap()), dec!(0.65));
+    }
+
+    #[tokio::test]
+    async fn test_adds_to_one_position_tighten_the_slippage_budget() {
+        let db = Database::new(&crate::testing::temp_database_url()).await.unwrap();
+        let strategy = Strategy::new(StrategyConfig {
+            add_slippage_decay: dec!(0.25),
+            ..StrategyConfig::default()
+        });
+        let portfolio = PortfolioState {
+            total_value: dec!(10000),
+            cash_available: dec!(10000),
+            total_exposure: Decimal::ZERO,
+            unrealized_pnl: Decimal::ZERO,
+            realized_pnl: Decimal::ZERO,
+            current_drawdown: Decimal::ZERO,
+            position_count: 0,
+            last_trade_at: None,
+            last_loss_at: None,
+        };
+        // 2% from the source's price: within 3% and 2.25%, not the 1.69% left after two fills
+        let validate = |held: &[StrategyPosition]| {
+            strategy.validate_entry(&EntryQuote::new(Utc::now(), dec!(0.51), dec!(0.50)), dec!(10), None, &portfolio, held, None)
+        };
+        let held = |stored: Vec<StoredPosition>| stored.iter().map(Bot::convert_position).collect::<Vec<_>>();
+
+        db.save_position("0xm1", "", "Yes", "BUY", 10.0, 0.50, None).await.unwrap();
+        assert!(validate(&held(db.get_open_positions().await.unwrap())).allowed);
+
+        // The add lands on the same row but still counts
+        db.save_position("0xm1", "", "Yes", "BUY", 10.0, 0.50, None).await.unwrap();
+        let stored = db.get_open_positions().await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].entries, 2);
+        let third = validate(&held(stored));
+        assert!(!third.allowed);
+        assert!(third.reason.contains("Slippage too high"));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_tick_copies_new_trade_end_to_end() {
+        use crate::testing::{self, MockPolymarket};
+
+        let trader = "0x1111111111111111111111111111111111111111";
+        let market = "0xmarket";
+        let now = Utc::now().timestamp();
+
+        let mock = MockPolymarket::start().await;
+        mock.mount_leaderboard(vec![testing::leaderboard_entry(trader, 50_000.0)]).await;
+        mock.mount_positions(trader, vec![testing::position(market, 2000.0, 0.5)]).await;
+        let old_trade = testing::trade(trader, market, "0xold", 100.0, 0.5, now - 3600);
+        // Served to discovery scoring, then to add_trader
+        mock.mount_trades(trader, vec![old_trade.clone()], Some(2)).await;
+        mock.mount_trades(
+            trader,
+            vec![testing::trade(trader, market, "0xnew", 100.0, 0.5, now), old_trade],
+            None,
+        ).await;
+        mock.mount_market(market, false).await;
+        mock.mount_order_book("1", "0.49", "0.51").await;
+
+        let config = BotConfig {
+            database_url: testing::temp_database_url(),
+            trading_config: TradingConfig {
+                sizing_method: "equal".to_string(),
+                ..TradingConfig::default()
+            },
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, mock.data_client(), Some(mock.clob_client()))
+            .await
+            .unwrap();
+        bot.initialize().await.unwrap();
+
+        let discovered = bot.copy_engine.discover_traders(10_000.0, 1, DEFAULT_LOAD_CONCURRENCY).await.unwrap();
+        assert_eq!(discovered.len(), 1);
+        bot.add_trader(&discovered[0].address).await.unwrap();
+
+        bot.tick().await.unwrap();
+
+        let copies: Vec<StoredCopyTrade> = sqlx::query_as("SELECT * FROM copy_trades")
+            .fetch_all(bot.db.pool())
+            .await
+            .unwrap();
+        assert_eq!(copies.len(), 1);
+        assert_eq!(copies[0].status, CopyTradeStatus::Simulated.as_str());
+        assert_eq!(copies[0].market_id, market);
+        // The USDC size buys a whole number of share lots at the 0.50 mid
+        let shares = Decimal::try_from(copies[0].our_size).unwrap() / dec!(0.50);
+        assert_eq!(shares % SHARE_LOT_SIZE, Decimal::ZERO);
+
+        let positions = bot.db.get_open_positions().await.unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].source_trader.as_deref(), Some(trader));
+
+        let equity = bot.db.get_equity_curve(1).await.unwrap();
+        assert_eq!(equity.len(), 1);
+        assert!(equity[0].exposure > 0.0);
+        assert!(*bot.cash_available.read().await < dec!(1000));
+    }
+
+    #[tokio::test]
+    async fn test_fills_sharing_a_transaction_are_each_processed() {
+        use crate::testing::{self, MockPolymarket};
+
+        let trader = "0x4444444444444444444444444444444444444444";
+        let market = "0xmarket";
+        let now = Utc::now().timestamp();
+
+        let mock = MockPolymarket::start().await;
+        mock.mount_positions(trader, vec![]).await;
+        mock.mount_trades(trader, vec![], Some(1)).await;
+        let yes = testing::trade(trader, market, "0xshared", 100.0, 0.5, now);
+        let mut no = testing::trade(trader, market, "0xshared", 40.0, 0.3, now);
+        no["outcome"] = serde_json::json!("No");
+        no["outcomeIndex"] = serde_json::json!(1);
+        mock.mount_trades(trader, vec![yes, no], None).await;
+        mock.mount_market(market, false).await;
+        mock.mount_order_book("1", "0.49", "0.51").await;
+        mock.mount_order_book("2", "0.29", "0.31").await;
+
+        let config = BotConfig {
+            database_url: testing::temp_database_url(),
+            trading_config: TradingConfig {
+                sizing_method: "equal".to_string(),
+                ..TradingConfig::default()
+            },
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, mock.data_client(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+        bot.add_trader(trader).await.unwrap();
+
+        let intents = bot.copy_engine.poll_for_trades().await.unwrap();
+        assert_eq!(intents.len(), 2);
+        assert_ne!(intents[0].source_trade.id, intents[1].source_trade.id);
+
+        for intent in intents {
+            let decision = bot.process_trade_intent(intent).await.unwrap();
+            assert!(!matches!(decision, TradeDecision::Deduped));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_losses_leave_cash_negative_and_reject_new_entries() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, mock.data_client(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+
+        *bot.realized_pnl.write().await = dec!(-2500);
+        bot.update_positions().await.unwrap();
+        assert_eq!(*bot.cash_available.read().await, dec!(-1500));
+        assert_equity_identity(&bot).await;
+
+        let trader = "0xloser";
+        bot.db.save_trader(trader, "", 1.0).await.unwrap();
+        let decision = bot.process_trade_intent(make_intent(trader, "0xmkt", dec!(50))).await.unwrap();
+        assert!(matches!(decision, TradeDecision::Rejected { .. }));
+        assert!(bot.db.get_open_positions().await.unwrap().is_empty());
+    }
+
+    async fn preflight_bot(mock: &crate::testing::MockPolymarket, dry_run: bool) -> Bot {
+        let config = BotConfig {
+            dry_run,
+            database_url: crate::testing::temp_database_url(),
+            ..BotConfig::default()
+        };
+        Bot::with_clients(config, mock.data_client(), Some(mock.clob_client())).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_failing_doctor_check_blocks_live_but_not_dry_run() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        mock.mount_leaderboard(vec![]).await;
+        // No balance endpoint mounted: the credentials check fails
+
+        let err = preflight_bot(&mock, false).await.preflight().await.unwrap_err();
+        assert!(err.to_string().contains("credentials"));
+
+        preflight_bot(&mock, true).await.preflight().await.unwrap();
+
+        mock.mount_balance(100.0).await;
+        preflight_bot(&mock, false).await.preflight().await.unwrap();
+    }
+
+    async fn assert_equity_identity(bot: &Bot) {
+        let portfolio = bot.build_portfolio_state().await;
+        assert_eq!(portfolio.cash_available + *bot.net_exposure.read().await, portfolio.equity());
+    }
+
+    /// Mark every open position at `price`, as a price source would without a CLOB client.
+    async fn mark_positions(bot: &Bot, price: f64) {
+        for pos in bot.db.get_open_positions().await.unwrap() {
+            bot.db.update_position_price(&pos.market_id, &pos.outcome, price).await.unwrap();
+        }
+        bot.recompute_portfolio().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_equity_is_cash_plus_market_value_across_cycles() {
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            strategy_config: StrategyConfig {
+                min_trade_interval_secs: 0,
+                ..StrategyConfig::default()
+            },
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, DataClient::new().unwrap(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+        assert_equity_identity(&bot).await;
+
+        // Bought at 0.40, now marked at 0.50: $10 unrealized gain
+        bot.db.save_position("0xold", "", "Yes", "BUY", 100.0, 0.40, None).await.unwrap();
+        mark_positions(&bot, 0.50).await;
+        // Nothing can quote it, so the next update keeps the mark rather than inventing one
+        assert!(bot.get_current_price("0xold", "Yes").await.is_err());
+        bot.update_positions().await.unwrap();
+        assert_eq!(*bot.unrealized_pnl.read().await, dec!(10));
+        assert_equity_identity(&bot).await;
+
+        // Close it, realizing the gain
+        *bot.realized_pnl.write().await += dec!(10);
+        bot.db.close_position("0xold", "Yes", 0.0, ExitReason::ManualClose.as_str()).await.unwrap();
+        bot.update_positions().await.unwrap();
+        assert_eq!(*bot.cash_available.read().await, dec!(1010));
+        assert_equity_identity(&bot).await;
+
+        // Open a new one through the normal path
+        bot.db.save_trader("0xabc", "", 1.0).await.unwrap();
+        bot.process_trade_intent(make_intent("0xabc", "0xnew", dec!(40))).await.unwrap();
+        bot.update_positions().await.unwrap();
+        assert_equity_identity(&bot).await;
+        assert_eq!(bot.build_portfolio_state().await.equity(), dec!(1010));
+    }
+
+    #[tokio::test]
+    async fn test_equity_identity_holds_with_an_open_short_and_across_restart() {
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            order_cost_usdc: dec!(1),
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config.clone(), DataClient::new().unwrap(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+
+        // Sold 100 at 0.60 and bought 100 at 0.40, both marked at 0.50: $10 unrealized
+        // gain on each, less $1 entry cost on each
+        bot.update_position_after_trade("0xshort", "Yes", &TradeSide::Sell, dec!(100), dec!(0.60), None)
+            .await
+            .unwrap();
+        bot.update_position_after_trade("0xlong", "Yes", &TradeSide::Buy, dec!(100), dec!(0.40), None)
+            .await
+            .unwrap();
+        mark_positions(&bot, 0.50).await;
+        assert_eq!(*bot.unrealized_pnl.read().await, dec!(20));
+        assert_eq!(*bot.net_exposure.read().await, Decimal::ZERO);
+        assert_eq!(*bot.cash_available.read().await, dec!(1018));
+        assert_equity_identity(&bot).await;
+
+        // Closing the short books its gain, net of costs, the same way after a restart
+        let realized = bot.close_manually("0xshort", "Yes").await.unwrap();
+        assert_eq!(realized, dec!(8));
+        bot.recompute_portfolio().await.unwrap();
+        let closed = bot.db.get_closed_positions().await.unwrap();
+        assert!((closed[0].realized_pnl.unwrap() - 8.0).abs() < 1e-9);
+        assert_equity_identity(&bot).await;
+        let equity = bot.build_portfolio_state().await.equity();
+        drop(bot);
+
+        let mut restarted = Bot::with_clients(config, DataClient::new().unwrap(), None).await.unwrap();
+        restarted.initialize().await.unwrap();
+        assert_equity_identity(&restarted).await;
+        assert_eq!(restarted.build_portfolio_state().await.equity(), equity);
+    }
+
+    #[tokio::test]
+    async fn test_two_entries_in_one_tick_keep_exposure_and_cash_consistent() {
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            order_cost_usdc: dec!(1),
+            strategy_config: StrategyConfig {
+                min_trade_interval_secs: 0,
+                ..StrategyConfig::default()
+            },
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, DataClient::new().unwrap(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+        bot.db.save_trader("0xabc", "", 1.0).await.unwrap();
+
+        // Two $40 buys of 80 shares at 0.50, each charged a $1 order cost
+        for market in ["0xfirst", "0xsecond"] {
+            let decision = bot.process_trade_intent(make_intent("0xabc", market, dec!(40))).await.unwrap();
+            assert_eq!(decision, TradeDecision::Simulated { price: dec!(0.50), size: dec!(40) });
+        }
+        let positions = bot.db.get_open_positions().await.unwrap();
+        assert!(positions.iter().all(|p| p.size == 80.0));
+        assert_eq!(*bot.total_exposure.read().await, dec!(80));
+        assert_eq!(*bot.cash_available.read().await, dec!(918));
+        assert_equity_identity(&bot).await;
+
+        // The next tick's recomputation agrees
+        bot.update_positions().await.unwrap();
+        assert_eq!(*bot.total_exposure.read().await, dec!(80));
+        assert_eq!(*bot.cash_available.read().await, dec!(918));
+    }
+
+    #[tokio::test]
+    async fn test_order_costs_are_netted_from_realized_pnl() {
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            order_cost_usdc: dec!(0.50),
+            order_cost_rate: dec!(0.01),
+            strategy_config: StrategyConfig {
+                // The stop below mustn't hold up the entry after it
+                loss_cooloff_secs: 0,
+                ..StrategyConfig::default()
+            },
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, DataClient::new().unwrap(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+
+        // Bought 100 @ 0.75, marked at 0.50: a $25 gross loss that trips the stop
+        bot.db.save_position("0xold", "", "Yes", "BUY", 100.0, 0.75, None).await.unwrap();
+        mark_positions(&bot, 0.50).await;
+        bot.check_exits().await.unwrap();
+
+        // Exit order: $0.50 + 1% of $50
+        assert_eq!(*bot.realized_pnl.read().await, dec!(-26));
+        assert_eq!(bot.get_stats().await.total_costs, dec!(1));
+
+        // The ledger also carries the entry order's cost: $0.50 + 1% of $75
+        let closed = bot.db.get_closed_positions().await.unwrap();
+        let recorded = closed[0].realized_pnl.unwrap();
+        assert!((recorded - (-25.0 - 1.0 - 1.25)).abs() < 1e-9);
+
+        // Entries are charged when they fill
+        bot.db.save_trader("0xabc", "", 1.0).await.unwrap();
+        bot.process_trade_intent(make_intent("0xabc", "0xnew", dec!(40))).await.unwrap();
+        let opened = &bot.db.get_open_positions().await.unwrap()[0];
+        let entry_cost = 0.5 + opened.size * opened.entry_price * 0.01;
+        let charged = bot.get_stats().await.total_costs - dec!(1);
+        assert!((charged.to_f64().unwrap() - entry_cost).abs() < 1e-9);
+        assert_eq!(*bot.realized_pnl.read().await, dec!(-26) - charged);
+    }
+
+    #[tokio::test]
+    async fn test_merge_restart_policy_keeps_wallet_weighted_average() {
+        let db = Database::new(&crate::testing::temp_database_url()).await.unwrap();
+        // Recorded 100 @ 0.40; the wallet holds 150 @ 0.50 after an unrecorded fill
+        db.save_position("0xaaa", "", "Yes", "BUY", 100.0, 0.40, None).await.unwrap();
+        db.save_position("0xbbb", "", "Yes", "BUY", 10.0, 0.30, None).await.unwrap();
+        let held = vec![Position::new(
+            "0xme".to_string(),
+            "0xaaa".to_string(),
+            "Yes".to_string(),
+            dec!(150),
+            dec!(0.50),
+        )];
+
+        let changed = reconcile_positions(&db, RestartPolicy::Merge, &held).await.unwrap();
+        assert_eq!(changed, 1);
+
+        // The missing 50 shares went in at 0.70, averaging to the wallet's 0.50
+        let positions = db.get_open_positions().await.unwrap();
+        let merged = positions.iter().find(|p| p.market_id == "0xaaa").unwrap();
+        assert_eq!(merged.size, 150.0);
+        assert!((merged.entry_price - 0.50).abs() < 1e-9);
+        assert!(positions.iter().any(|p| p.market_id == "0xbbb"));
+
+        // Trusting the API instead drops what the wallet doesn't hold, shorts included,
+        // without booking P&L at whatever price the rows were last marked
+        db.update_position_price("0xbbb", "Yes", 0.90).await.unwrap();
+        db.save_position("0xccc", "", "Yes", "SELL", 20.0, 0.60, None).await.unwrap();
+        reconcile_positions(&db, RestartPolicy::TrustApi, &held).await.unwrap();
+        let positions = db.get_open_positions().await.unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].market_id, "0xaaa");
+
+        let closed = db.get_closed_positions().await.unwrap();
+        assert_eq!(closed.len(), 2);
+        for row in &closed {
+            assert_eq!(row.exit_reason.as_deref(), Some("reconciled"));
+            assert_eq!(row.realized_pnl, Some(0.0));
+            assert_eq!(row.exit_price, None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restart_reconciles_against_the_proxy_wallet() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        let funder: alloy_primitives::Address = "0x00000000000000000000000000000000000f00d5".parse().unwrap();
+        let clob = mock.clob_client().with_funder(funder);
+        mock.mount_positions(&funder.to_string(), vec![crate::testing::position("0xheld", 40.0, 0.25)]).await;
+
+        let config = BotConfig {
+            dry_run: false,
+            restart_policy: RestartPolicy::TrustApi,
+            database_url: crate::testing::temp_database_url(),
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, mock.data_client(), Some(clob)).await.unwrap();
+        bot.initialize().await.unwrap();
+
+        let positions = bot.db.get_open_positions().await.unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].market_id, "0xheld");
+        assert_eq!(positions[0].size, 40.0);
+    }
+
+    #[tokio::test]
+    async fn test_trader_inactivity_survives_restart() {
+        let trader = "0x5555555555555555555555555555555555555555";
+        let last_trade = Utc::now() - chrono::Duration::days(30);
+
+        // The first session sees the old trade; afterwards it has aged out of the fetch window
+        let mock = crate::testing::MockPolymarket::start().await;
+        mock.mount_positions(trader, vec![]).await;
+        mock.mount_trades(
+            trader,
+            vec![crate::testing::trade(trader, "0xmarket", "0xold", 100.0, 0.5, last_trade.timestamp())],
+            Some(1),
+        ).await;
+        mock.mount_trades(trader, vec![], None).await;
+
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config.clone(), mock.data_client(), None).await.unwrap();
+        bot.db.save_trader(trader, "", 1.0).await.unwrap();
+        bot.initialize().await.unwrap();
+        bot.persist_watermarks().await.unwrap();
+        drop(bot);
+
+        let mut restarted = Bot::with_clients(config, mock.data_client(), None).await.unwrap();
+        restarted.initialize().await.unwrap();
+        assert_eq!(
+            restarted.copy_engine.last_trades_seen().await[trader].timestamp(),
+            last_trade.timestamp()
+        );
+        assert_eq!(restarted.copy_engine.detect_inactive_traders(Utc::now()).await, vec![trader.to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_restart_restores_peak_equity_for_drawdown() {
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            ..BotConfig::default()
+        };
+
+        let mut bot = Bot::with_clients(config.clone(), DataClient::new().unwrap(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+        *bot.peak_equity.write().await = dec!(1250);
+        bot.update_bot_state().await.unwrap();
+        drop(bot);
+
+        let mut restarted = Bot::with_clients(config.clone(), DataClient::new().unwrap(), None).await.unwrap();
+        restarted.initialize().await.unwrap();
+        assert_eq!(*restarted.peak_equity.read().await, dec!(1250));
+
+        // Back at the $1000 starting equity: 20% below the restored peak
+        let portfolio = restarted.build_portfolio_state().await;
+        assert_eq!(portfolio.current_drawdown, dec!(0.2));
+        assert!(restarted.strategy.should_halt_trading(&portfolio).0);
+        drop(restarted);
+
+        // Resetting the peak on restart clears the halt, and the reset peak is what's stored
+        let reset = BotConfig { reset_peak_equity: true, ..config };
+        let mut restarted = Bot::with_clients(reset, DataClient::new().unwrap(), None).await.unwrap();
+        restarted.initialize().await.unwrap();
+        let portfolio = restarted.build_portfolio_state().await;
+        assert_eq!(portfolio.current_drawdown, Decimal::ZERO);
+        assert!(!restarted.strategy.should_halt_trading(&portfolio).0);
+        assert_eq!(restarted.db.get_bot_state().await.unwrap().peak_equity, 1000.0);
+    }
+
+    #[tokio::test]
+    async fn test_portfolio_take_profit_closes_everything_and_pauses() {
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            strategy_config: StrategyConfig {
+                portfolio_take_profit: Some(dec!(0.10)),
+                ..StrategyConfig::default()
+            },
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, DataClient::new().unwrap(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+
+        // 1000 shares bought at 0.40 and marked at 0.50: +$100, 10% on $1000
+        bot.db.save_position("0xaaa", "", "Yes", "BUY", 600.0, 0.40, None).await.unwrap();
+        bot.db.save_position("0xbbb", "", "No", "BUY", 400.0, 0.40, None).await.unwrap();
+        mark_positions(&bot, 0.50).await;
+
+        bot.tick().await.unwrap();
+
+        assert!(bot.db.get_open_positions().await.unwrap().is_empty());
+        assert_eq!(*bot.realized_pnl.read().await, dec!(100));
+        assert_eq!(*bot.unrealized_pnl.read().await, Decimal::ZERO);
+
+        // Gains are banked, so trading stays paused
+        let portfolio = bot.build_portfolio_state().await;
+        let (halted, reason) = bot.strategy.should_halt_trading(&portfolio);
+        assert!(halted);
+        assert!(reason.contains("take-profit"));
+    }
+
+    #[tokio::test]
+    async fn test_manual_close_records_manual_close_exit() {
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, DataClient::new().unwrap(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+
+        // Bought 100 @ 0.40, marked at 0.50
+        bot.db.save_position("0xaaa", "", "Yes", "BUY", 100.0, 0.40, None).await.unwrap();
+        bot.db.save_position("0xbbb", "", "Yes", "BUY", 100.0, 0.40, None).await.unwrap();
+        mark_positions(&bot, 0.50).await;
+
+        // A preview reports the same P&L without closing anything
+        let preview = bot.preview_close("0xaaa", "yes").await.unwrap();
+        assert!((preview - dec!(10)).abs() < dec!(0.000001));
+        assert_eq!(bot.db.get_open_positions().await.unwrap().len(), 2);
+
+        let realized = bot.close_manually("0xaaa", "yes").await.unwrap();
+        assert!((realized - dec!(10)).abs() < dec!(0.000001));
+        assert!(bot.close_manually("0xaaa", "Yes").await.is_err());
+
+        let closed = bot.db.get_closed_positions().await.unwrap();
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].exit_reason.as_deref(), Some("manual_close"));
+        assert!((closed[0].realized_pnl.unwrap() - 10.0).abs() < 1e-9);
+        assert_eq!(bot.db.get_open_positions().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_loss_cooloff_expires_on_injected_clock() {
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            ..BotConfig::default()
+        };
+        let clock = crate::testing::MockClock::new(Utc::now());
+        let mut bot = Bot::with_clients(config, DataClient::new().unwrap(), None)
+            .await
+            .unwrap()
+            .with_clock(Arc::new(clock.clone()));
+        bot.initialize().await.unwrap();
+
+        // Bought 100 @ 0.60, marked at 0.50
+        bot.db.save_position("0xaaa", "", "Yes", "BUY", 100.0, 0.60, None).await.unwrap();
+        mark_positions(&bot, 0.50).await;
+        bot.close_manually("0xaaa", "Yes").await.unwrap();
+
+        let portfolio = bot.build_portfolio_state().await;
+        assert_eq!(portfolio.last_loss_at, Some(clock.now()));
+        let validate = || {
+            bot.strategy.validate_entry(&EntryQuote::new(clock.now(), dec!(0.50), dec!(0.50)), dec!(10), None, &portfolio, &[], None)
+        };
+        assert!(validate().reason.contains("cool-off"));
+
+        clock.advance(chrono::Duration::seconds(bot.strategy.config().loss_cooloff_secs));
+        assert!(validate().allowed);
+    }
+
+    #[tokio::test]
+    async fn test_trader_with_sustained_losses_is_auto_paused() {
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            strategy_config: StrategyConfig {
+                auto_prune_losers: true,
+                prune_loss_usdc: dec!(50),
+                ..StrategyConfig::default()
+            },
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, DataClient::new().unwrap(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+        bot.db.save_trader("0xloser", "", 1.0).await.unwrap();
+        bot.db.save_trader("0xdipped", "", 1.0).await.unwrap();
+
+        // 0xloser: -$30 twice; 0xdipped: -$20 once
+        for (market, trader) in [("0xm1", "0xloser"), ("0xm2", "0xloser"), ("0xm3", "0xdipped")] {
+            let exit = if trader == "0xloser" { 0.20 } else { 0.30 };
+            bot.db.save_position(market, "", "Yes", "BUY", 100.0, 0.50, Some(trader)).await.unwrap();
+            bot.db.update_position_price(market, "Yes", exit).await.unwrap();
+            bot.db.close_position(market, "Yes", 0.0, "stop_loss").await.unwrap();
+        }
+
+        bot.prune_losing_traders().await.unwrap();
+        assert_eq!(bot.db.get_paused_addresses().await.unwrap(), ["0xloser"]);
+        assert_eq!(bot.db.get_tracked_addresses().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_stops_after_max_runtime() {
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            poll_interval_secs: 3600,
+            max_runtime: Some(Duration::from_millis(50)),
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, DataClient::new().unwrap(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+
+        tokio::time::timeout(Duration::from_secs(10), bot.run())
+            .await
+            .expect("run loop should stop on its own")
+            .unwrap();
+        assert!(!bot.get_stats().await.is_running);
+    }
+
+    #[tokio::test]
+    async fn test_allocation_ramp_starts_with_the_first_live_session() {
+        let database_url = crate::testing::temp_database_url();
+        let trading_config = TradingConfig {
+            max_portfolio_allocation: dec!(0.5),
+            allocation_ramp_days: 10,
+            allocation_ramp_start: dec!(0.1),
+            ..TradingConfig::default()
+        };
+
+        // Dry runs a month before going live don't advance the ramp
+        let dry = BotConfig {
+            database_url: database_url.clone(),
+            trading_config: trading_config.clone(),
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(dry, DataClient::new().unwrap(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+        sqlx::query("UPDATE bot_state SET started_at = datetime('now', '-30 days')")
+            .execute(bot.db.pool())
+            .await
+            .unwrap();
+        assert_eq!(bot.allocation_cap(Utc::now()), dec!(0.1));
+        drop(bot);
+
+        let mock = crate::testing::MockPolymarket::start().await;
+        let live = BotConfig {
+            dry_run: false,
+            database_url,
+            trading_config,
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(live, mock.data_client(), Some(mock.clob_client())).await.unwrap();
+        bot.initialize().await.unwrap();
+        let now = Utc::now();
+        assert!(bot.allocation_cap(now) < dec!(0.11));
+        let halfway = bot.allocation_cap(now + chrono::Duration::days(5));
+        assert!(halfway > dec!(0.29) && halfway < dec!(0.31));
+        assert_eq!(bot.allocation_cap(now + chrono::Duration::days(10)), dec!(0.5));
+    }
+
+    #[tokio::test]
+    async fn test_wallet_balance_sync_updates_sizing_base() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        let clob = mock.clob_client();
+        mock.mount_balance(2500.0).await;
+        mock.mount_value(&clob.funder_address().to_string(), 500.0).await;
+
+        let config = BotConfig {
+            dry_run: false,
+            auto_sync_portfolio: true,
+            database_url: crate::testing::temp_database_url(),
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, mock.data_client(), Some(clob)).await.unwrap();
+        bot.initialize().await.unwrap();
+        assert_eq!(bot.copy_engine.portfolio_value().await, dec!(1000));
+
+        bot.sync_portfolio_value().await.unwrap();
+
+        assert_eq!(bot.copy_engine.portfolio_value().await, dec!(3000));
+        assert_eq!(*bot.portfolio_value.read().await, dec!(3000));
+        assert_eq!(*bot.cash_available.read().await, dec!(2500));
+
+        // Repricing positions later in the tick keeps the synced cash
+        bot.update_positions().await.unwrap();
+        assert_eq!(*bot.cash_available.read().await, dec!(2500));
+    }
+
+    #[tokio::test]
+    async fn test_simulate_live_marks_with_real_prices_without_ordering() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        let market = "0xlive";
+        mock.mount_market(market, false).await;
+        mock.mount_order_book("1", "0.48", "0.52").await;
+
+        // Live settings, which simulate-live overrides
+        let config = BotConfig {
+            dry_run: false,
+            cancel_on_exit: true,
+            database_url: crate::testing::temp_database_url(),
+            strategy_config: StrategyConfig {
+                min_trade_interval_secs: 0,
+                ..StrategyConfig::default()
+            },
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::simulate_live_with_clients(config, mock.data_client(), mock.clob_client()).await.unwrap();
+        assert!(bot.config.dry_run && !bot.config.cancel_on_exit);
+        bot.initialize().await.unwrap();
+        bot.db.save_trader("0xabc", "", 1.0).await.unwrap();
+
+        // 80 shares sized at the 0.50 mid fill at the 0.52 ask
+        let decision = bot.process_trade_intent(make_intent("0xabc", market, dec!(40))).await.unwrap();
+        assert_eq!(decision, TradeDecision::Simulated { price: dec!(0.52), size: dec!(41.6) });
+        assert_eq!(mock.order_submissions().await, 0);
+
+        // The book moves up ten cents
+        mock.reset().await;
+        mock.mount_market(market, false).await;
+        mock.mount_order_book("1", "0.58", "0.62").await;
+        bot.update_positions().await.unwrap();
+
+        let positions = bot.db.get_open_positions().await.unwrap();
+        assert_eq!(positions[0].current_price, 0.60);
+        assert_eq!(*bot.unrealized_pnl.read().await, dec!(6.4));
+        assert_eq!(mock.order_submissions().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_entry_retried_when_market_status_unavailable() {
+        // No market is mounted, so the status lookup fails
+        let mock = crate::testing::MockPolymarket::start().await;
+        mock.mount_order_book("1", "0.48", "0.52").await;
+
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, mock.data_client(), Some(mock.clob_client())).await.unwrap();
+        bot.initialize().await.unwrap();
+        bot.db.save_trader("0xabc", "", 1.0).await.unwrap();
+
+        let intent = make_intent("0xabc", "0xunknown", dec!(40));
+        assert!(bot.process_trade_intent(intent.clone()).await.is_err());
+        assert!(bot.db.get_open_positions().await.unwrap().is_empty());
+        let trade_id = format!("0xabc-{}", intent.source_trade.id);
+        assert!(!bot.db.has_seen_trade(&trade_id).await.unwrap(), "left for a retry");
+
+        // Once the status is known the same trade is copied
+        mock.mount_market("0xunknown", false).await;
+        let decision = bot.process_trade_intent(intent).await.unwrap();
+        assert!(matches!(decision, TradeDecision::Simulated { .. }), "{:?}", decision);
+    }
+
+    #[tokio::test]
+    async fn test_entry_into_recorded_resolution_rejected_without_clob() {
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, DataClient::new().unwrap(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+        bot.db.save_trader("0xabc", "", 1.0).await.unwrap();
+        bot.db.save_resolved_market("0xdone", "Yes", Utc::now()).await.unwrap();
+
+        let decision = bot.process_trade_intent(make_intent("0xabc", "0xdone", dec!(40))).await.unwrap();
+        assert_eq!(decision, TradeDecision::Rejected { reason: "Market resolved".to_string() });
+        assert!(bot.db.get_open_positions().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delayed_copy_cancelled_when_price_moves() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        let market = "0xdelayed";
+        mock.mount_market(market, false).await;
+        mock.mount_order_book("1", "0.48", "0.52").await;
+
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            strategy_config: StrategyConfig {
+                min_trade_interval_secs: 0,
+                copy_delay_secs: Some(1),
+                ..StrategyConfig::default()
+            },
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, mock.data_client(), Some(mock.clob_client())).await.unwrap();
+        bot.initialize().await.unwrap();
+        bot.db.save_trader("0xabc", "", 1.0).await.unwrap();
+
+        // The book runs away from the source's 0.50 while the copy waits
+        let move_book = async {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            mock.reset().await;
+            mock.mount_market(market, false).await;
+            mock.mount_order_book("1", "0.58", "0.62").await;
+        };
+        let (decision, _) = tokio::join!(bot.process_trade_intent(make_intent("0xabc", market, dec!(40))), move_book);
+
+        match decision.unwrap() {
+            TradeDecision::Rejected { reason } => assert!(reason.contains("Slippage too high")),
+            other => panic!("expected the copy to be cancelled, got {:?}", other),
+        }
+        assert!(bot.db.get_open_positions().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delayed_copy_sized_against_book_after_delay() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        let market = "0xthinned";
+        mock.mount_market(market, false).await;
+        mock.mount_order_book("1", "0.49", "0.51").await;
+
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            strategy_config: StrategyConfig {
+                min_trade_interval_secs: 0,
+                copy_delay_secs: Some(1),
+                max_book_fraction: Some(dec!(0.1)),
+                ..StrategyConfig::default()
+            },
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, mock.data_client(), Some(mock.clob_client())).await.unwrap();
+        bot.initialize().await.unwrap();
+        bot.db.save_trader("0xabc", "", 1.0).await.unwrap();
+
+        // The ask thins from 500 to 100 shares while the copy waits: 10% of $51
+        let thin_book = async {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            mock.reset().await;
+            mock.mount_market(market, false).await;
+            mock.mount_order_book_with_size("1", "0.49", "0.51", "100").await;
+        };
+        let (decision, _) = tokio::join!(bot.process_trade_intent(make_intent("0xabc", market, dec!(40))), thin_book);
+
+        match decision.unwrap() {
+            // $5.10 of depth is 10.2 shares at the 0.50 mid
+            TradeDecision::Simulated { price, size } => assert!(size > Decimal::ZERO && size / price <= dec!(10.2)),
+            other => panic!("expected a simulated copy, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_submission_found_on_book_is_executed() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        let market = "0xrested";
+        mock.mount_market(market, false).await;
+        mock.mount_order_book("1", "0.48", "0.52").await;
+        // The submission errors, yet the order is resting on the book
+        mock.reject_order_submissions().await;
+        mock.mount_order_lookup(crate::testing::open_order("0xresting")).await;
+
+        let config = BotConfig {
+            dry_run: false,
+            order_confirm_grace: Duration::ZERO,
+            database_url: crate::testing::temp_database_url(),
+            strategy_config: StrategyConfig {
+                min_trade_interval_secs: 0,
+                ..StrategyConfig::default()
+            },
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, mock.data_client(), Some(mock.clob_client())).await.unwrap();
+        bot.initialize().await.unwrap();
+        bot.db.save_trader("0xabc", "", 1.0).await.unwrap();
+
+        let decision = bot.process_trade_intent(make_intent("0xabc", market, dec!(40))).await.unwrap();
+        assert_eq!(decision, TradeDecision::Executed { order_id: Some("0xresting".to_string()) });
+        assert_eq!(mock.order_submissions().await, 1);
+        assert!(bot.db.get_copy_trades_with_status(CopyTradeStatus::Failed).await.unwrap().is_empty());
+        assert_eq!(bot.db.get_copy_trades_with_status(CopyTradeStatus::Executed).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_reprocessed_trade_signs_the_same_order() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        let market = "0xagain";
+        mock.mount_market(market, false).await;
+        mock.mount_order_book("1", "0.48", "0.52").await;
+        mock.expect_order_submission(Duration::ZERO, 2).await;
+
+        let intent = make_intent("0xabc", market, dec!(40));
+        // The same source trade, polled again later by a restarted process whose seen
+        // record never committed
+        let mut repolled = intent.clone();
+        repolled.created_at += chrono::Duration::seconds(90);
+        for intent in [intent, repolled] {
+            let config = BotConfig {
+                dry_run: false,
+                database_url: crate::testing::temp_database_url(),
+                ..BotConfig::default()
+            };
+            let mut bot = Bot::with_clients(config, mock.data_client(), Some(mock.clob_client())).await.unwrap();
+            bot.initialize().await.unwrap();
+            bot.db.save_trader("0xabc", "", 1.0).await.unwrap();
+            bot.process_trade_intent(intent).await.unwrap();
+        }
+
+        let orders = mock.submitted_orders().await;
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[0]["order"], orders[1]["order"]);
+    }
+
+    #[tokio::test]
+    async fn test_resolution_is_cached_and_settles_positions_on_restart() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        let market = "0xsettled";
+        let closed_at = Utc::now() - chrono::Duration::hours(1);
+        mock.mount_resolution(market, "Yes", closed_at).await;
+
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            ..BotConfig::default()
+        };
+        let bot = Bot::with_clients(config.clone(), mock.data_client(), Some(mock.clob_client())).await.unwrap();
+        let first = bot.market_resolution(market).await.unwrap().unwrap();
+        assert_eq!(first.winning_outcome, "Yes");
+        // Timed at the market's close, not its scheduled end
+        assert_eq!(first.resolved_at, closed_at.format("%Y-%m-%d %H:%M:%S").to_string());
+
+        assert_eq!(mock.market_fetches().await, 1);
+
+        // The second lookup is served from the cache
+        assert_eq!(bot.market_resolution(market).await.unwrap(), Some(first));
+        assert_eq!(mock.market_fetches().await, 1);
+
+        // A restarted bot settles its open positions without asking the API; the short
+        // in the losing outcome gains its entry price
+        bot.db.save_position(market, "", "Yes", "BUY", 100.0, 0.40, None).await.unwrap();
+        bot.db.save_position(market, "", "No", "SELL", 100.0, 0.30, None).await.unwrap();
+        let mut restarted = Bot::with_clients(config, mock.data_client(), Some(mock.clob_client())).await.unwrap();
+        restarted.initialize().await.unwrap();
+
+        assert!(restarted.db.get_open_positions().await.unwrap().is_empty());
+        let closed = restarted.db.get_closed_positions().await.unwrap();
+        assert!(closed.iter().all(|p| p.exit_reason.as_deref() == Some(SETTLEMENT_EXIT_REASON)));
+        let won = closed.iter().find(|p| p.outcome == "Yes").unwrap();
+        assert_eq!(won.exit_price, Some(1.0));
+        assert_eq!(*restarted.realized_pnl.read().await, dec!(90));
+        assert_eq!(mock.market_fetches().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_unresolved_market_looked_up_once_per_interval() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        let market = "0xpending";
+        mock.mount_market(market, false).await;
+
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, mock.data_client(), Some(mock.clob_client())).await.unwrap();
+        bot.db.save_position(market, "", "Yes", "BUY", 100.0, 0.40, None).await.unwrap();
+        bot.db.save_position(market, "", "No", "BUY", 100.0, 0.55, None).await.unwrap();
+
+        // Both positions share one lookup, and the next tick makes none
+        assert_eq!(bot.settle_resolved_positions(false).await.unwrap(), 0);
+        assert_eq!(bot.settle_resolved_positions(false).await.unwrap(), 0);
+        assert_eq!(mock.market_fetches().await, 1);
+    }
+
+    async fn shutdown_with_cancel_on_exit(cancel_on_exit: bool) {
+        let mock = crate::testing::MockPolymarket::start().await;
+        mock.expect_cancel_all(if cancel_on_exit { 1 } else { 0 }).await;
+
+        let config = BotConfig {
+            dry_run: false,
+            cancel_on_exit,
+            database_url: crate::testing::temp_database_url(),
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, mock.data_client(), Some(mock.clob_client()))
+            .await
+            .unwrap();
+        bot.initialize().await.unwrap();
+        bot.shutdown().await.unwrap();
+
+        assert_eq!(bot.db.get_equity_curve(1).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_cancels_orders_when_enabled() {
+        shutdown_with_cancel_on_exit(true).await;
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_leaves_orders_by_default() {
+        shutdown_with_cancel_on_exit(false).await;
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_fetches_final_fills_of_resting_orders() {
+        let mock = crate::testing::MockPolymarket::start().await;
+        mock.mount_open_orders(vec![
+            crate::testing::open_order("0xpartial"),
+            crate::testing::open_order("0xuntouched"),
+        ]).await;
+        let mut partial = crate::testing::open_order("0xpartial");
+        partial["status"] = serde_json::json!("CANCELED");
+        partial["sizeMatched"] = serde_json::json!("4");
+        mock.mount_order_lookup(partial).await;
+
+        let config = BotConfig {
+            dry_run: false,
+            database_url: crate::testing::temp_database_url(),
+            ..BotConfig::default()
+        };
+        let bot = Bot::with_clients(config, mock.data_client(), Some(mock.clob_client()))
+            .await
+            .unwrap();
+
+        let resting = bot.resting_orders().await;
+        assert_eq!(resting.len(), 2);
+        let filled = bot.final_fills(vec![resting[0].clone()]).await;
+        assert_eq!(filled.len(), 1);
+        assert_eq!(filled[0].matched_size(), dec!(4));
+        assert_eq!(filled[0].status, "CANCELED");
+    }
+
+    #[tokio::test]
+    async fn test_streamed_mid_preferred_over_book_once_received() {
+        use futures::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message;
+
+        use crate::testing::{self, MockPolymarket};
+
+        let mock = MockPolymarket::start().await;
+        mock.mount_market("0xm1", false).await;
+        mock.mount_order_book("1", "0.40", "0.42").await;
+
+        // Answers every subscription with a book for the Yes token
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            while let Some(Ok(message)) = socket.next().await {
+                if matches!(&message, Message::Text(text) if text.contains("assets_ids")) {
+                    let book = r#"{"event_type": "book", "asset_id": "1", "bids": [{"price": "0.70"}], "asks": [{"price": "0.72"}]}"#;
+                    socket.send(Message::Text(book.to_string())).await.unwrap();
+                }
+            }
+        });
+
+        let config = BotConfig { database_url: testing::temp_database_url(), ..BotConfig::default() };
+        let mut bot = Bot::with_clients(config, mock.data_client(), Some(mock.clob_client())).await.unwrap();
+        bot.market_stream = Some(MarketStream::spawn(url));
+
+        // Nothing streamed before the first subscription, so the book serves it
+        assert_eq!(bot.get_current_price("0xm1", "Yes").await.unwrap(), dec!(0.41));
+
+        let mut price = Decimal::ZERO;
+        for _ in 0..200 {
+            price = bot.get_current_price("0xm1", "Yes").await.unwrap();
+            if price == dec!(0.71) {
+                break;
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(price, dec!(0.71));
+    }
+
+    #[test]
+    fn test_stats_list_why_trades_were_sized_to_zero() {
+        let stats = BotStats {
+            portfolio_value: dec!(1000),
+            cash_available: dec!(1000),
+            total_exposure: Decimal::ZERO,
+            unrealized_pnl: Decimal::ZERO,
+            realized_pnl: Decimal::ZERO,
+            total_costs: Decimal::ZERO,
+            max_drawdown: Decimal::ZERO,
+            tracked_traders: 1,
+            zero_size_reasons: HashMap::from([(ZeroSizeReason::NoEdge, 1), (ZeroSizeReason::ExposureCap, 2)]),
+            total_trades: 0,
+            executed_trades: 0,
+            failed_trades: 0,
+            is_running: true,
+            dry_run: true,
+        };
+        assert!(stats.to_string().contains("Sized to Zero:   3 (exposure cap: 2, no edge: 1)"));
+    }
+
+    /// Buy intent for 100 shares at 0.50 from `trader` in `market`.
+    fn make_intent(trader: &str, market: &str, calculated_size: Decimal) -> CopyTradeIntent {
+        CopyTradeIntent {
+            source_trader: trader.to_string(),
+            source_trade: Trade {
+                id: format!("0xtx_{}", market),
+                trader_address: trader.to_string(),
+                market_id: market.to_string(),
+                market_title: String::new(),
+                side: TradeSide::Buy,
+                outcome: "Yes".to_string(),
+                size: dec!(100),
+                price: dec!(0.50),
+                amount_usdc: dec!(50),
+                timestamp: Utc::now(),
+                transaction_hash: "0xtx".to_string(),
+                is_taker: true,
+                fee_usdc: Decimal::ZERO,
+            },
+            calculated_size,
+            created_at: Utc::now(),
+            source_held_market: false,
+            source_position_pnl: None,
+        }
+    }
+
+    /// Poll a bot's tracked traders and process each intent, keyed by market.
+    async fn poll_and_process(bot: &mut Bot) -> HashMap<String, TradeDecision> {
+        let mut decisions = HashMap::new();
+        for intent in bot.copy_engine.poll_for_trades().await.unwrap() {
+            let market = intent.source_trade.market_id.clone();
+            decisions.insert(market, bot.process_trade_intent(intent).await.unwrap());
+        }
+        decisions
+    }
+
+    #[tokio::test]
+    async fn test_new_markets_only_skips_source_adds() {
+        use crate::testing::{self, MockPolymarket};
+
+        let trader = "0x3333333333333333333333333333333333333333";
+        let now = Utc::now().timestamp() - 10;
+        let mock = MockPolymarket::start().await;
+        // Holdings fetched after both trades: 0xnew holds nothing beyond the trade itself
+        mock.mount_positions(
+            trader,
+            vec![testing::position("0xheld", 200.0, 0.5), testing::position("0xnew", 20.0, 0.5)],
+        ).await;
+        mock.mount_trades(trader, vec![], Some(1)).await;
+        mock.mount_trades(
+            trader,
+            vec![
+                testing::trade(trader, "0xheld", "0xadd", 20.0, 0.5, now),
+                testing::trade(trader, "0xnew", "0xentry", 20.0, 0.5, now),
+            ],
+            None,
+        ).await;
+
+        let config = BotConfig {
+            database_url: testing::temp_database_url(),
+            trading_config: TradingConfig {
+                sizing_method: "equal".to_string(),
+                ..TradingConfig::default()
+            },
+            strategy_config: StrategyConfig {
+                new_markets_only: true,
+                min_trade_interval_secs: 0,
+                ..StrategyConfig::default()
+            },
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, mock.data_client(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+        bot.add_trader(trader).await.unwrap();
+
+        let decisions = poll_and_process(&mut bot).await;
+        assert!(matches!(decisions["0xheld"], TradeDecision::Rejected { .. }));
+        assert!(matches!(decisions["0xnew"], TradeDecision::Simulated { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_profit_only_filter_skips_underwater_source_positions() {
+        use crate::testing::{self, MockPolymarket};
+
+        let trader = "0x4444444444444444444444444444444444444444";
+        let now = Utc::now().timestamp() - 10;
+        let mock = MockPolymarket::start().await;
+        let mut underwater = testing::position("0xunder", 200.0, 0.60);
+        underwater["curPrice"] = serde_json::json!(0.40);
+        let mut winning = testing::position("0xup", 200.0, 0.40);
+        winning["curPrice"] = serde_json::json!(0.60);
+        mock.mount_positions(trader, vec![underwater, winning]).await;
+        mock.mount_trades(trader, vec![], Some(1)).await;
+        mock.mount_trades(
+            trader,
+            vec![
+                testing::trade(trader, "0xunder", "0xdip", 20.0, 0.5, now),
+                testing::trade(trader, "0xup", "0xmomentum", 20.0, 0.5, now),
+            ],
+            None,
+        ).await;
+
+        let config = BotConfig {
+            database_url: testing::temp_database_url(),
+            trading_config: TradingConfig {
+                sizing_method: "equal".to_string(),
+                ..TradingConfig::default()
+            },
+            strategy_config: StrategyConfig {
+                source_position_pnl_filter: SourcePnlFilter::ProfitOnly,
+                min_trade_interval_secs: 0,
+                ..StrategyConfig::default()
+            },
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, mock.data_client(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+        bot.add_trader(trader).await.unwrap();
+
+        let decisions = poll_and_process(&mut bot).await;
+        assert_eq!(
+            decisions["0xunder"],
+            TradeDecision::Rejected { reason: "Source position P&L filtered".to_string() }
+        );
+        assert!(matches!(decisions["0xup"], TradeDecision::Simulated { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_dust_source_trades_are_skipped() {
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            strategy_config: StrategyConfig {
+                min_source_trade_usdc: dec!(10),
+                min_trade_interval_secs: 0,
+                ..StrategyConfig::default()
+            },
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, DataClient::new().unwrap(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+        bot.db.save_trader("0xabc", "", 1.0).await.unwrap();
+
+        let mut dust = make_intent("0xabc", "0xdust", dec!(20));
+        dust.source_trade.amount_usdc = dec!(2);
+        let decision = bot.process_trade_intent(dust).await.unwrap();
+        assert!(matches!(decision, TradeDecision::Rejected { .. }));
+        assert!(bot.db.get_open_positions().await.unwrap().is_empty());
+
+        let decision = bot.process_trade_intent(make_intent("0xabc", "0xreal", dec!(20))).await.unwrap();
+        assert!(matches!(decision, TradeDecision::Simulated { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_execution_cap_defers_excess_intents() {
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            max_executions_per_tick: Some(2),
+            strategy_config: StrategyConfig { min_trade_interval_secs: 0, ..StrategyConfig::default() },
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, DataClient::new().unwrap(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+        bot.db.save_trader("0xabc", "", 1.0).await.unwrap();
+
+        let intents = (1..=5).map(|i| make_intent("0xabc", &format!("0xm{}", i), dec!(20))).collect();
+        bot.process_intents(intents).await.unwrap();
+
+        let simulated = bot.db.get_copy_trades_with_status(CopyTradeStatus::Simulated).await.unwrap();
+        let markets: Vec<&str> = simulated.iter().map(|t| t.market_id.as_str()).collect();
+        assert_eq!(markets, ["0xm1", "0xm2"]);
+        assert_eq!(bot.db.count_pending_intents().await.unwrap(), 3);
+
+        // Deferred intents go first on the next tick
+        bot.process_intents(Vec::new()).await.unwrap();
+        let simulated = bot.db.get_copy_trades_with_status(CopyTradeStatus::Simulated).await.unwrap();
+        assert_eq!(simulated.len(), 4);
+        assert_eq!(simulated[3].market_id, "0xm4");
+        assert_eq!(bot.db.count_pending_intents().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_intent_returns_simulated_decision() {
+        let config = BotConfig {
+            database_url: crate::testing::temp_database_url(),
+            ..BotConfig::default()
+        };
+        let mut bot = Bot::with_clients(config, DataClient::new().unwrap(), None).await.unwrap();
+        bot.initialize().await.unwrap();
+        bot.db.save_trader("0xabc", "", 1.0).await.unwrap();
+
+        let intent = make_intent("0xabc", "0xmarket", dec!(40));
+
+        let decision = bot.process_trade_intent(intent.clone()).await.unwrap();
+        assert_eq!(decision, TradeDecision::Simulated { price: dec!(0.50), size: dec!(40) });
+
+        let repeat = bot.process_trade_intent(intent).await.unwrap();
+        assert_eq!(repeat, TradeDecision::Deduped);
+    }
+
+    #[tokio::test]
+    async fn test_bad_fill_triggers_configured_reaction() {
+        let exec = MockExecutor { fill_price: Some("0.56"), ..MockExecutor::new(1) };
+        let mut config = StrategyConfig {
+            max_realized_slippage: Some(dec!(0.05)),
+            ..StrategyConfig::default()
+        };
+
+        // 0.50 -> 0.56 is 12% worse than validated: alert only
+        let review = review_fill(&exec, &Strategy::new(config.clone()), "o1", "t", &TradeSide::Buy, dec!(10), dec!(0.50))
+            .await;
+        assert!(matches!(review, FillReview::BadFill { closed: false, .. }));
+        assert_eq!(exec.market_orders.load(Ordering::SeqCst), 0);
+
+        // With close_on_bad_fill the filled size is unwound, not the 20 requested
+        config.close_on_bad_fill = true;
+        let review = review_fill(&exec, &Strategy::new(config.clone()), "o1", "t", &TradeSide::Buy, dec!(20), dec!(0.50))
+            .await;
+        assert!(matches!(review, FillReview::BadFill { closed: true, .. }));
+        assert_eq!(exec.market_orders.load(Ordering::SeqCst), 1);
+        assert_eq!(*exec.market_sizes.lock().unwrap(), vec![dec!(10)]);
+
+        // A fill within the limit is accepted at its realized price
+        let review = review_fill(&exec, &Strategy::new(config), "o1", "t", &TradeSide::Buy, dec!(10), dec!(0.55))
+            .await;
+        assert_eq!(review, FillReview::Ok { fill_price: dec!(0.56) });
+    }
+
+    #[tokio::test]
+    async fn test_fill_within_patience_skips_market_order() {
+        let exec = MockExecutor::new(2);
+        let response = place_with_patience(
+            &exec, "token", OrderSide::Buy, dec!(10), dec!(0.5),
+            Duration::from_millis(200), Duration::from_millis(5),
+        ).await.unwrap();
+
+        assert_eq!(response.order_id.as_deref(), Some("limit"));
+        assert_eq!(exec.market_orders.load(Ordering::SeqCst), 0);
+        assert_eq!(exec.cancels.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_unfilled_entry_escalates_to_market_order() {
+        let exec = MockExecutor::new(usize::MAX);
+        let response = place_with_patience(
+            &exec, "token", OrderSide::Buy, dec!(10), dec!(0.5),
+            Duration::from_millis(20), Duration::from_millis(5),
+        ).await.unwrap();
+
+        assert_eq!(response.order_id.as_deref(), Some("market"));
+        assert_eq!(exec.cancels.load(Ordering::SeqCst), 1);
+        assert_eq!(exec.market_orders.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fill_racing_the_cancel_is_not_bought_twice() {
+        let exec = MockExecutor { fill_on_cancel: true, ..MockExecutor::new(usize::MAX) };
+        let response = place_with_patience(
+            &exec, "token", OrderSide::Buy, dec!(10), dec!(0.5),
+            Duration::from_millis(20), Duration::from_millis(5),
+        ).await.unwrap();
+
+        assert_eq!(response.order_id.as_deref(), Some("limit"));
+        assert_eq!(exec.cancels.load(Ordering::SeqCst), 1);
+        assert_eq!(exec.market_orders.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_poll_jitter_stays_within_band() {
+        let base = Duration::from_secs(30);
+        let delays: Vec<Duration> = (0..200)
+            .map(|_| jittered_interval(base, 0.2, random_unit()))
+            .collect();
+
+        assert!(delays.iter().all(|d| *d >= Duration::from_secs(24) && *d <= Duration::from_secs(36)));
+        assert!(delays.iter().any(|d| *d != delays[0]));
+
+        // No jitter leaves the interval untouched
+        assert_eq!(jittered_interval(base, 0.0, random_unit()), base);
+    }
+}
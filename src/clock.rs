@@ -0,0 +1,18 @@
+//! Source of the current time, injected into time-based rules so tests can control it.
+
+use chrono::{DateTime, Utc};
+
+/// Tells the current time.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The system's wall clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
@@ -0,0 +1,345 @@
+//! Configuration file support.
+//!
+//! Settings are stored as JSON and layered over the built-in defaults: any key
+//! missing from the file keeps its default value, and CLI flags override both.
+//! Unknown keys, mistyped values and out-of-range risk settings are rejected
+//! with an error naming the offending key.
+
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::display::{DisplayConfig, MAX_PRECISION};
+use crate::trading::{StrategyConfig, TradingConfig, MAX_ENTRY_PATIENCE_SECS};
+
+/// Default configuration file path.
+pub const DEFAULT_CONFIG_PATH: &str = "polycopier.json";
+
+/// Persistent bot configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppConfig {
+    /// Position sizing and trader selection
+    pub trading: TradingConfig,
+
+    /// Entry/exit rules and portfolio risk
+    pub strategy: StrategyConfig,
+
+    /// Number formatting in reports and console output
+    pub display: DisplayConfig,
+}
+
+impl AppConfig {
+    /// Load configuration from a file, falling back to defaults if it doesn't exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        Self::from_json(&contents)
+            .with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    /// Parse and validate a JSON config document.
+    pub fn from_json(contents: &str) -> Result<Self> {
+        let value: Value = serde_json::from_str(contents).context("Malformed JSON")?;
+        let defaults = serde_json::to_value(Self::default())?;
+        check_keys(&value, &defaults, &mut Vec::new())?;
+
+        let config: Self = serde_json::from_value(value)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check semantic invariants that the types alone can't express.
+    pub fn validate(&self) -> Result<()> {
+        let t = &self.trading;
+        fraction("trading.max_portfolio_allocation", t.max_portfolio_allocation)?;
+        fraction("trading.max_single_position", t.max_single_position)?;
+        fraction("trading.allocation_ramp_start", t.allocation_ramp_start)?;
+        if t.allocation_ramp_days < 0 {
+            bail!("trading.allocation_ramp_days must not be negative, got {}", t.allocation_ramp_days);
+        }
+        fraction("trading.max_drawdown_pct", t.max_drawdown_pct)?;
+        fraction("trading.slippage_tolerance", t.slippage_tolerance)?;
+        fraction("trading.kelly_fraction", t.kelly_fraction)?;
+        fraction_f64("trading.min_win_rate", t.min_win_rate)?;
+        fraction_f64("trading.max_trader_mdd", t.max_trader_mdd)?;
+        if let Some(days) = t.metrics_half_life_days.filter(|d| d.is_nan() || *d <= 0.0) {
+            bail!("trading.metrics_half_life_days must be positive, got {}", days);
+        }
+        if t.max_conviction_multiplier < Decimal::ONE {
+            bail!("trading.max_conviction_multiplier must be at least 1, got {}", t.max_conviction_multiplier);
+        }
+        if t.max_metrics_age_secs <= 0 {
+            bail!("trading.max_metrics_age_secs must be positive, got {}", t.max_metrics_age_secs);
+        }
+        if t.inactive_after_days <= 0 {
+            bail!("trading.inactive_after_days must be positive, got {}", t.inactive_after_days);
+        }
+        if t.price_sources.is_empty() {
+            bail!("trading.price_sources must list at least one source");
+        }
+        if !t.price_sources.iter().any(|s| s.is_market()) {
+            bail!("trading.price_sources must include stream or clob_book to price entries");
+        }
+        if let Some(dup) = t.price_sources.iter().enumerate().find(|(i, s)| t.price_sources[..*i].contains(s)) {
+            bail!("trading.price_sources lists {} more than once", dup.1);
+        }
+        if t.max_price_staleness_secs <= 0 {
+            bail!("trading.max_price_staleness_secs must be positive, got {}", t.max_price_staleness_secs);
+        }
+        if t.min_trade_size < Decimal::ZERO || t.min_trade_size > t.max_trade_size {
+            bail!(
+                "trading.min_trade_size must be between 0 and trading.max_trade_size ({}), got {}",
+                t.max_trade_size, t.min_trade_size
+            );
+        }
+        if !SIZING_METHODS.contains(&t.sizing_method.to_lowercase().as_str()) {
+            bail!(
+                "trading.sizing_method must be one of {}, got {:?}",
+                SIZING_METHODS.join(", "), t.sizing_method
+            );
+        }
+
+        let s = &self.strategy;
+        fraction("strategy.min_entry_price", s.min_entry_price)?;
+        fraction("strategy.max_entry_price", s.max_entry_price)?;
+        if s.min_entry_price >= s.max_entry_price {
+            bail!(
+                "strategy.min_entry_price ({}) must be below strategy.max_entry_price ({})",
+                s.min_entry_price, s.max_entry_price
+            );
+        }
+        fraction("strategy.max_entry_slippage", s.max_entry_slippage)?;
+        fraction("strategy.min_entry_slippage", s.min_entry_slippage)?;
+        fraction("strategy.add_slippage_decay", s.add_slippage_decay)?;
+        if let Some(f) = s.max_book_fraction {
+            fraction("strategy.max_book_fraction", f)?;
+        }
+        if let Some(f) = s.max_volume_fraction {
+            fraction("strategy.max_volume_fraction", f)?;
+        }
+        fraction("strategy.exit_price_scaling", s.exit_price_scaling)?;
+        fraction("strategy.max_portfolio_drawdown", s.max_portfolio_drawdown)?;
+        fraction("strategy.max_single_market_exposure", s.max_single_market_exposure)?;
+        if s.take_profit_pct <= Decimal::ZERO {
+            bail!("strategy.take_profit_pct must be positive, got {}", s.take_profit_pct);
+        }
+        if s.stop_loss_pct <= Decimal::ZERO || s.stop_loss_pct > Decimal::ONE {
+            bail!("strategy.stop_loss_pct must be in (0, 1], got {}", s.stop_loss_pct);
+        }
+        if let Some(trail) = s.trailing_stop_pct.filter(|t| *t <= Decimal::ZERO || *t > Decimal::ONE) {
+            bail!("strategy.trailing_stop_pct must be in (0, 1], got {}", trail);
+        }
+        if let Some(target) = s.hard_take_profit.filter(|t| *t <= Decimal::ZERO) {
+            bail!("strategy.hard_take_profit must be positive, got {}", target);
+        }
+        if let Some((low, high)) = s.exit_price_extreme {
+            fraction("strategy.exit_price_extreme low", low)?;
+            fraction("strategy.exit_price_extreme high", high)?;
+            if low >= high {
+                bail!("strategy.exit_price_extreme low ({}) must be below high ({})", low, high);
+            }
+        }
+        if let Some(target) = s.portfolio_take_profit.filter(|t| *t <= Decimal::ZERO) {
+            bail!("strategy.portfolio_take_profit must be positive, got {}", target);
+        }
+        if s.max_concurrent_positions == 0 {
+            bail!("strategy.max_concurrent_positions must be at least 1");
+        }
+        if s.prune_loss_usdc < Decimal::ZERO {
+            bail!("strategy.prune_loss_usdc must not be negative, got {}", s.prune_loss_usdc);
+        }
+        if s.prune_window_days <= 0 {
+            bail!("strategy.prune_window_days must be positive, got {}", s.prune_window_days);
+        }
+        if let Some(secs) = s.entry_patience_secs.filter(|secs| *secs > MAX_ENTRY_PATIENCE_SECS) {
+            bail!("strategy.entry_patience_secs must be at most {}, got {}", MAX_ENTRY_PATIENCE_SECS, secs);
+        }
+        for (key, secs) in [
+            ("strategy.max_trade_age_secs", s.max_trade_age_secs),
+            ("strategy.holdings_max_age_secs", s.holdings_max_age_secs),
+            ("strategy.min_hold_secs", s.min_hold_secs),
+            ("strategy.min_trade_interval_secs", s.min_trade_interval_secs),
+            ("strategy.loss_cooloff_secs", s.loss_cooloff_secs),
+            ("strategy.copy_delay_secs", s.copy_delay_secs.unwrap_or(0)),
+        ] {
+            if secs < 0 {
+                bail!("{} must not be negative, got {}", key, secs);
+            }
+        }
+
+        let d = &self.display;
+        for (key, dp) in [
+            ("display.money_precision", d.money_precision),
+            ("display.price_precision", d.price_precision),
+            ("display.percent_precision", d.percent_precision),
+        ] {
+            if dp > MAX_PRECISION {
+                bail!("{} must be at most {}, got {}", key, MAX_PRECISION, dp);
+            }
+        }
+        Ok(())
+    }
+
+    /// Write configuration to a file.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let contents = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, contents + "\n")
+            .with_context(|| format!("Failed to write config file {}", path.display()))
+    }
+
+    /// Set a single value by dotted key (e.g. `strategy.take_profit_pct`).
+    ///
+    /// The key must already exist; the value is parsed to match the current type.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let mut root = serde_json::to_value(&*self)?;
+
+        let mut slot = &mut root;
+        for part in key.split('.') {
+            slot = slot
+                .get_mut(part)
+                .with_context(|| format!("Unknown config key: {}", key))?;
+        }
+        if slot.is_object() {
+            bail!("Config key {} is a section, not a value", key);
+        }
+
+        *slot = match slot {
+            // Decimals are stored as strings
+            Value::String(_) => Value::String(value.to_string()),
+            _ => serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string())),
+        };
+
+        let updated: Self = serde_json::from_value(root)
+            .with_context(|| format!("Invalid value for {}: {}", key, value))?;
+        updated.validate()?;
+        *self = updated;
+        Ok(())
+    }
+}
+
+/// Accepted `trading.sizing_method` values (case-insensitive).
+const SIZING_METHODS: &[&str] = &["kelly", "fixed", "fixed_fraction", "risk_parity", "riskparity", "equal"];
+
+/// Reject keys that don't exist in the defaults, and values that don't fit their key's type.
+///
+/// Each leaf is checked by deserializing the defaults with just that value
+/// swapped in, so type errors name the exact key.
+fn check_keys(value: &Value, defaults: &Value, path: &mut Vec<String>) -> Result<()> {
+    let (Value::Object(fields), Value::Object(known)) = (value, defaults) else {
+        bail!("Config section {} must be an object", display_key(path));
+    };
+
+    for (key, field) in fields {
+        path.push(key.clone());
+        match known.get(key) {
+            None => bail!("Unknown config key: {}", display_key(path)),
+            Some(section @ Value::Object(children)) if !children.is_empty() => {
+                check_keys(field, section, path)?;
+            }
+            Some(_) => {
+                let mut probe = serde_json::to_value(AppConfig::default())?;
+                let mut slot = &mut probe;
+                for part in path.iter() {
+                    slot = &mut slot[part.as_str()];
+                }
+                *slot = field.clone();
+                serde_json::from_value::<AppConfig>(probe)
+                    .map_err(|e| anyhow!("Invalid value for {}: {}", display_key(path), e))?;
+            }
+        }
+        path.pop();
+    }
+    Ok(())
+}
+
+fn display_key(path: &[String]) -> String {
+    if path.is_empty() {
+        "(root)".to_string()
+    } else {
+        path.join(".")
+    }
+}
+
+fn fraction(key: &str, value: Decimal) -> Result<()> {
+    if value < Decimal::ZERO || value > Decimal::ONE {
+        bail!("{} must be between 0 and 1, got {}", key, value);
+    }
+    Ok(())
+}
+
+fn fraction_f64(key: &str, value: f64) -> Result<()> {
+    if !(0.0..=1.0).contains(&value) {
+        bail!("{} must be between 0 and 1, got {}", key, value);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_config_set_persists_and_reloads() {
+        let path = std::env::temp_dir().join(format!("polycopier-{}.json", uuid::Uuid::new_v4()));
+
+        let mut config = AppConfig::load(&path).unwrap();
+        assert_eq!(config.strategy.take_profit_pct, StrategyConfig::default().take_profit_pct);
+
+        config.set("strategy.take_profit_pct", "0.4").unwrap();
+        config.set("trading.include_maker_trades", "true").unwrap();
+        config.save(&path).unwrap();
+
+        let reloaded = AppConfig::load(&path).unwrap();
+        assert_eq!(reloaded.strategy.take_profit_pct, dec!(0.4));
+        assert!(reloaded.trading.include_maker_trades);
+
+        assert!(config.set("strategy.no_such_key", "1").is_err());
+        assert!(config.set("strategy.max_concurrent_positions", "lots").is_err());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_unknown_keys_are_rejected_by_name() {
+        let err = AppConfig::from_json(r#"{"strategy": {"take_proft_pct": "0.3"}}"#).unwrap_err();
+        assert!(err.to_string().contains("strategy.take_proft_pct"), "{}", err);
+
+        let err = AppConfig::from_json(r#"{"stratgy": {}}"#).unwrap_err();
+        assert!(err.to_string().contains("stratgy"), "{}", err);
+
+        // Known keys on their own are fine
+        let config = AppConfig::from_json(r#"{"strategy": {"take_profit_pct": "0.3"}}"#).unwrap();
+        assert_eq!(config.strategy.take_profit_pct, dec!(0.3));
+    }
+
+    #[test]
+    fn test_mistyped_and_out_of_range_values_are_rejected() {
+        let err = AppConfig::from_json(r#"{"strategy": {"max_concurrent_positions": "lots"}}"#).unwrap_err();
+        assert!(err.to_string().contains("strategy.max_concurrent_positions"), "{}", err);
+
+        let err = AppConfig::from_json(r#"{"strategy": {"stop_loss_pct": "1.5"}}"#).unwrap_err();
+        assert!(err.to_string().contains("strategy.stop_loss_pct"), "{}", err);
+
+        let err = AppConfig::from_json(r#"{"strategy": {"entry_patience_secs": 600}}"#).unwrap_err();
+        assert!(err.to_string().contains("strategy.entry_patience_secs"), "{}", err);
+
+        let err = AppConfig::from_json(r#"{"trading": {"max_single_position": 2}}"#).unwrap_err();
+        assert!(err.to_string().contains("trading.max_single_position"), "{}", err);
+
+        let err = AppConfig::from_json(r#"{"trading": {"sizing_method": "kely"}}"#).unwrap_err();
+        assert!(err.to_string().contains("trading.sizing_method"), "{}", err);
+
+        let mut config = AppConfig::default();
+        assert!(config.set("strategy.max_portfolio_drawdown", "3").is_err());
+        assert_eq!(config.strategy.max_portfolio_drawdown, StrategyConfig::default().max_portfolio_drawdown);
+    }
+}
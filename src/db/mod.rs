@@ -5,16 +5,52 @@
 //! - Tracked traders and their metrics
 //! - Seen trades (to avoid duplicates)
 //! - Our positions and copy trades
+//! - Resolved market outcomes
 //! - Equity curve for P&L tracking
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
-use sqlx::{sqlite::SqlitePoolOptions, SqlitePool};
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use sqlx::pool::PoolConnection;
+use sqlx::{sqlite::SqlitePoolOptions, Sqlite, SqliteConnection, SqlitePool, Transaction};
+use tokio::sync::{MappedMutexGuard, Mutex, MutexGuard};
 
 /// Database connection pool with full state management.
 pub struct Database {
     pool: SqlitePool,
+    /// Open batch transaction; while set, every query runs on it
+    batch: Mutex<Option<Transaction<'static, Sqlite>>>,
+    /// A heartbeat arrived while a batch was open and is written once it closes
+    heartbeat_due: AtomicBool,
+}
+
+/// Connection a query runs on: the open batch transaction, or one from the pool.
+enum Conn<'a> {
+    Pooled(PoolConnection<Sqlite>),
+    Batch(MappedMutexGuard<'a, Transaction<'static, Sqlite>>),
+}
+
+impl Deref for Conn<'_> {
+    type Target = SqliteConnection;
+
+    fn deref(&self) -> &SqliteConnection {
+        match self {
+            Self::Pooled(conn) => conn,
+            Self::Batch(tx) => tx,
+        }
+    }
+}
+
+impl DerefMut for Conn<'_> {
+    fn deref_mut(&mut self) -> &mut SqliteConnection {
+        match self {
+            Self::Pooled(conn) => conn,
+            Self::Batch(tx) => tx,
+        }
+    }
 }
 
 /// Bot state stored in database.
@@ -25,10 +61,47 @@ pub struct BotState {
     pub current_exposure: f64,
     pub total_pnl: f64,
     pub total_trades: i64,
+    pub peak_equity: f64,
     pub is_running: bool,
     pub last_poll_at: Option<String>,
     pub started_at: String,
     pub updated_at: String,
+    pub heartbeat_at: Option<String>,
+    pub poll_interval_secs: i64,
+    /// Start of the first session that placed real orders; dry runs leave it unset
+    pub live_started_at: Option<String>,
+}
+
+/// Poll intervals without a heartbeat before a running bot is presumed dead.
+const STALE_AFTER_POLLS: i64 = 3;
+
+impl BotState {
+    /// Last sign of life: the heartbeat, else the last poll, else the start.
+    fn last_seen(&self) -> Option<DateTime<Utc>> {
+        let at = self.heartbeat_at.as_deref().or(self.last_poll_at.as_deref()).unwrap_or(&self.started_at);
+        parse_timestamp(at)
+    }
+
+    /// Whether the state claims running but the heartbeat stopped several poll intervals ago,
+    /// as after a crash or kill that skipped graceful shutdown.
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        let limit = chrono::Duration::seconds(self.poll_interval_secs.max(1) * STALE_AFTER_POLLS);
+        self.is_running && self.last_seen().is_none_or(|seen| now - seen > limit)
+    }
+
+    /// Whether a bot process is actually alive.
+    pub fn is_live(&self, now: DateTime<Utc>) -> bool {
+        self.is_running && !self.is_stale(now)
+    }
+
+    /// Run state for status output.
+    pub fn run_state(&self, now: DateTime<Utc>) -> &'static str {
+        match (self.is_running, self.is_stale(now)) {
+            (false, _) => "Stopped",
+            (true, false) => "Running",
+            (true, true) => "Stale (no heartbeat; likely crashed)",
+        }
+    }
 }
 
 /// Stored position record.
@@ -45,7 +118,107 @@ pub struct StoredPosition {
     pub unrealized_pnl: f64,
     pub source_trader: Option<String>,
     pub opened_at: String,
-    pub updated_at: String,
+    pub closed_at: Option<String>,
+    pub exit_price: Option<f64>,
+    pub realized_pnl: Option<f64>,
+    pub exit_reason: Option<String>,
+    /// Best marked price since entry (high for a buy, low for a sell); None until first marked
+    pub peak_price: Option<f64>,
+    /// Fills the position was built from: 1 when opened, plus one per add
+    pub entries: i64,
+    /// Trading costs netted from realized P&L at close; None on rows closed before they were stored
+    pub costs: Option<f64>,
+}
+
+/// Parse a stored timestamp: SQLite's `datetime('now')` / `CURRENT_TIMESTAMP` format
+/// (`YYYY-MM-DD HH:MM:SS`, UTC), or RFC 3339.
+pub fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f")
+        .map(|t| t.and_utc())
+        .or_else(|_| DateTime::parse_from_rfc3339(s).map(|t| t.with_timezone(&Utc)))
+        .ok()
+}
+
+/// P&L of `size` shares entered at `entry` and exited at `exit`; a sell gains as the price falls.
+pub fn position_pnl(side: &str, entry: f64, exit: f64, size: f64) -> f64 {
+    if side.eq_ignore_ascii_case("sell") {
+        (entry - exit) * size
+    } else {
+        (exit - entry) * size
+    }
+}
+
+/// Exit reason recorded when a market resolves (matches `ExitReason::MarketResolution`).
+pub const SETTLEMENT_EXIT_REASON: &str = "market_resolution";
+
+/// Column definitions of the positions table.
+const POSITIONS_COLUMNS: &str = r#"
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    market_id TEXT NOT NULL,
+    market_title TEXT NOT NULL DEFAULT '',
+    outcome TEXT NOT NULL,
+    side TEXT NOT NULL,
+    size REAL NOT NULL,
+    entry_price REAL NOT NULL,
+    current_price REAL NOT NULL DEFAULT 0,
+    unrealized_pnl REAL NOT NULL DEFAULT 0,
+    source_trader TEXT,
+    opened_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    closed_at TEXT,
+    exit_price REAL,
+    realized_pnl REAL,
+    exit_reason TEXT,
+    settled_at TEXT,
+    peak_price REAL,
+    entries INTEGER NOT NULL DEFAULT 1,
+    costs REAL
+"#;
+
+/// Lifecycle status of a copy trade, stored as text in `copy_trades.status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyTradeStatus {
+    /// Recorded, not yet sent
+    Pending,
+    /// Order placed on the CLOB
+    Executed,
+    /// Filled in dry-run
+    Simulated,
+    /// Order placement failed
+    Failed,
+}
+
+impl CopyTradeStatus {
+    pub const ALL: [Self; 4] = [Self::Pending, Self::Executed, Self::Simulated, Self::Failed];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Executed => "executed",
+            Self::Simulated => "simulated",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl std::fmt::Display for CopyTradeStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for CopyTradeStatus {
+    type Err = std::convert::Infallible;
+
+    /// Case- and whitespace-insensitive. Unrecognized values read as `Failed`,
+    /// so a row with a status we don't know is never picked up and re-executed.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Ok(Self::ALL
+            .into_iter()
+            .find(|status| status.as_str().eq_ignore_ascii_case(s))
+            .unwrap_or(Self::Failed))
+    }
 }
 
 /// Stored copy trade record.
@@ -70,6 +243,14 @@ pub struct StoredCopyTrade {
     pub executed_at: Option<String>,
 }
 
+/// A market's winning outcome, cached once observed since it never changes.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct ResolvedMarket {
+    pub condition_id: String,
+    pub winning_outcome: String,
+    pub resolved_at: String,
+}
+
 /// Equity curve point for tracking P&L over time.
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct EquityPoint {
@@ -84,16 +265,72 @@ pub struct EquityPoint {
 impl Database {
     /// Create a new database connection.
     pub async fn new(database_url: &str) -> Result<Self> {
+        // Migrate over one connection and only then open the pool: a pooled connection
+        // that sat idle through a schema change prepares upserts against its stale
+        // schema and rejects conflict targets on indexes created since
+        let migrator = Self::connect(database_url, 1).await?;
+        migrator.run_migrations().await?;
+        migrator.pool.close().await;
+
+        Self::connect(database_url, 5).await
+    }
+
+    async fn connect(database_url: &str, max_connections: u32) -> Result<Self> {
         let pool = SqlitePoolOptions::new()
-            .max_connections(5)
+            .max_connections(max_connections)
             .connect(database_url)
             .await
             .context("Failed to connect to database")?;
+        Ok(Self { pool, batch: Mutex::new(None), heartbeat_due: AtomicBool::new(false) })
+    }
 
-        let db = Self { pool };
-        db.run_migrations().await?;
+    async fn conn(&self) -> Result<Conn<'_>> {
+        match MutexGuard::try_map(self.batch.lock().await, Option::as_mut) {
+            Ok(tx) => Ok(Conn::Batch(tx)),
+            Err(unbatched) => {
+                drop(unbatched);
+                Ok(Conn::Pooled(self.pool.acquire().await?))
+            }
+        }
+    }
+
+    /// Start a batch: every query until [`Database::commit_batch`] or
+    /// [`Database::rollback_batch`] runs in one transaction, so the writes land together.
+    pub async fn begin_batch(&self) -> Result<()> {
+        let mut batch = self.batch.lock().await;
+        if batch.is_some() {
+            anyhow::bail!("A write batch is already open");
+        }
+        *batch = Some(self.pool.begin().await?);
+        Ok(())
+    }
+
+    /// Commit the open batch, if any.
+    pub async fn commit_batch(&self) -> Result<()> {
+        if let Some(tx) = self.batch.lock().await.take() {
+            tx.commit().await.context("Failed to commit write batch")?;
+        }
+        self.flush_heartbeat().await
+    }
 
-        Ok(db)
+    /// Commit the open batch's writes so far and keep batching after them, so records
+    /// of something that can't be undone, like a placed order, survive a later rollback.
+    pub async fn checkpoint_batch(&self) -> Result<()> {
+        let mut batch = self.batch.lock().await;
+        if let Some(tx) = batch.take() {
+            tx.commit().await.context("Failed to commit write batch")?;
+            self.flush_heartbeat().await?;
+            *batch = Some(self.pool.begin().await?);
+        }
+        Ok(())
+    }
+
+    /// Discard the open batch's writes, if any.
+    pub async fn rollback_batch(&self) -> Result<()> {
+        if let Some(tx) = self.batch.lock().await.take() {
+            tx.rollback().await.context("Failed to roll back write batch")?;
+        }
+        self.flush_heartbeat().await
     }
 
     /// Run all database migrations.
@@ -107,15 +344,23 @@ impl Database {
                 current_exposure REAL NOT NULL DEFAULT 0,
                 total_pnl REAL NOT NULL DEFAULT 0,
                 total_trades INTEGER NOT NULL DEFAULT 0,
+                peak_equity REAL NOT NULL DEFAULT 0,
                 is_running INTEGER NOT NULL DEFAULT 0,
                 last_poll_at TEXT,
                 started_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
-                updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+                updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                heartbeat_at TEXT,
+                poll_interval_secs INTEGER NOT NULL DEFAULT 30,
+                live_started_at TEXT
             )
             "#,
         )
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await?;
+        self.add_column_if_missing("bot_state", "peak_equity", "REAL NOT NULL DEFAULT 0").await?;
+        self.add_column_if_missing("bot_state", "heartbeat_at", "TEXT").await?;
+        self.add_column_if_missing("bot_state", "poll_interval_secs", "INTEGER NOT NULL DEFAULT 30").await?;
+        self.add_column_if_missing("bot_state", "live_started_at", "TEXT").await?;
 
         // Tracked traders
         sqlx::query(
@@ -125,17 +370,27 @@ impl Database {
                 pseudonym TEXT,
                 profile_image TEXT,
                 is_tracked INTEGER NOT NULL DEFAULT 1,
+                is_paused INTEGER NOT NULL DEFAULT 0,
                 allocation_weight REAL NOT NULL DEFAULT 1.0,
                 last_known_value REAL DEFAULT 0,
                 tracking_since TEXT,
+                last_seen_trade_id TEXT,
+                last_seen_trade_at INTEGER,
+                last_trade_seen_at INTEGER,
                 created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
                 updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
             )
             "#,
         )
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await?;
 
+        // Columns added after the initial schema
+        self.add_column_if_missing("tracked_traders", "last_seen_trade_id", "TEXT").await?;
+        self.add_column_if_missing("tracked_traders", "last_seen_trade_at", "INTEGER").await?;
+        self.add_column_if_missing("tracked_traders", "is_paused", "INTEGER NOT NULL DEFAULT 0").await?;
+        self.add_column_if_missing("tracked_traders", "last_trade_seen_at", "INTEGER").await?;
+
         // Trader metrics history
         sqlx::query(
             r#"
@@ -156,7 +411,7 @@ impl Database {
             )
             "#,
         )
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await?;
 
         // Seen trades (to avoid duplicates)
@@ -170,32 +425,23 @@ impl Database {
             )
             "#,
         )
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await?;
 
         // Our positions
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS positions (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                market_id TEXT NOT NULL,
-                market_title TEXT NOT NULL DEFAULT '',
-                outcome TEXT NOT NULL,
-                side TEXT NOT NULL,
-                size REAL NOT NULL,
-                entry_price REAL NOT NULL,
-                current_price REAL NOT NULL DEFAULT 0,
-                unrealized_pnl REAL NOT NULL DEFAULT 0,
-                source_trader TEXT,
-                opened_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
-                updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
-                closed_at TEXT,
-                UNIQUE(market_id, outcome, side)
-            )
-            "#,
-        )
-        .execute(&self.pool)
-        .await?;
+        sqlx::query(&format!("CREATE TABLE IF NOT EXISTS positions ({})", POSITIONS_COLUMNS))
+            .execute(&mut *self.conn().await?)
+            .await?;
+
+        // Realized P&L columns added after the initial schema
+        self.add_column_if_missing("positions", "exit_price", "REAL").await?;
+        self.add_column_if_missing("positions", "realized_pnl", "REAL").await?;
+        self.add_column_if_missing("positions", "exit_reason", "TEXT").await?;
+        self.add_column_if_missing("positions", "settled_at", "TEXT").await?;
+        self.add_column_if_missing("positions", "peak_price", "REAL").await?;
+        self.add_column_if_missing("positions", "entries", "INTEGER NOT NULL DEFAULT 1").await?;
+        self.add_column_if_missing("positions", "costs", "REAL").await?;
+        self.drop_positions_unique_constraint().await?;
 
         // Copy trades
         sqlx::query(
@@ -222,7 +468,45 @@ impl Database {
             )
             "#,
         )
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
+        .await?;
+
+        // Copy intents deferred to a later tick, as JSON
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS pending_intents (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                intent TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+            )
+            "#,
+        )
+        .execute(&mut *self.conn().await?)
+        .await?;
+
+        // Resolved markets
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS resolved_markets (
+                condition_id TEXT PRIMARY KEY,
+                winning_outcome TEXT NOT NULL,
+                resolved_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&mut *self.conn().await?)
+        .await?;
+
+        // Market categories
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS market_categories (
+                condition_id TEXT PRIMARY KEY,
+                category TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&mut *self.conn().await?)
         .await?;
 
         // Equity curve
@@ -238,51 +522,129 @@ impl Database {
             )
             "#,
         )
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await?;
 
         // Indexes
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_seen_trades_trader ON seen_trades(trader_address)")
-            .execute(&self.pool)
+            .execute(&mut *self.conn().await?)
             .await?;
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_copy_trades_status ON copy_trades(status)")
-            .execute(&self.pool)
+            .execute(&mut *self.conn().await?)
             .await?;
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_positions_market ON positions(market_id)")
-            .execute(&self.pool)
+            .execute(&mut *self.conn().await?)
             .await?;
+        // Only one open position per market, outcome and side; closed ones are archived rows
+        sqlx::query(
+            r#"
+            CREATE UNIQUE INDEX IF NOT EXISTS idx_positions_open
+            ON positions(market_id, outcome, side) WHERE closed_at IS NULL
+            "#,
+        )
+        .execute(&mut *self.conn().await?)
+        .await?;
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_equity_curve_time ON equity_curve(timestamp)")
-            .execute(&self.pool)
+            .execute(&mut *self.conn().await?)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Add a column to an existing table if an older schema lacks it.
+    async fn add_column_if_missing(&self, table: &str, column: &str, decl: &str) -> Result<()> {
+        let columns: Vec<(String,)> =
+            sqlx::query_as(&format!("SELECT name FROM pragma_table_info('{}')", table))
+                .fetch_all(&mut *self.conn().await?)
+                .await?;
+
+        if !columns.iter().any(|(name,)| name == column) {
+            sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, decl))
+                .execute(&mut *self.conn().await?)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Rebuild a positions table created with a table-wide `UNIQUE(market_id, outcome, side)`,
+    /// which made a closed position block (or absorb) a new one in the same market.
+    async fn drop_positions_unique_constraint(&self) -> Result<()> {
+        let (schema,): (String,) =
+            sqlx::query_as("SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'positions'")
+                .fetch_one(&mut *self.conn().await?)
+                .await?;
+        if !schema.contains("UNIQUE(market_id, outcome, side)") {
+            return Ok(());
+        }
+
+        let columns: Vec<(String,)> = sqlx::query_as("SELECT name FROM pragma_table_info('positions')")
+            .fetch_all(&mut *self.conn().await?)
             .await?;
+        let columns = columns.into_iter().map(|(name,)| name).collect::<Vec<_>>().join(", ");
 
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(&format!("CREATE TABLE positions_new ({})", POSITIONS_COLUMNS))
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(&format!("INSERT INTO positions_new ({0}) SELECT {0} FROM positions", columns))
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DROP TABLE positions").execute(&mut *tx).await?;
+        sqlx::query("ALTER TABLE positions_new RENAME TO positions").execute(&mut *tx).await?;
+        tx.commit().await.context("Failed to migrate positions table")?;
+        Ok(())
+    }
+
+    /// Verify the database accepts writes (fails on read-only files or a locked database).
+    pub async fn check_writable(&self) -> Result<()> {
+        let (version,): (i64,) = sqlx::query_as("PRAGMA user_version")
+            .fetch_one(&mut *self.conn().await?)
+            .await?;
+        sqlx::query(&format!("PRAGMA user_version = {}", version))
+            .execute(&mut *self.conn().await?)
+            .await
+            .context("Database is not writable")?;
         Ok(())
     }
 
     // ==================== Bot State ====================
 
     /// Initialize or get bot state.
-    pub async fn init_bot_state(&self, portfolio_value: f64) -> Result<BotState> {
+    pub async fn init_bot_state(&self, portfolio_value: f64, poll_interval_secs: i64) -> Result<BotState> {
         sqlx::query(
             r#"
-            INSERT INTO bot_state (id, portfolio_value, is_running, started_at, updated_at)
-            VALUES (1, ?, 1, datetime('now'), datetime('now'))
+            INSERT INTO bot_state (id, portfolio_value, is_running, poll_interval_secs, heartbeat_at, started_at, updated_at)
+            VALUES (1, ?, 1, ?, datetime('now'), datetime('now'), datetime('now'))
             ON CONFLICT(id) DO UPDATE SET
                 portfolio_value = excluded.portfolio_value,
                 is_running = 1,
+                poll_interval_secs = excluded.poll_interval_secs,
+                heartbeat_at = excluded.heartbeat_at,
                 updated_at = datetime('now')
             "#,
         )
         .bind(portfolio_value)
-        .execute(&self.pool)
+        .bind(poll_interval_secs)
+        .execute(&mut *self.conn().await?)
         .await?;
 
         self.get_bot_state().await
     }
 
+    /// Record the start of live trading, unless an earlier live session already has.
+    pub async fn mark_live_started(&self) -> Result<BotState> {
+        sqlx::query("UPDATE bot_state SET live_started_at = COALESCE(live_started_at, datetime('now')) WHERE id = 1")
+            .execute(&mut *self.conn().await?)
+            .await?;
+
+        self.get_bot_state().await
+    }
+
     /// Get current bot state.
     pub async fn get_bot_state(&self) -> Result<BotState> {
         sqlx::query_as::<_, BotState>("SELECT * FROM bot_state WHERE id = 1")
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.conn().await?)
             .await
             .context("Bot state not initialized")
     }
@@ -293,6 +655,7 @@ impl Database {
         exposure: f64,
         total_pnl: f64,
         total_trades: i64,
+        peak_equity: f64,
     ) -> Result<()> {
         sqlx::query(
             r#"
@@ -300,6 +663,7 @@ impl Database {
                 current_exposure = ?,
                 total_pnl = ?,
                 total_trades = ?,
+                peak_equity = MAX(peak_equity, ?),
                 last_poll_at = datetime('now'),
                 updated_at = datetime('now')
             WHERE id = 1
@@ -308,16 +672,54 @@ impl Database {
         .bind(exposure)
         .bind(total_pnl)
         .bind(total_trades)
-        .execute(&self.pool)
+        .bind(peak_equity)
+        .execute(&mut *self.conn().await?)
         .await?;
 
         Ok(())
     }
 
+    /// Overwrite the drawdown high-water mark, which `update_bot_state` only ever raises.
+    pub async fn reset_peak_equity(&self, peak_equity: f64) -> Result<()> {
+        sqlx::query("UPDATE bot_state SET peak_equity = ?, updated_at = datetime('now') WHERE id = 1")
+            .bind(peak_equity)
+            .execute(&mut *self.conn().await?)
+            .await?;
+        Ok(())
+    }
+
+    /// Record that the bot process is alive.
+    ///
+    /// A heartbeat is never part of a batch: one arriving while a batch is open is
+    /// written as soon as the batch commits, checkpoints or rolls back, so a rolled-back
+    /// tick can't drop it and it never waits on the batch's write lock.
+    pub async fn record_heartbeat(&self) -> Result<()> {
+        if self.batch.lock().await.is_some() {
+            self.heartbeat_due.store(true, Ordering::SeqCst);
+            return Ok(());
+        }
+        self.write_heartbeat().await
+    }
+
+    /// Write a heartbeat held back by a batch, if any.
+    async fn flush_heartbeat(&self) -> Result<()> {
+        if self.heartbeat_due.swap(false, Ordering::SeqCst) {
+            self.write_heartbeat().await?;
+        }
+        Ok(())
+    }
+
+    async fn write_heartbeat(&self) -> Result<()> {
+        sqlx::query("UPDATE bot_state SET heartbeat_at = datetime('now') WHERE id = 1")
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
     /// Mark bot as stopped.
     pub async fn mark_bot_stopped(&self) -> Result<()> {
         sqlx::query("UPDATE bot_state SET is_running = 0, updated_at = datetime('now') WHERE id = 1")
-            .execute(&self.pool)
+            .execute(&mut *self.conn().await?)
             .await?;
         Ok(())
     }
@@ -345,7 +747,7 @@ impl Database {
         .bind(address)
         .bind(pseudonym)
         .bind(allocation_weight)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await?;
 
         Ok(())
@@ -355,7 +757,30 @@ impl Database {
     pub async fn get_tracked_addresses(&self) -> Result<Vec<String>> {
         let rows: Vec<(String,)> =
             sqlx::query_as("SELECT address FROM tracked_traders WHERE is_tracked = 1")
-                .fetch_all(&self.pool)
+                .fetch_all(&mut *self.conn().await?)
+                .await?;
+
+        Ok(rows.into_iter().map(|(a,)| a).collect())
+    }
+
+    /// Pause or resume copying a tracked trader. Returns false if the trader isn't tracked.
+    pub async fn set_trader_paused(&self, address: &str, paused: bool) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE tracked_traders SET is_paused = ?, updated_at = datetime('now') WHERE address = ? AND is_tracked = 1",
+        )
+        .bind(paused)
+        .bind(address)
+        .execute(&mut *self.conn().await?)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Addresses of tracked traders that are paused.
+    pub async fn get_paused_addresses(&self) -> Result<Vec<String>> {
+        let rows: Vec<(String,)> =
+            sqlx::query_as("SELECT address FROM tracked_traders WHERE is_tracked = 1 AND is_paused = 1")
+                .fetch_all(&mut *self.conn().await?)
                 .await?;
 
         Ok(rows.into_iter().map(|(a,)| a).collect())
@@ -367,7 +792,7 @@ impl Database {
             "UPDATE tracked_traders SET is_tracked = 0, updated_at = datetime('now') WHERE address = ?",
         )
         .bind(address)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await?;
 
         Ok(())
@@ -380,12 +805,93 @@ impl Database {
         )
         .bind(value)
         .bind(address)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Persist the newest trade seen from a trader (survives restarts).
+    pub async fn update_trader_watermark(
+        &self,
+        address: &str,
+        trade_id: &str,
+        trade_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE tracked_traders SET
+                last_seen_trade_id = ?,
+                last_seen_trade_at = ?,
+                updated_at = datetime('now')
+            WHERE address = ? AND COALESCE(last_seen_trade_at, 0) <= ?
+            "#,
+        )
+        .bind(trade_id)
+        .bind(trade_at.timestamp())
+        .bind(address)
+        .bind(trade_at.timestamp())
+        .execute(&mut *self.conn().await?)
         .await?;
 
         Ok(())
     }
 
+    /// Get the newest trade seen from a trader, if any.
+    pub async fn get_trader_watermark(&self, address: &str) -> Result<Option<(String, DateTime<Utc>)>> {
+        let row: Option<(Option<String>, Option<i64>)> = sqlx::query_as(
+            "SELECT last_seen_trade_id, last_seen_trade_at FROM tracked_traders WHERE address = ?",
+        )
+        .bind(address)
+        .fetch_optional(&mut *self.conn().await?)
+        .await?;
+
+        Ok(match row {
+            Some((Some(id), Some(ts))) => DateTime::from_timestamp(ts, 0).map(|at| (id, at)),
+            _ => None,
+        })
+    }
+
+    /// Persist when a trader last traded, for inactivity detection across restarts.
+    pub async fn update_trader_last_trade(&self, address: &str, trade_at: DateTime<Utc>) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE tracked_traders SET
+                last_trade_seen_at = ?,
+                updated_at = datetime('now')
+            WHERE address = ? AND COALESCE(last_trade_seen_at, 0) < ?
+            "#,
+        )
+        .bind(trade_at.timestamp())
+        .bind(address)
+        .bind(trade_at.timestamp())
+        .execute(&mut *self.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    /// When tracking of a trader started and when they last traded, as far as recorded.
+    pub async fn get_trader_activity(
+        &self,
+        address: &str,
+    ) -> Result<(Option<DateTime<Utc>>, Option<DateTime<Utc>>)> {
+        let row: Option<(Option<String>, Option<i64>)> = sqlx::query_as(
+            "SELECT tracking_since, last_trade_seen_at FROM tracked_traders WHERE address = ?",
+        )
+        .bind(address)
+        .fetch_optional(&mut *self.conn().await?)
+        .await?;
+
+        Ok(match row {
+            Some((since, last)) => (
+                since.as_deref().and_then(parse_timestamp),
+                last.and_then(|ts| DateTime::from_timestamp(ts, 0)),
+            ),
+            None => (None, None),
+        })
+    }
+
     // ==================== Seen Trades ====================
 
     /// Check if we've already seen a trade.
@@ -393,7 +899,7 @@ impl Database {
         let result: Option<(i64,)> =
             sqlx::query_as("SELECT 1 FROM seen_trades WHERE trade_id = ?")
                 .bind(trade_id)
-                .fetch_optional(&self.pool)
+                .fetch_optional(&mut *self.conn().await?)
                 .await?;
 
         Ok(result.is_some())
@@ -412,7 +918,7 @@ impl Database {
         .bind(trade_id)
         .bind(trader_address)
         .bind(market_id)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await?;
 
         Ok(())
@@ -423,7 +929,7 @@ impl Database {
         let (count,): (i64,) =
             sqlx::query_as("SELECT COUNT(*) FROM seen_trades WHERE trader_address = ?")
                 .bind(trader_address)
-                .fetch_one(&self.pool)
+                .fetch_one(&mut *self.conn().await?)
                 .await?;
 
         Ok(count)
@@ -432,6 +938,14 @@ impl Database {
     // ==================== Positions ====================
 
     /// Save or update a position.
+    ///
+    /// Only an open position is added to; once closed (or settled) a position is archived
+    /// and a later fill in the same market and outcome opens a new one.
+    ///
+    /// Adding to an open position keeps a size-weighted average entry:
+    /// `(old_entry * old_size + new_entry * new_size) / (old_size + new_size)`,
+    /// so the stored cost basis `entry * size` is the sum of both fills' costs, and
+    /// counts the add in `entries`.
     pub async fn save_position(
         &self,
         market_id: &str,
@@ -446,10 +960,11 @@ impl Database {
             r#"
             INSERT INTO positions (market_id, market_title, outcome, side, size, entry_price, source_trader)
             VALUES (?, ?, ?, ?, ?, ?, ?)
-            ON CONFLICT(market_id, outcome, side) DO UPDATE SET
+            ON CONFLICT(market_id, outcome, side) WHERE closed_at IS NULL DO UPDATE SET
                 size = positions.size + excluded.size,
                 entry_price = (positions.entry_price * positions.size + excluded.entry_price * excluded.size)
                              / (positions.size + excluded.size),
+                entries = positions.entries + 1,
                 updated_at = datetime('now')
             RETURNING id
             "#,
@@ -461,23 +976,49 @@ impl Database {
         .bind(size)
         .bind(entry_price)
         .bind(source_trader)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *self.conn().await?)
         .await?;
 
         Ok(sqlx::Row::get(&result, "id"))
     }
 
+    /// Overwrite an open position's size and entry price.
+    pub async fn set_position(
+        &self,
+        market_id: &str,
+        outcome: &str,
+        side: &str,
+        size: f64,
+        entry_price: f64,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE positions SET size = ?, entry_price = ?, updated_at = datetime('now')
+            WHERE market_id = ? AND outcome = ? AND side = ? AND closed_at IS NULL
+            "#,
+        )
+        .bind(size)
+        .bind(entry_price)
+        .bind(market_id)
+        .bind(outcome)
+        .bind(side)
+        .execute(&mut *self.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
     /// Get all open positions.
     pub async fn get_open_positions(&self) -> Result<Vec<StoredPosition>> {
         sqlx::query_as::<_, StoredPosition>(
             "SELECT * FROM positions WHERE closed_at IS NULL AND size > 0.0001",
         )
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.conn().await?)
         .await
         .context("Failed to fetch positions")
     }
 
-    /// Update position price and P&L.
+    /// Update position price and P&L, and the peak price a trailing stop measures from.
     pub async fn update_position_price(
         &self,
         market_id: &str,
@@ -489,44 +1030,251 @@ impl Database {
             UPDATE positions SET
                 current_price = ?,
                 unrealized_pnl = (? - entry_price) * size,
+                peak_price = CASE WHEN side = 'SELL'
+                    THEN MIN(COALESCE(peak_price, entry_price), ?)
+                    ELSE MAX(COALESCE(peak_price, entry_price), ?) END,
                 updated_at = datetime('now')
             WHERE market_id = ? AND outcome = ? AND closed_at IS NULL
             "#,
         )
         .bind(current_price)
         .bind(current_price)
+        .bind(current_price)
+        .bind(current_price)
         .bind(market_id)
         .bind(outcome)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await?;
 
         Ok(())
     }
 
-    /// Close a position.
-    pub async fn close_position(&self, market_id: &str, outcome: &str) -> Result<()> {
+    /// Last marked price of an open position, and when it was marked.
+    pub async fn last_price(&self, market_id: &str, outcome: &str) -> Result<Option<(f64, DateTime<Utc>)>> {
+        let row: Option<(f64, String)> = sqlx::query_as(
+            "SELECT current_price, updated_at FROM positions WHERE market_id = ? AND outcome = ? AND closed_at IS NULL",
+        )
+        .bind(market_id)
+        .bind(outcome)
+        .fetch_optional(&mut *self.conn().await?)
+        .await?;
+
+        Ok(row.and_then(|(price, at)| Some((price, parse_timestamp(&at)?))))
+    }
+
+    /// Close a position at its last marked price, recording realized P&L net of `costs`.
+    ///
+    /// Closing for [`SETTLEMENT_EXIT_REASON`] also marks the position settled.
+    pub async fn close_position(&self, market_id: &str, outcome: &str, costs: f64, reason: &str) -> Result<()> {
         sqlx::query(
-            "UPDATE positions SET closed_at = datetime('now'), updated_at = datetime('now') WHERE market_id = ? AND outcome = ?",
+            r#"
+            UPDATE positions SET
+                closed_at = datetime('now'),
+                exit_price = current_price,
+                realized_pnl = (CASE WHEN side = 'SELL' THEN entry_price - current_price
+                                     ELSE current_price - entry_price END) * size - ?,
+                costs = ?,
+                exit_reason = ?,
+                settled_at = CASE WHEN ? = ? THEN datetime('now') END,
+                updated_at = datetime('now')
+            WHERE market_id = ? AND outcome = ? AND closed_at IS NULL
+            "#,
+        )
+        .bind(costs)
+        .bind(costs)
+        .bind(reason)
+        .bind(reason)
+        .bind(SETTLEMENT_EXIT_REASON)
+        .bind(market_id)
+        .bind(outcome)
+        .execute(&mut *self.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Close a position the wallet no longer holds, booking no P&L since its exit price is unknown.
+    pub async fn mark_position_reconciled(&self, market_id: &str, outcome: &str, side: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE positions SET
+                closed_at = datetime('now'),
+                realized_pnl = 0,
+                costs = 0,
+                exit_reason = 'reconciled',
+                updated_at = datetime('now')
+            WHERE market_id = ? AND outcome = ? AND side = ? AND closed_at IS NULL
+            "#,
         )
         .bind(market_id)
         .bind(outcome)
-        .execute(&self.pool)
+        .bind(side)
+        .execute(&mut *self.conn().await?)
         .await?;
 
         Ok(())
     }
 
-    /// Get total exposure across all positions.
+    /// Total realized P&L booked by closed positions.
+    pub async fn get_total_realized_pnl(&self) -> Result<f64> {
+        let (total,): (f64,) = sqlx::query_as(
+            "SELECT COALESCE(SUM(realized_pnl), 0.0) FROM positions WHERE closed_at IS NOT NULL",
+        )
+        .fetch_one(&mut *self.conn().await?)
+        .await?;
+        Ok(total)
+    }
+
+    /// Reconstruct realized P&L for closed positions recorded without one.
+    ///
+    /// Positions settled at resolution are priced at the payout (1 for the winning
+    /// outcome, 0 otherwise); others at the recorded exit price, then the payout if their
+    /// market has since resolved, then the last marked price. Sells gain as the price falls.
+    /// Returns (backfilled, skipped) where skipped positions have no usable price.
+    pub async fn backfill_realized_pnl(&self) -> Result<(u64, u64)> {
+        type Row = (i64, String, String, f64, f64, Option<f64>, f64, Option<String>, Option<String>);
+        let rows: Vec<Row> = sqlx::query_as(
+            r#"
+            SELECT p.id, p.outcome, p.side, p.size, p.entry_price, p.exit_price, p.current_price,
+                   p.settled_at, r.winning_outcome
+            FROM positions p LEFT JOIN resolved_markets r ON r.condition_id = p.market_id
+            WHERE p.closed_at IS NOT NULL AND p.realized_pnl IS NULL
+            "#,
+        )
+        .fetch_all(&mut *self.conn().await?)
+        .await?;
+
+        let (mut updated, mut skipped) = (0, 0);
+        for (id, outcome, side, size, entry, exit, current, settled_at, winner) in rows {
+            let payout = winner.map(|w| if w.eq_ignore_ascii_case(&outcome) { 1.0 } else { 0.0 });
+            let price = match (settled_at.is_some(), payout, exit) {
+                (true, Some(payout), _) => Some(payout),
+                (_, _, Some(exit)) => Some(exit),
+                (_, Some(payout), None) => Some(payout),
+                // Settlement marked the payout before closing, even a payout of 0
+                (true, None, None) => Some(current),
+                (false, None, None) => Some(current).filter(|p| *p > 0.0),
+            };
+            let Some(price) = price else {
+                skipped += 1;
+                continue;
+            };
+
+            sqlx::query(
+                r#"
+                UPDATE positions SET
+                    exit_price = ?,
+                    realized_pnl = ?,
+                    costs = COALESCE(costs, 0),
+                    updated_at = datetime('now')
+                WHERE id = ?
+                "#,
+            )
+            .bind(price)
+            .bind(position_pnl(&side, entry, price, size))
+            .bind(id)
+            .execute(&mut *self.conn().await?)
+            .await?;
+            updated += 1;
+        }
+
+        Ok((updated, skipped))
+    }
+
+    /// Get closed positions, most recently closed first.
+    pub async fn get_closed_positions(&self) -> Result<Vec<StoredPosition>> {
+        sqlx::query_as::<_, StoredPosition>(
+            "SELECT * FROM positions WHERE closed_at IS NOT NULL ORDER BY closed_at DESC",
+        )
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .context("Failed to fetch closed positions")
+    }
+
+    /// Get total exposure across all positions (gross, regardless of direction).
     pub async fn get_total_exposure(&self) -> Result<f64> {
+        self.get_gross_exposure().await
+    }
+
+    /// Get gross exposure: long plus short notional.
+    pub async fn get_gross_exposure(&self) -> Result<f64> {
+        let (exposure,): (f64,) = sqlx::query_as(
+            "SELECT COALESCE(SUM(ABS(size * current_price)), 0) FROM positions WHERE closed_at IS NULL",
+        )
+        .fetch_one(&mut *self.conn().await?)
+        .await?;
+
+        Ok(exposure)
+    }
+
+    /// Get net directional exposure: long notional minus short notional.
+    pub async fn get_net_exposure(&self) -> Result<f64> {
         let (exposure,): (f64,) = sqlx::query_as(
-            "SELECT COALESCE(SUM(size * current_price), 0) FROM positions WHERE closed_at IS NULL",
+            r#"
+            SELECT COALESCE(SUM(CASE WHEN side = 'SELL' THEN -1 ELSE 1 END * size * current_price), 0)
+            FROM positions WHERE closed_at IS NULL
+            "#,
         )
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *self.conn().await?)
         .await?;
 
         Ok(exposure)
     }
 
+    // ==================== Resolved Markets ====================
+
+    /// Record a market's resolution; the first one observed is kept.
+    pub async fn save_resolved_market(
+        &self,
+        condition_id: &str,
+        winning_outcome: &str,
+        resolved_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT OR IGNORE INTO resolved_markets (condition_id, winning_outcome, resolved_at) VALUES (?, ?, ?)",
+        )
+        .bind(condition_id)
+        .bind(winning_outcome)
+        .bind(resolved_at.format("%Y-%m-%d %H:%M:%S").to_string())
+        .execute(&mut *self.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get a market's cached resolution, if it has been observed.
+    pub async fn get_resolved_market(&self, condition_id: &str) -> Result<Option<ResolvedMarket>> {
+        let market = sqlx::query_as("SELECT * FROM resolved_markets WHERE condition_id = ?")
+            .bind(condition_id)
+            .fetch_optional(&mut *self.conn().await?)
+            .await?;
+
+        Ok(market)
+    }
+
+    // ==================== Market Categories ====================
+
+    /// Cache a market's category.
+    pub async fn save_market_category(&self, condition_id: &str, category: &str) -> Result<()> {
+        sqlx::query("INSERT OR REPLACE INTO market_categories (condition_id, category) VALUES (?, ?)")
+            .bind(condition_id)
+            .bind(category)
+            .execute(&mut *self.conn().await?)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get a market's cached category, if it has been fetched.
+    pub async fn get_market_category(&self, condition_id: &str) -> Result<Option<String>> {
+        let row: Option<(String,)> = sqlx::query_as("SELECT category FROM market_categories WHERE condition_id = ?")
+            .bind(condition_id)
+            .fetch_optional(&mut *self.conn().await?)
+            .await?;
+
+        Ok(row.map(|(category,)| category))
+    }
+
     // ==================== Copy Trades ====================
 
     /// Save a new copy trade.
@@ -548,7 +1296,7 @@ impl Database {
             INSERT INTO copy_trades (
                 id, source_trader, source_trade_id, market_id, market_title,
                 side, outcome, source_size, source_price, our_size, status
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'pending')
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(id)
@@ -561,7 +1309,8 @@ impl Database {
         .bind(source_size)
         .bind(source_price)
         .bind(our_size)
-        .execute(&self.pool)
+        .bind(CopyTradeStatus::Pending.as_str())
+        .execute(&mut *self.conn().await?)
         .await?;
 
         Ok(())
@@ -571,18 +1320,12 @@ impl Database {
     pub async fn update_copy_trade_status(
         &self,
         id: &str,
-        status: &str,
+        status: CopyTradeStatus,
         order_id: Option<&str>,
         our_price: Option<f64>,
         tx_hash: Option<&str>,
         error: Option<&str>,
     ) -> Result<()> {
-        let executed_at = if status == "executed" {
-            Some("datetime('now')")
-        } else {
-            None
-        };
-
         sqlx::query(
             r#"
             UPDATE copy_trades SET
@@ -591,18 +1334,18 @@ impl Database {
                 our_price = COALESCE(?, our_price),
                 tx_hash = COALESCE(?, tx_hash),
                 error_message = ?,
-                executed_at = CASE WHEN ? = 'executed' THEN datetime('now') ELSE executed_at END
+                executed_at = CASE WHEN ? THEN datetime('now') ELSE executed_at END
             WHERE id = ?
             "#,
         )
-        .bind(status)
+        .bind(status.as_str())
         .bind(order_id)
         .bind(our_price)
         .bind(tx_hash)
         .bind(error)
-        .bind(status)
+        .bind(status == CopyTradeStatus::Executed)
         .bind(id)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await?;
 
         Ok(())
@@ -610,29 +1353,66 @@ impl Database {
 
     /// Get pending copy trades.
     pub async fn get_pending_copy_trades(&self) -> Result<Vec<StoredCopyTrade>> {
+        self.get_copy_trades_with_status(CopyTradeStatus::Pending).await
+    }
+
+    /// Get copy trades with the given status, oldest first.
+    pub async fn get_copy_trades_with_status(&self, status: CopyTradeStatus) -> Result<Vec<StoredCopyTrade>> {
         sqlx::query_as::<_, StoredCopyTrade>(
-            "SELECT * FROM copy_trades WHERE status = 'pending' ORDER BY created_at",
+            "SELECT * FROM copy_trades WHERE status = ? ORDER BY created_at",
         )
-        .fetch_all(&self.pool)
+        .bind(status.as_str())
+        .fetch_all(&mut *self.conn().await?)
         .await
-        .context("Failed to fetch pending trades")
+        .with_context(|| format!("Failed to fetch {} trades", status))
+    }
+
+    /// Defer a serialized copy intent to a later tick.
+    pub async fn push_pending_intent(&self, intent: &str) -> Result<()> {
+        sqlx::query("INSERT INTO pending_intents (intent) VALUES (?)")
+            .bind(intent)
+            .execute(&mut *self.conn().await?)
+            .await?;
+        Ok(())
+    }
+
+    /// Remove and return all deferred copy intents, oldest first.
+    pub async fn take_pending_intents(&self) -> Result<Vec<String>> {
+        let rows: Vec<(i64, String)> = sqlx::query_as("SELECT id, intent FROM pending_intents ORDER BY id")
+            .fetch_all(&mut *self.conn().await?)
+            .await?;
+        if let Some((last, _)) = rows.last() {
+            sqlx::query("DELETE FROM pending_intents WHERE id <= ?")
+                .bind(last)
+                .execute(&mut *self.conn().await?)
+                .await?;
+        }
+        Ok(rows.into_iter().map(|(_, intent)| intent).collect())
+    }
+
+    /// Number of deferred copy intents.
+    pub async fn count_pending_intents(&self) -> Result<i64> {
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM pending_intents")
+            .fetch_one(&mut *self.conn().await?)
+            .await?;
+        Ok(count)
     }
 
     /// Get copy trade statistics.
     pub async fn get_copy_trade_stats(&self) -> Result<(i64, i64, i64)> {
         let (total,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM copy_trades")
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.conn().await?)
             .await?;
 
-        let (executed,): (i64,) =
-            sqlx::query_as("SELECT COUNT(*) FROM copy_trades WHERE status = 'executed'")
-                .fetch_one(&self.pool)
-                .await?;
+        let (executed,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM copy_trades WHERE status = ?")
+            .bind(CopyTradeStatus::Executed.as_str())
+            .fetch_one(&mut *self.conn().await?)
+            .await?;
 
-        let (failed,): (i64,) =
-            sqlx::query_as("SELECT COUNT(*) FROM copy_trades WHERE status = 'failed'")
-                .fetch_one(&self.pool)
-                .await?;
+        let (failed,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM copy_trades WHERE status = ?")
+            .bind(CopyTradeStatus::Failed.as_str())
+            .fetch_one(&mut *self.conn().await?)
+            .await?;
 
         Ok((total, executed, failed))
     }
@@ -657,7 +1437,7 @@ impl Database {
         .bind(exposure)
         .bind(unrealized_pnl)
         .bind(realized_pnl)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await?;
 
         Ok(())
@@ -669,7 +1449,7 @@ impl Database {
             "SELECT * FROM equity_curve ORDER BY timestamp DESC LIMIT ?",
         )
         .bind(limit)
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.conn().await?)
         .await
         .context("Failed to fetch equity curve")
     }
@@ -706,3 +1486,193 @@ impl Database {
         &self.pool
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_offsetting_positions_have_low_net_but_high_gross_exposure() {
+        let db = Database::new(&crate::testing::temp_database_url()).await.unwrap();
+
+        db.save_position("0xm1", "", "Yes", "BUY", 200.0, 0.50, None).await.unwrap();
+        db.save_position("0xm1", "", "Yes", "SELL", 180.0, 0.50, None).await.unwrap();
+        db.update_position_price("0xm1", "Yes", 0.50).await.unwrap();
+
+        let gross = db.get_gross_exposure().await.unwrap();
+        let net = db.get_net_exposure().await.unwrap();
+        assert!((gross - 190.0).abs() < 1e-9);
+        assert!((net - 10.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_batched_writes_commit_or_roll_back_together() {
+        let db = Database::new(&crate::testing::temp_database_url()).await.unwrap();
+        db.init_bot_state(1000.0, 30).await.unwrap();
+
+        // A tick that fails midway leaves nothing behind
+        db.begin_batch().await.unwrap();
+        db.save_position("0xm1", "", "Yes", "BUY", 10.0, 0.50, None).await.unwrap();
+        db.record_equity_point(1005.0, 5.0, 0.0, 0.0).await.unwrap();
+        db.update_bot_state(5.0, 5.0, 1, 1005.0).await.unwrap();
+        assert_eq!(db.get_open_positions().await.unwrap().len(), 1, "batch sees its own writes");
+        db.rollback_batch().await.unwrap();
+
+        assert!(db.get_open_positions().await.unwrap().is_empty());
+        assert_eq!(db.get_bot_state().await.unwrap().total_trades, 0);
+        let (points,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM equity_curve")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(points, 0);
+
+        // A successful one lands all at once
+        db.begin_batch().await.unwrap();
+        assert!(db.begin_batch().await.is_err());
+        db.save_position("0xm1", "", "Yes", "BUY", 10.0, 0.50, None).await.unwrap();
+        db.update_bot_state(5.0, 5.0, 1, 1005.0).await.unwrap();
+        db.commit_batch().await.unwrap();
+
+        assert_eq!(db.get_open_positions().await.unwrap().len(), 1);
+        assert_eq!(db.get_bot_state().await.unwrap().total_trades, 1);
+
+        // Writes before a checkpoint survive a rollback of the rest
+        db.begin_batch().await.unwrap();
+        db.save_position("0xm2", "", "Yes", "BUY", 10.0, 0.50, None).await.unwrap();
+        db.checkpoint_batch().await.unwrap();
+        db.save_position("0xm3", "", "Yes", "BUY", 10.0, 0.50, None).await.unwrap();
+        db.rollback_batch().await.unwrap();
+
+        let markets: Vec<_> = db.get_open_positions().await.unwrap().into_iter().map(|p| p.market_id).collect();
+        assert_eq!(markets.len(), 2);
+        assert!(markets.contains(&"0xm2".to_string()));
+        assert!(!markets.contains(&"0xm3".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_during_batch_lands_outside_it() {
+        let db = Database::new(&crate::testing::temp_database_url()).await.unwrap();
+        db.init_bot_state(1000.0, 30).await.unwrap();
+        sqlx::query("UPDATE bot_state SET heartbeat_at = NULL").execute(db.pool()).await.unwrap();
+
+        db.begin_batch().await.unwrap();
+        db.save_position("0xm1", "", "Yes", "BUY", 10.0, 0.50, None).await.unwrap();
+        db.record_heartbeat().await.unwrap();
+        db.rollback_batch().await.unwrap();
+
+        // The tick's writes are gone but the heartbeat survives
+        assert!(db.get_open_positions().await.unwrap().is_empty());
+        assert!(db.get_bot_state().await.unwrap().heartbeat_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_new_position_opens_in_market_with_settled_position() {
+        let db = Database::new(&crate::testing::temp_database_url()).await.unwrap();
+        assert_eq!(crate::trading::ExitReason::MarketResolution.as_str(), SETTLEMENT_EXIT_REASON);
+
+        db.save_position("0xm1", "", "Yes", "BUY", 100.0, 0.40, None).await.unwrap();
+        db.update_position_price("0xm1", "Yes", 1.0).await.unwrap();
+        db.close_position("0xm1", "Yes", 0.0, SETTLEMENT_EXIT_REASON).await.unwrap();
+
+        // Relisted market: the same key opens a fresh position instead of reviving the settled one
+        db.save_position("0xm1", "", "Yes", "BUY", 20.0, 0.55, None).await.unwrap();
+        db.save_position("0xm1", "", "Yes", "BUY", 20.0, 0.45, None).await.unwrap();
+
+        let open = db.get_open_positions().await.unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].size, 40.0);
+        assert!((open[0].entry_price - 0.50).abs() < 1e-9);
+        assert!(open[0].exit_reason.is_none());
+
+        let closed = db.get_closed_positions().await.unwrap();
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].size, 100.0);
+        assert_eq!(closed[0].exit_reason.as_deref(), Some(SETTLEMENT_EXIT_REASON));
+        assert_ne!(closed[0].id, open[0].id);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_realized_pnl_for_legacy_closed_position() {
+        let db = Database::new(&crate::testing::temp_database_url()).await.unwrap();
+
+        db.save_position("0xm1", "", "Yes", "BUY", 100.0, 0.40, None).await.unwrap();
+        db.save_position("0xm2", "", "Yes", "BUY", 50.0, 0.30, None).await.unwrap();
+        db.update_position_price("0xm1", "Yes", 0.65).await.unwrap();
+
+        // Simulate rows closed by an older version: no exit price or realized P&L
+        sqlx::query("UPDATE positions SET closed_at = datetime('now')")
+            .execute(db.pool())
+            .await
+            .unwrap();
+
+        let (backfilled, skipped) = db.backfill_realized_pnl().await.unwrap();
+        assert_eq!((backfilled, skipped), (1, 1));
+
+        let closed = db.get_closed_positions().await.unwrap();
+        let m1 = closed.iter().find(|p| p.market_id == "0xm1").unwrap();
+        assert_eq!(m1.exit_price, Some(0.65));
+        assert!((m1.realized_pnl.unwrap() - 25.0).abs() < 1e-9);
+
+        // Running again is a no-op
+        assert_eq!(db.backfill_realized_pnl().await.unwrap(), (0, 1));
+    }
+
+    #[tokio::test]
+    async fn test_backfill_prices_resolved_losers_and_signs_sells() {
+        let db = Database::new(&crate::testing::temp_database_url()).await.unwrap();
+
+        // A buy of the losing outcome, never marked; a short marked down to 0.50
+        db.save_position("0xlost", "", "Yes", "BUY", 100.0, 0.40, None).await.unwrap();
+        db.save_position("0xshort", "", "Yes", "SELL", 100.0, 0.70, None).await.unwrap();
+        db.update_position_price("0xshort", "Yes", 0.50).await.unwrap();
+        db.save_resolved_market("0xlost", "No", Utc::now()).await.unwrap();
+        sqlx::query("UPDATE positions SET closed_at = datetime('now')")
+            .execute(db.pool())
+            .await
+            .unwrap();
+
+        assert_eq!(db.backfill_realized_pnl().await.unwrap(), (2, 0));
+
+        let closed = db.get_closed_positions().await.unwrap();
+        let lost = closed.iter().find(|p| p.market_id == "0xlost").unwrap();
+        assert_eq!(lost.exit_price, Some(0.0));
+        assert!((lost.realized_pnl.unwrap() + 40.0).abs() < 1e-9);
+        let short = closed.iter().find(|p| p.market_id == "0xshort").unwrap();
+        assert!((short.realized_pnl.unwrap() - 20.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_old_heartbeat_while_running_is_stale() {
+        let db = Database::new(&crate::testing::temp_database_url()).await.unwrap();
+        db.init_bot_state(1000.0, 30).await.unwrap();
+        db.record_heartbeat().await.unwrap();
+
+        let now = Utc::now();
+        let state = db.get_bot_state().await.unwrap();
+        assert!(!state.is_stale(now));
+        assert_eq!(state.run_state(now), "Running");
+
+        // Killed without a graceful shutdown ten minutes ago: still flagged running
+        sqlx::query("UPDATE bot_state SET heartbeat_at = datetime('now', '-10 minutes')")
+            .execute(db.pool())
+            .await
+            .unwrap();
+        let state = db.get_bot_state().await.unwrap();
+        assert!(state.is_running);
+        assert!(state.is_stale(now));
+        assert!(state.run_state(now).starts_with("Stale"));
+
+        db.mark_bot_stopped().await.unwrap();
+        assert_eq!(db.get_bot_state().await.unwrap().run_state(now), "Stopped");
+    }
+
+    #[test]
+    fn test_copy_trade_status_round_trips_with_failed_fallback() {
+        for status in CopyTradeStatus::ALL {
+            assert_eq!(status.as_str().parse::<CopyTradeStatus>(), Ok(status));
+        }
+        assert_eq!(" Executed ".parse::<CopyTradeStatus>(), Ok(CopyTradeStatus::Executed));
+        assert_eq!("settled".parse::<CopyTradeStatus>(), Ok(CopyTradeStatus::Failed));
+        assert_eq!("".parse::<CopyTradeStatus>(), Ok(CopyTradeStatus::Failed));
+    }
+}
@@ -0,0 +1,170 @@
+//! Number formatting for money, prices and percentages.
+//!
+//! Values are rounded as decimals with one configured strategy, so the same
+//! amount prints the same way whether it started as an `f64` or a `Decimal`.
+
+use std::sync::OnceLock;
+
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+
+use crate::metrics::MAX_PROFIT_FACTOR;
+
+/// How ties are rounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rounding {
+    /// Ties away from zero (1.005 -> 1.01)
+    #[default]
+    HalfUp,
+    /// Ties to the nearest even digit (1.005 -> 1.00)
+    Bankers,
+}
+
+impl std::fmt::Display for Rounding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::HalfUp => "half_up",
+            Self::Bankers => "bankers",
+        })
+    }
+}
+
+impl Rounding {
+    fn strategy(self) -> RoundingStrategy {
+        match self {
+            Self::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+            Self::Bankers => RoundingStrategy::MidpointNearestEven,
+        }
+    }
+}
+
+/// Decimal places and rounding for printed numbers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DisplayConfig {
+    /// Decimal places for dollar amounts
+    pub money_precision: u32,
+
+    /// Decimal places for share prices
+    pub price_precision: u32,
+
+    /// Decimal places for percentages
+    pub percent_precision: u32,
+
+    pub rounding: Rounding,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            money_precision: 2,
+            price_precision: 3,
+            percent_precision: 2,
+            rounding: Rounding::HalfUp,
+        }
+    }
+}
+
+/// Largest precision accepted in config.
+pub const MAX_PRECISION: u32 = 10;
+
+impl DisplayConfig {
+    /// Dollar amount, without the `$`.
+    pub fn money(&self, value: impl Amount) -> String {
+        self.round(value.to_decimal(), self.money_precision)
+    }
+
+    /// Share price.
+    pub fn price(&self, value: impl Amount) -> String {
+        self.round(value.to_decimal(), self.price_precision)
+    }
+
+    /// Fraction shown as a percentage, without the `%` (0.1234 -> "12.34").
+    pub fn pct(&self, fraction: impl Amount) -> String {
+        self.round(fraction.to_decimal() * Decimal::ONE_HUNDRED, self.percent_precision)
+    }
+
+    fn round(&self, value: Decimal, dp: u32) -> String {
+        let rounded = value.round_dp_with_strategy(dp, self.rounding.strategy());
+        format!("{:.*}", dp as usize, rounded)
+    }
+}
+
+/// A number that can be formatted: `Decimal` or `f64`.
+pub trait Amount {
+    fn to_decimal(self) -> Decimal;
+}
+
+impl Amount for Decimal {
+    fn to_decimal(self) -> Decimal {
+        self
+    }
+}
+
+impl Amount for f64 {
+    /// Uses the shortest decimal that round-trips, so 1.005 is 1.005 and not 1.00499...
+    fn to_decimal(self) -> Decimal {
+        Decimal::try_from(self).unwrap_or_default()
+    }
+}
+
+static CONFIG: OnceLock<DisplayConfig> = OnceLock::new();
+
+/// Set the process-wide formatting, once at startup.
+pub fn configure(config: DisplayConfig) {
+    let _ = CONFIG.set(config);
+}
+
+fn config() -> &'static DisplayConfig {
+    CONFIG.get_or_init(DisplayConfig::default)
+}
+
+/// Dollar amount with the configured formatting.
+pub fn money(value: impl Amount) -> String {
+    config().money(value)
+}
+
+/// Share price with the configured formatting.
+pub fn price(value: impl Amount) -> String {
+    config().price(value)
+}
+
+/// Fraction as a percentage with the configured formatting.
+pub fn pct(fraction: impl Amount) -> String {
+    config().pct(fraction)
+}
+
+/// Profit factor to two places, or `>100` at the cap for records with no losses.
+pub fn profit_factor(value: f64) -> String {
+    if value >= MAX_PROFIT_FACTOR {
+        format!(">{}", MAX_PROFIT_FACTOR)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_rounding_is_consistent_across_types() {
+        let half_up = DisplayConfig::default();
+        assert_eq!(half_up.money(dec!(1.005)), "1.01");
+        assert_eq!(half_up.money(1.005_f64), "1.01");
+        assert_eq!(half_up.money(dec!(-1.005)), "-1.01");
+
+        let bankers = DisplayConfig { rounding: Rounding::Bankers, ..DisplayConfig::default() };
+        assert_eq!(bankers.money(dec!(1.005)), "1.00");
+        assert_eq!(bankers.money(1.005_f64), "1.00");
+        assert_eq!(bankers.money(dec!(1.015)), "1.02");
+
+        let precise = DisplayConfig { price_precision: 4, ..DisplayConfig::default() };
+        assert_eq!(precise.price(dec!(0.5)), "0.5000");
+        assert_eq!(precise.price(0.123456_f64), "0.1235");
+        assert_eq!(half_up.price(dec!(0.5)), "0.500");
+        assert_eq!(half_up.pct(dec!(0.12345)), "12.35");
+    }
+}
@@ -0,0 +1,144 @@
+//! Pre-flight health checks: credentials, database and API reachability.
+
+use rust_decimal::Decimal;
+
+use crate::api::{ClobClient, DataClient};
+use crate::db::Database;
+use crate::display;
+
+/// Outcome of a single health check.
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: &'static str,
+    /// Whether a failure should block live trading
+    pub critical: bool,
+    pub passed: bool,
+    pub detail: String,
+}
+
+impl Check {
+    fn from_result(name: &'static str, critical: bool, result: anyhow::Result<String>) -> Self {
+        match result {
+            Ok(detail) => Self { name, critical, passed: true, detail },
+            Err(e) => Self { name, critical, passed: false, detail: e.to_string() },
+        }
+    }
+}
+
+impl std::fmt::Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match (self.passed, self.critical) {
+            (true, _) => "PASS",
+            (false, true) => "FAIL",
+            (false, false) => "WARN",
+        };
+        write!(f, "[{}] {}: {}", status, self.name, self.detail)
+    }
+}
+
+/// Run all checks. `clob` is `None` when trading credentials aren't configured;
+/// `max_exposure` is the most USDC live trading may commit, which the exchange
+/// allowance must cover.
+pub async fn run_checks(
+    db: &Database,
+    data_client: &DataClient,
+    clob: Option<&ClobClient>,
+    max_exposure: Decimal,
+) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    checks.push(Check::from_result(
+        "database",
+        true,
+        db.check_writable().await.map(|_| "writable".to_string()),
+    ));
+
+    checks.push(Check::from_result(
+        "data api",
+        true,
+        data_client
+            .get_leaderboard(None, None, None, Some(1), None)
+            .await
+            .map(|_| "reachable".to_string()),
+    ));
+
+    // An authenticated balance call validates both the credentials and CLOB reachability
+    let credentials = match clob {
+        Some(clob) => clob
+            .get_collateral_balance()
+            .await
+            .map(|balance| format!("{} authenticated, ${} USDC", clob.address(), display::money(balance))),
+        None => Err(anyhow::anyhow!("CLOB credentials not configured")),
+    };
+    checks.push(Check::from_result("credentials", true, credentials));
+
+    if let Some(clob) = clob {
+        let allowance = clob.check_allowance().await;
+        // Without a reported allowance there's nothing to judge, so only warn
+        let critical = !matches!(allowance, Ok(None));
+        let allowance = allowance.and_then(|allowance| {
+            let Some(allowance) = allowance else {
+                anyhow::bail!(
+                    "not reported by the CLOB; make sure the Polymarket exchange is approved to spend your USDC"
+                );
+            };
+            if allowance.is_zero() || allowance < max_exposure {
+                anyhow::bail!(
+                    "exchange may spend ${} USDC, below the ${} max exposure; approve the Polymarket \
+                     exchange to spend your USDC (e.g. by enabling trading on polymarket.com)",
+                    display::money(allowance),
+                    display::money(max_exposure)
+                );
+            }
+            Ok(if allowance == Decimal::MAX {
+                "unlimited".to_string()
+            } else {
+                format!("${} USDC approved", display::money(allowance))
+            })
+        });
+        checks.push(Check::from_result("allowance", critical, allowance));
+    }
+
+    checks
+}
+
+/// Critical checks that failed.
+pub fn critical_failures(checks: &[Check]) -> Vec<&Check> {
+    checks.iter().filter(|c| c.critical && !c.passed).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    use crate::testing::MockPolymarket;
+
+    #[tokio::test]
+    async fn test_insufficient_allowance_fails_check() {
+        let mock = MockPolymarket::start().await;
+        mock.mount_balance_allowance(1000.0, Some(50.0)).await;
+        let db = Database::new(&crate::testing::temp_database_url()).await.unwrap();
+
+        let checks = run_checks(&db, &mock.data_client(), Some(&mock.clob_client()), dec!(500)).await;
+        let allowance = checks.iter().find(|c| c.name == "allowance").unwrap();
+        assert!(!allowance.passed);
+        assert!(allowance.detail.contains("$50.00"));
+        assert!(critical_failures(&checks).iter().any(|c| c.name == "allowance"));
+
+        // An unlimited approval covers any exposure
+        mock.reset().await;
+        mock.mount_balance(1000.0).await;
+        let checks = run_checks(&db, &mock.data_client(), Some(&mock.clob_client()), dec!(500)).await;
+        assert!(checks.iter().find(|c| c.name == "allowance").unwrap().passed);
+
+        // An unreported allowance is unknown: a warning, not a blocking failure
+        mock.reset().await;
+        mock.mount_balance_without_allowance(1000.0).await;
+        let checks = run_checks(&db, &mock.data_client(), Some(&mock.clob_client()), dec!(500)).await;
+        let allowance = checks.iter().find(|c| c.name == "allowance").unwrap();
+        assert!(!allowance.passed && !allowance.critical);
+        assert!(checks.iter().find(|c| c.name == "credentials").unwrap().passed);
+        assert!(!critical_failures(&checks).iter().any(|c| c.name == "allowance"));
+    }
+}
@@ -0,0 +1,156 @@
+//! Dry-run summary: what the recorded simulated copy trades would have done live.
+
+use std::collections::BTreeMap;
+
+use rust_decimal::Decimal;
+
+use crate::db::StoredCopyTrade;
+use crate::display;
+use crate::output::Report;
+
+/// Net position a market outcome would have built up.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarketExposure {
+    pub trades: usize,
+    pub net_shares: Decimal,
+    pub net_exposure: Decimal,
+}
+
+/// Aggregate of simulated copy trades, priced as if they had been placed live.
+#[derive(Debug, Clone, Default)]
+pub struct DryRunSummary {
+    pub trades: usize,
+    pub buy_notional: Decimal,
+    pub sell_notional: Decimal,
+    pub estimated_fees: Decimal,
+    /// Keyed by (market, outcome)
+    pub markets: BTreeMap<(String, String), MarketExposure>,
+    pub first_at: Option<String>,
+    pub last_at: Option<String>,
+}
+
+impl DryRunSummary {
+    /// Aggregate `trades` (oldest first), charging `order_cost` per order plus `fee_rate` of notional.
+    pub fn from_trades(trades: &[StoredCopyTrade], order_cost: Decimal, fee_rate: Decimal) -> Self {
+        let mut summary = Self {
+            trades: trades.len(),
+            first_at: trades.first().map(|t| t.created_at.clone()),
+            last_at: trades.last().map(|t| t.created_at.clone()),
+            ..Self::default()
+        };
+
+        for trade in trades {
+            let shares = Decimal::try_from(trade.our_size).unwrap_or_default();
+            let price = Decimal::try_from(trade.our_price.unwrap_or(trade.source_price)).unwrap_or_default();
+            let notional = shares * price;
+            let sell = trade.side.eq_ignore_ascii_case("sell");
+
+            if sell {
+                summary.sell_notional += notional;
+            } else {
+                summary.buy_notional += notional;
+            }
+            summary.estimated_fees += order_cost + notional * fee_rate;
+
+            let market = summary
+                .markets
+                .entry((trade.market_id.clone(), trade.outcome.clone()))
+                .or_default();
+            market.trades += 1;
+            if sell {
+                market.net_shares -= shares;
+                market.net_exposure -= notional;
+            } else {
+                market.net_shares += shares;
+                market.net_exposure += notional;
+            }
+        }
+
+        summary
+    }
+
+    /// Capital that would be tied up: bought minus sold notional.
+    pub fn net_exposure(&self) -> Decimal {
+        self.buy_notional - self.sell_notional
+    }
+
+    /// Summary and per-market exposure as a report.
+    pub fn to_report(&self) -> Report {
+        let mut report = Report::new("Dry-Run Summary");
+        report
+            .field("Period", "First Trade", self.first_at.as_deref().unwrap_or("-"))
+            .field("Period", "Last Trade", self.last_at.as_deref().unwrap_or("-"))
+            .field("Totals", "Simulated Trades", self.trades)
+            .field("Totals", "Markets", self.markets.len())
+            .field("Totals", "Bought ($)", display::money(self.buy_notional))
+            .field("Totals", "Sold ($)", display::money(self.sell_notional))
+            .field("Totals", "Gross Notional ($)", display::money(self.buy_notional + self.sell_notional))
+            .field("Totals", "Net Exposure ($)", display::money(self.net_exposure()))
+            .field("Totals", "Estimated Fees ($)", display::money(self.estimated_fees));
+
+        let mut markets: Vec<_> = self.markets.iter().collect();
+        markets.sort_by_key(|(_, m)| std::cmp::Reverse(m.net_exposure.abs()));
+        let rows = markets
+            .into_iter()
+            .map(|((market, outcome), exposure)| {
+                let avg_price = if exposure.net_shares.is_zero() {
+                    Decimal::ZERO
+                } else {
+                    exposure.net_exposure / exposure.net_shares
+                };
+                vec![
+                    market.clone(),
+                    outcome.clone(),
+                    exposure.trades.to_string(),
+                    format!("{:.2}", exposure.net_shares),
+                    display::price(avg_price),
+                    display::money(exposure.net_exposure),
+                ]
+            })
+            .collect();
+        report.table("Exposure by Market", &["market", "outcome", "trades", "shares", "avg_price", "exposure"], rows);
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    use crate::db::{CopyTradeStatus, Database};
+
+    #[tokio::test]
+    async fn test_summary_aggregates_simulated_trades() {
+        let db = Database::new(&crate::testing::temp_database_url()).await.unwrap();
+        db.save_trader("0xabc", "", 1.0).await.unwrap();
+
+        let fills = [
+            ("t1", "0xm1", "Buy", 100.0, 0.50, CopyTradeStatus::Simulated),
+            ("t2", "0xm1", "Sell", 40.0, 0.75, CopyTradeStatus::Simulated),
+            ("t3", "0xm2", "Buy", 20.0, 0.25, CopyTradeStatus::Simulated),
+            ("t4", "0xm2", "Buy", 1000.0, 0.25, CopyTradeStatus::Failed),
+        ];
+        for (id, market, side, shares, price, status) in fills {
+            db.save_copy_trade(id, "0xabc", id, market, "", side, "Yes", 10.0, price, shares).await.unwrap();
+            db.update_copy_trade_status(id, status, None, Some(price), None, None).await.unwrap();
+        }
+
+        let simulated = db.get_copy_trades_with_status(CopyTradeStatus::Simulated).await.unwrap();
+        let summary = DryRunSummary::from_trades(&simulated, dec!(0.10), dec!(0.01));
+
+        assert_eq!(summary.trades, 3);
+        assert_eq!(summary.buy_notional, dec!(55));
+        assert_eq!(summary.sell_notional, dec!(30));
+        assert_eq!(summary.net_exposure(), dec!(25));
+        // $0.10 per order plus 1% of $85
+        assert_eq!(summary.estimated_fees, dec!(1.15));
+
+        let m1 = &summary.markets[&("0xm1".to_string(), "Yes".to_string())];
+        assert_eq!(m1, &MarketExposure { trades: 2, net_shares: dec!(60), net_exposure: dec!(20) });
+
+        let report = summary.to_report();
+        assert_eq!(report.tables[0].rows.len(), 2);
+        assert_eq!(report.tables[0].rows[0][0], "0xm1");
+    }
+}
@@ -0,0 +1,223 @@
+//! Realized-P&L ledger of closed positions, and its tax-lot export.
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use tracing::warn;
+
+use crate::db::{self, StoredPosition};
+use crate::display;
+use crate::output::Report;
+
+/// Positions held longer than this are long-term.
+pub const LONG_TERM_DAYS: i64 = 365;
+
+/// Ledger output format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LedgerFormat {
+    /// Closed positions with realized P&L, as a regular report
+    #[default]
+    Report,
+    /// One row per disposal with dates, proceeds, cost basis and holding term
+    TaxCsv,
+}
+
+/// Holding-period classification of a disposal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Term {
+    Short,
+    Long,
+}
+
+impl std::fmt::Display for Term {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Short => "short",
+            Self::Long => "long",
+        })
+    }
+}
+
+/// One closed position as a taxable disposal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaxRow {
+    pub description: String,
+    pub shares: Decimal,
+    pub acquired: DateTime<Utc>,
+    pub disposed: DateTime<Utc>,
+    pub proceeds: Decimal,
+    /// Cost of the shares plus trading costs, so `proceeds - cost_basis` is the realized P&L
+    pub cost_basis: Decimal,
+    pub term: Term,
+}
+
+impl TaxRow {
+    /// Build a row from a closed position; `None` if it's open or missing its dates or exit price.
+    pub fn from_position(position: &StoredPosition) -> Option<Self> {
+        let acquired = db::parse_timestamp(&position.opened_at)?;
+        let disposed = db::parse_timestamp(position.closed_at.as_deref()?)?;
+        let shares = Decimal::try_from(position.size).ok()?;
+        let entry = Decimal::try_from(position.entry_price).ok()?;
+        let exit = Decimal::try_from(position.exit_price?).ok()?;
+
+        // A short is opened by selling, so its proceeds come at entry
+        let (proceeds, cost) = if position.side.eq_ignore_ascii_case("sell") {
+            (shares * entry, shares * exit)
+        } else {
+            (shares * exit, shares * entry)
+        };
+        // Trading costs belong in the basis. Rows closed before costs were stored carry
+        // them only inside realized P&L, booked then as `(exit - entry) * size - costs`
+        let costs = match position.costs {
+            Some(costs) => Decimal::try_from(costs).unwrap_or(Decimal::ZERO),
+            None => position
+                .realized_pnl
+                .and_then(|pnl| Decimal::try_from(pnl).ok())
+                .map_or(Decimal::ZERO, |pnl| ((exit - entry) * shares - pnl).max(Decimal::ZERO)),
+        };
+
+        let term = if disposed - acquired > Duration::days(LONG_TERM_DAYS) { Term::Long } else { Term::Short };
+        let title = if position.market_title.is_empty() { &position.market_id } else { &position.market_title };
+
+        Some(Self {
+            description: format!("{} ({})", title, position.outcome),
+            shares: shares.normalize(),
+            acquired,
+            disposed,
+            proceeds: proceeds.round_dp(2),
+            cost_basis: (cost + costs).round_dp(2),
+            term,
+        })
+    }
+
+    pub fn gain(&self) -> Decimal {
+        self.proceeds - self.cost_basis
+    }
+
+    fn to_cells(&self) -> Vec<String> {
+        // Decimal never formats in scientific notation; fixed places keep columns uniform
+        vec![
+            self.description.clone(),
+            self.shares.to_string(),
+            self.acquired.format("%Y-%m-%d").to_string(),
+            self.disposed.format("%Y-%m-%d").to_string(),
+            format!("{:.2}", self.proceeds),
+            format!("{:.2}", self.cost_basis),
+            format!("{:.2}", self.gain()),
+            self.term.to_string(),
+        ]
+    }
+}
+
+/// Closed positions with their realized P&L, newest first.
+pub fn ledger_report(closed: &[StoredPosition]) -> Report {
+    let mut report = Report::new("Ledger");
+    let realized: f64 = closed.iter().filter_map(|p| p.realized_pnl).sum();
+    report
+        .field("Totals", "Closed Positions", closed.len())
+        .field("Totals", "Realized P&L ($)", display::money(realized));
+
+    let rows = closed
+        .iter()
+        .map(|p| {
+            vec![
+                p.market_id.clone(),
+                p.outcome.clone(),
+                p.side.clone(),
+                format!("{:.2}", p.size),
+                display::price(p.entry_price),
+                p.exit_price.map_or("-".to_string(), display::price),
+                p.realized_pnl.map_or("-".to_string(), display::money),
+                p.opened_at.clone(),
+                p.closed_at.clone().unwrap_or_default(),
+                p.exit_reason.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    report.table(
+        "Closed Positions",
+        &["market", "outcome", "side", "shares", "entry", "exit", "realized_pnl", "opened_at", "closed_at", "exit_reason"],
+        rows,
+    );
+    report
+}
+
+/// Tax lots for closed positions, oldest disposal first. Positions that can't be
+/// turned into a row are logged and left out.
+pub fn tax_report(closed: &[StoredPosition]) -> Report {
+    let mut rows: Vec<TaxRow> = closed
+        .iter()
+        .filter_map(|p| {
+            let row = TaxRow::from_position(p);
+            if row.is_none() {
+                warn!(market = %p.market_id, outcome = %p.outcome, "Skipping position without dates or exit price");
+            }
+            row
+        })
+        .collect();
+    rows.sort_by_key(|r| r.disposed);
+
+    let mut report = Report::new("Tax Lots");
+    report.table(
+        "Disposals",
+        &["description", "shares", "date_acquired", "date_disposed", "proceeds", "cost_basis", "gain_loss", "term"],
+        rows.iter().map(TaxRow::to_cells).collect(),
+    );
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    use crate::db::Database;
+    use crate::output::Format;
+
+    #[tokio::test]
+    async fn test_closed_position_produces_tax_row() {
+        let db = Database::new(&crate::testing::temp_database_url()).await.unwrap();
+        db.save_trader("0xabc", "", 1.0).await.unwrap();
+        db.save_position("0xm1", "Will it rain?", "Yes", "BUY", 100.0, 0.40, Some("0xabc")).await.unwrap();
+        db.update_position_price("0xm1", "Yes", 0.65).await.unwrap();
+        db.close_position("0xm1", "Yes", 0.50, "take_profit").await.unwrap();
+
+        let mut closed = db.get_closed_positions().await.unwrap();
+        let row = TaxRow::from_position(&closed[0]).unwrap();
+        assert_eq!(row.description, "Will it rain? (Yes)");
+        assert_eq!(row.proceeds, dec!(65));
+        assert_eq!(row.cost_basis, dec!(40.50));
+        assert_eq!(row.gain(), dec!(24.50));
+        assert_eq!(row.term, Term::Short);
+
+        let csv = tax_report(&closed).render(Format::Csv).unwrap();
+        let today = Utc::now().format("%Y-%m-%d");
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            format!("Will it rain? (Yes),100,{today},{today},65.00,40.50,24.50,short")
+        );
+
+        // Held over a year, and a tiny price that f64 would print as 1e-7
+        closed[0].opened_at = (Utc::now() - Duration::days(400)).format("%Y-%m-%d %H:%M:%S").to_string();
+        closed[0].exit_price = Some(0.0000001);
+        closed[0].realized_pnl = None;
+        closed[0].costs = None;
+        let row = TaxRow::from_position(&closed[0]).unwrap();
+        assert_eq!(row.term, Term::Long);
+        assert_eq!(row.to_cells()[4..7], ["0.00", "40.00", "-40.00"]);
+    }
+
+    #[tokio::test]
+    async fn test_profitable_short_reports_a_gain() {
+        let db = Database::new(&crate::testing::temp_database_url()).await.unwrap();
+        // Sold 100 at 0.60, bought back at 0.40, with $0.50 of costs
+        db.save_position("0xm1", "", "Yes", "SELL", 100.0, 0.60, None).await.unwrap();
+        db.update_position_price("0xm1", "Yes", 0.40).await.unwrap();
+        db.close_position("0xm1", "Yes", 0.50, "take_profit").await.unwrap();
+
+        let closed = db.get_closed_positions().await.unwrap();
+        let row = TaxRow::from_position(&closed[0]).unwrap();
+        assert_eq!(row.proceeds, dec!(60));
+        assert_eq!(row.cost_basis, dec!(40.50));
+        assert_eq!(row.gain(), dec!(19.50));
+    }
+}
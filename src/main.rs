@@ -3,25 +3,51 @@
 //! Mimics high-value traders with intelligent position sizing based on
 //! MDD, Sharpe ratio, and other performance metrics.
 
+mod activity;
 mod api;
+mod attribution;
 mod backtest;
 mod bot;
+mod clock;
+mod config;
 mod db;
+mod display;
+mod doctor;
+mod dry_run;
+mod ledger;
 mod metrics;
 mod models;
+mod output;
+mod status;
+mod telemetry;
 mod trading;
 
-use anyhow::Result;
+#[cfg(test)]
+mod testing;
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use tracing::{info, Level};
 use tracing_subscriber::FmtSubscriber;
 
-use crate::backtest::{BacktestConfig, Backtester, PaperConfig, PaperTrader};
-use crate::bot::{Bot, BotConfig};
-use crate::db::Database;
-use crate::trading::{CopyEngine, StrategyConfig, TradingConfig};
+use crate::backtest::{
+    BacktestConfig, BacktestDiff, Backtester, PaperConfig, PaperTrader, SimulatedPosition, DEFAULT_CACHE_DIR,
+};
+use crate::bot::{Bot, BotConfig, RestartPolicy};
+use crate::config::{AppConfig, DEFAULT_CONFIG_PATH};
+use crate::attribution::AttributionKey;
+use crate::api::{fetch_prices, ClobClient, DataClient, PriceChain, PriceSource, DEFAULT_PRICE_CONCURRENCY};
+use crate::db::{CopyTradeStatus, Database};
+use crate::dry_run::DryRunSummary;
+use crate::ledger::LedgerFormat;
+use crate::metrics::{RatioBasis, TRADER_PERIODS_PER_YEAR};
+use crate::output::Report;
+use crate::status::{StatusSnapshot, DEFAULT_COMPACT_WIDTH};
+use crate::trading::{CopyEngine, PositionSizer, Strategy, TradingConfig, DEFAULT_LOAD_CONCURRENCY, SIZING_EXAMPLES};
 
 /// Polymarket copy-trading bot CLI.
 #[derive(Parser)]
@@ -36,6 +62,10 @@ struct Cli {
     #[arg(short, long, default_value = "info")]
     log_level: String,
 
+    /// Configuration file path (JSON; missing keys use defaults)
+    #[arg(long, default_value = DEFAULT_CONFIG_PATH)]
+    config: PathBuf,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -55,6 +85,10 @@ enum Commands {
         /// Time period (DAY, WEEK, MONTH, ALL)
         #[arg(short, long, default_value = "MONTH")]
         period: String,
+
+        /// Maximum number of traders scored at once
+        #[arg(long, default_value_t = DEFAULT_LOAD_CONCURRENCY)]
+        concurrency: usize,
     },
 
     /// Add a trader to track
@@ -69,13 +103,56 @@ enum Commands {
         address: String,
     },
 
+    /// Stop copying a tracked trader's new trades, keeping its history and positions
+    Pause {
+        /// Trader's wallet address
+        address: String,
+    },
+
+    /// Resume copying a paused trader
+    Resume {
+        /// Trader's wallet address
+        address: String,
+    },
+
     /// List all tracked traders
-    List,
+    List {
+        /// Maximum traders loaded from the API at once
+        #[arg(long, default_value_t = DEFAULT_LOAD_CONCURRENCY)]
+        concurrency: usize,
+    },
 
     /// Show detailed stats for a trader
     Stats {
         /// Trader's wallet address
         address: String,
+
+        /// Report Sharpe/Sortino annualized or as raw per-period ratios
+        #[arg(long, value_enum, default_value = "annualized")]
+        ratios: RatioBasis,
+
+        /// Write the report to a file (.json, .csv, .md or .txt)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// List a trader's recent trades, or those of every tracked trader
+    Trades {
+        /// Trader's wallet address
+        #[arg(required_unless_present = "all")]
+        address: Option<String>,
+
+        /// Merge recent trades from all tracked traders, newest first
+        #[arg(long, conflicts_with = "address")]
+        all: bool,
+
+        /// Maximum number of trades to show
+        #[arg(short, long, default_value_t = 20)]
+        limit: u32,
+
+        /// Write the list to a file (.json, .csv, .md or .txt)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
     },
 
     /// Start the copy-trading bot
@@ -88,16 +165,154 @@ enum Commands {
         #[arg(short, long, default_value = "30")]
         interval: u64,
 
+        /// Random jitter on the polling interval, in percent (0-100)
+        #[arg(long, default_value = "0")]
+        poll_jitter: f64,
+
         /// Dry run (don't execute trades)
         #[arg(long)]
         dry_run: bool,
+
+        /// Cancel all resting orders on shutdown
+        #[arg(long)]
+        cancel_on_exit: bool,
+
+        /// Periodically resync the portfolio value from the wallet balance
+        #[arg(long)]
+        auto_sync_portfolio: bool,
+
+        /// Also copy traders' maker (limit order) fills
+        #[arg(long)]
+        include_maker_trades: bool,
+
+        /// Start live trading even if doctor checks fail
+        #[arg(long)]
+        skip_doctor: bool,
+
+        /// Stop after running this long (e.g. 90s, 30m, 6h, 1d)
+        #[arg(long, value_parser = parse_duration)]
+        max_runtime: Option<std::time::Duration>,
+
+        /// How to reconcile stored positions with wallet holdings on startup
+        #[arg(long, value_enum, default_value = "trust-db")]
+        restart_policy: RestartPolicy,
+
+        /// Measure drawdown from current equity instead of the stored peak, clearing a
+        /// drawdown halt
+        #[arg(long)]
+        reset_peak: bool,
+
+        /// Estimated flat cost per order in USDC, netted from realized P&L
+        #[arg(long, default_value = "0")]
+        order_cost: f64,
+
+        /// Estimated cost per order as a percent of notional
+        #[arg(long, default_value = "0")]
+        order_cost_pct: f64,
+
+        /// Write each tick's database changes in one transaction (order records are
+        /// still committed as soon as each order returns)
+        #[arg(long)]
+        batch_writes: bool,
+
+        /// Execute at most this many copy trades per poll, deferring the rest
+        #[arg(long)]
+        max_executions_per_tick: Option<usize>,
+
+        /// Push run metrics to a StatsD/DogStatsD collector at host:port
+        #[arg(long)]
+        statsd_addr: Option<String>,
+
+        /// Wait this long after a failed order submission before checking the book
+        /// and marking the copy trade failed (e.g. 2s, 0s)
+        #[arg(long, value_parser = parse_duration, default_value = "2s")]
+        order_confirm_grace: std::time::Duration,
+    },
+
+    /// Check credentials, database and API connectivity
+    Doctor,
+
+    /// Show the effective configuration, or change a setting
+    Config {
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+    },
+
+    /// Close one open position now (the bot must be stopped)
+    Close {
+        /// Market condition ID
+        market_id: String,
+
+        /// Outcome held (e.g. "Yes")
+        outcome: String,
+
+        /// Show the P&L a close at the live order-book price would realize, without
+        /// placing an order or changing the database
+        #[arg(long)]
+        dry_run: bool,
     },
 
-    /// Show current configuration
-    Config,
+    /// Summarize recorded dry-run trades as if they had been placed live
+    DryRunSummary {
+        /// Estimated fixed cost per order in USDC
+        #[arg(long, default_value = "0")]
+        order_cost: f64,
+
+        /// Estimated cost per order as a percent of notional
+        #[arg(long, default_value = "0.1")]
+        order_cost_pct: f64,
+
+        /// Write the report to a file (.json, .csv, .md or .txt)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
 
     /// Show bot status and statistics
-    Status,
+    Status {
+        /// Write the report to a file (.json, .csv, .md or .txt)
+        #[arg(short, long, conflicts_with = "compact")]
+        output: Option<PathBuf>,
+
+        /// Print a single line for status bars (tmux, polybar)
+        #[arg(long)]
+        compact: bool,
+
+        /// Maximum width of the compact line
+        #[arg(long, default_value_t = DEFAULT_COMPACT_WIDTH, requires = "compact")]
+        width: usize,
+    },
+
+    /// List open positions
+    Positions {
+        /// Show holding time and distance to stop/target, riskiest first
+        #[arg(long)]
+        aging: bool,
+    },
+
+    /// Backfill realized P&L for closed positions recorded without it
+    BackfillPnl,
+
+    /// Export closed positions and their realized P&L
+    Ledger {
+        /// 'report' for the realized-P&L ledger, 'tax-csv' for one row per disposal
+        #[arg(long, value_enum, default_value = "report")]
+        format: LedgerFormat,
+
+        /// Write to a file (tax-csv is always CSV; otherwise .json, .csv, .md or .txt)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Break down realized P&L by source trader or market category
+    Attribution {
+        /// Group closed positions by 'trader' or 'category'
+        #[arg(long, value_enum, default_value = "trader")]
+        by: AttributionKey,
+
+        /// Write the report to a file (.json, .csv, .md or .txt)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
 
     /// Run historical backtest on a trader's trades
     Backtest {
@@ -128,6 +343,70 @@ enum Commands {
         /// Use relaxed strategy parameters (wider price bounds, higher drawdown limit)
         #[arg(long)]
         relaxed: bool,
+
+        /// Only validate the configuration, without fetching any data
+        #[arg(long)]
+        validate_only: bool,
+
+        /// Only simulate trades in this market (condition ID)
+        #[arg(long)]
+        market: Option<String>,
+
+        /// Only simulate trades on this outcome (e.g. "Yes")
+        #[arg(long)]
+        outcome: Option<String>,
+
+        /// Always fetch trade histories from the API, without reading or writing the cache
+        #[arg(long, conflicts_with = "refresh_cache")]
+        no_cache: bool,
+
+        /// Re-fetch trade histories even if cached, and update the cache
+        #[arg(long)]
+        refresh_cache: bool,
+
+        /// Report Sharpe/Sortino annualized or as raw per-period ratios
+        #[arg(long, value_enum, default_value = "annualized")]
+        ratios: RatioBasis,
+
+        /// Settle positions still open when their market resolves, at 1.0 or 0.0
+        #[arg(long)]
+        settle_at_resolution: bool,
+
+        /// Report per-trade returns net of fees, so they reconcile with the total return
+        #[arg(long)]
+        net_returns: bool,
+
+        /// Write the results to a file (.json, .csv, .md or .txt)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Also save the full results as JSON, for comparison with backtest-diff
+        #[arg(long)]
+        save_results: Option<PathBuf>,
+    },
+
+    /// Compare two results files saved with 'backtest --save-results'
+    BacktestDiff {
+        /// Baseline results
+        a: PathBuf,
+
+        /// Results to compare against the baseline
+        b: PathBuf,
+
+        /// Write the diff to a file (.json, .csv, .md or .txt)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Run the full bot against live order-book prices without placing orders
+    SimulateLive {
+        /// Simulated portfolio value in USDC
+        #[arg(short, long, default_value = "1000")]
+        portfolio: f64,
+
+        /// Polling interval in seconds
+        #[arg(short, long, default_value = "30")]
+        interval: u64,
     },
 
     /// Start paper trading (simulated live trading)
@@ -147,6 +426,26 @@ enum Commands {
         /// Simulated fee percentage (0-100)
         #[arg(long, default_value = "0.1")]
         fee: f64,
+
+        /// Maximum concurrent order book requests when marking positions
+        #[arg(long, default_value_t = DEFAULT_PRICE_CONCURRENCY)]
+        price_concurrency: usize,
+
+        /// Stop after running this long (e.g. 90s, 30m, 6h, 1d)
+        #[arg(long, value_parser = parse_duration)]
+        max_runtime: Option<std::time::Duration>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Persist a setting to the config file (e.g. `strategy.take_profit_pct 0.3`)
+    Set {
+        /// Dotted key, e.g. trading.max_single_position
+        key: String,
+
+        /// New value
+        value: String,
     },
 }
 
@@ -172,25 +471,29 @@ async fn main() -> Result<()> {
     // Initialize database
     let db = Database::new(&cli.database).await?;
 
+    // Load configuration file (defaults for anything unset)
+    let app_config = AppConfig::load(&cli.config)?;
+    display::configure(app_config.display.clone());
+
     // Initialize copy engine
-    let config = TradingConfig::default();
-    let engine = CopyEngine::new(config)?;
+    let engine = CopyEngine::new(app_config.trading.clone())?;
 
     match cli.command {
         Commands::Discover {
             min_pnl,
             limit,
             period: _,
+            concurrency,
         } => {
             info!("Discovering top traders with min P&L ${}", min_pnl);
 
-            let traders = engine.discover_traders(min_pnl, limit).await?;
+            let traders = engine.discover_traders(min_pnl, limit, concurrency).await?;
 
             println!("\n{:<44} {:<20} {:>10}", "ADDRESS", "NAME", "SCORE");
             println!("{}", "-".repeat(76));
 
             for trader in traders {
-                let score = trader.score();
+                let score = trader.score(app_config.trading.min_trades_for_score);
                 println!(
                     "{:<44} {:<20} {:>10.1}",
                     trader.address,
@@ -213,11 +516,11 @@ async fn main() -> Result<()> {
             if let Some(trader) = traders.iter().find(|t| t.address == address) {
                 if let Some(m) = &trader.metrics {
                     println!("\nMetrics:");
-                    println!("  Win Rate:     {:.1}%", m.win_rate * 100.0);
+                    println!("  Win Rate:     {}%", display::pct(m.win_rate));
                     println!("  Sharpe Ratio: {:.2}", m.sharpe_ratio);
-                    println!("  Max Drawdown: {:.1}%", m.max_drawdown * 100.0);
+                    println!("  Max Drawdown: {}%", display::pct(m.max_drawdown));
                     println!("  Total Trades: {}", m.total_trades);
-                    println!("  Score:        {:.1}", m.composite_score());
+                    println!("  Score:        {:.1}", m.composite_score(app_config.trading.min_trades_for_score));
                 }
             }
         }
@@ -228,7 +531,23 @@ async fn main() -> Result<()> {
             println!("Stopped tracking: {}", address);
         }
 
-        Commands::List => {
+        Commands::Pause { address } => {
+            if db.set_trader_paused(&address, true).await? {
+                println!("Paused: {} (open positions are still managed)", address);
+            } else {
+                println!("Not tracking {}. Use 'polycopier track <address>' first.", address);
+            }
+        }
+
+        Commands::Resume { address } => {
+            if db.set_trader_paused(&address, false).await? {
+                println!("Resumed: {}", address);
+            } else {
+                println!("Not tracking {}. Use 'polycopier track <address>' first.", address);
+            }
+        }
+
+        Commands::List { concurrency } => {
             let addresses = db.get_tracked_addresses().await?;
 
             if addresses.is_empty() {
@@ -237,11 +556,18 @@ async fn main() -> Result<()> {
             }
 
             // Load traders into engine
-            for addr in &addresses {
-                let _ = engine.add_trader(addr.clone()).await;
+            let loaded = engine.add_traders(&addresses, concurrency).await;
+            for address in &addresses {
+                let (tracking_since, last_trade_seen_at) = db.get_trader_activity(address).await?;
+                engine.restore_activity(address, tracking_since, last_trade_seen_at).await;
             }
-
-            let traders = engine.get_tracked_traders().await;
+            let traders: std::collections::HashMap<String, crate::models::Trader> = engine
+                .get_tracked_traders()
+                .await
+                .into_iter()
+                .map(|t| (t.address.clone(), t))
+                .collect();
+            let paused = db.get_paused_addresses().await?;
 
             println!(
                 "\n{:<44} {:<12} {:>8} {:>8} {:>10}",
@@ -249,25 +575,69 @@ async fn main() -> Result<()> {
             );
             println!("{}", "-".repeat(86));
 
-            for trader in traders {
+            for (address, result) in &loaded {
+                let trader = match (result, traders.get(address)) {
+                    (Ok(()), Some(trader)) => trader,
+                    (Err(e), _) => {
+                        println!("{:<44} (failed to load: {})", address, e);
+                        continue;
+                    }
+                    (Ok(()), None) => {
+                        println!("{:<44} (failed to load)", address);
+                        continue;
+                    }
+                };
                 let (win_rate, sharpe, score) = trader
                     .metrics
                     .as_ref()
-                    .map(|m| (m.win_rate * 100.0, m.sharpe_ratio, m.composite_score()))
+                    .map(|m| (m.win_rate, m.sharpe_ratio, m.composite_score(app_config.trading.min_trades_for_score)))
                     .unwrap_or((0.0, 0.0, 0.0));
 
                 println!(
-                    "{:<44} {:<12} {:>7.1}% {:>8.2} {:>10.1}",
+                    "{:<44} {:<12} {:>7}% {:>8.2} {:>10.1}{}",
                     trader.address,
                     truncate(&trader.display_name(), 10),
-                    win_rate,
+                    display::pct(win_rate),
                     sharpe,
-                    score
+                    score,
+                    if paused.contains(&trader.address) { "  (paused)" } else { "" }
                 );
             }
+
+            let window = chrono::Duration::days(app_config.trading.inactive_after_days);
+            let inactive: Vec<_> = loaded
+                .iter()
+                .filter_map(|(address, _)| traders.get(address))
+                .filter(|t| t.is_inactive(chrono::Utc::now(), window))
+                .collect();
+            if !inactive.is_empty() {
+                println!("\nNo trades in {}+ days:", app_config.trading.inactive_after_days);
+                for trader in inactive {
+                    println!("  {}", trader.address);
+                }
+            }
+        }
+
+        Commands::Trades { address, all, limit, output } => {
+            let addresses = if all { db.get_tracked_addresses().await? } else { address.into_iter().collect() };
+            if addresses.is_empty() {
+                println!("No traders being tracked. Use 'polycopier track <address>' to add one.");
+                return Ok(());
+            }
+
+            let data_client = DataClient::new()?;
+            let mut lists = Vec::new();
+            for address in &addresses {
+                match data_client.get_trades(address, Some(limit), None).await {
+                    Ok(trades) => lists.push(trades),
+                    Err(e) => tracing::warn!(trader = %address, error = %e, "Failed to fetch trades"),
+                }
+            }
+            let trades = activity::merge_recent(lists, limit as usize);
+            output::emit(&activity::trades_report(&trades), output.as_deref())?;
         }
 
-        Commands::Stats { address } => {
+        Commands::Stats { address, ratios, output } => {
             engine.add_trader(address.clone()).await?;
 
             let traders = engine.get_tracked_traders().await;
@@ -276,54 +646,72 @@ async fn main() -> Result<()> {
                 .find(|t| t.address == address)
                 .ok_or_else(|| anyhow::anyhow!("Trader not found"))?;
 
-            println!("\n=== Trader: {} ===", trader.display_name());
-            println!("Address: {}", trader.address);
+            let mut report = Report::new(format!("Trader: {}", trader.display_name()));
+            report.field("Trader", "Address", &trader.address);
 
             if let Some(m) = &trader.metrics {
-                println!("\n--- Performance Metrics ---");
-                println!("Total Trades:   {}", m.total_trades);
-                println!("Total Volume:   ${:.2}", m.total_volume);
-                println!("Total P&L:      ${:.2}", m.total_pnl);
-
-                println!("\n--- Win/Loss ---");
-                println!("Win Rate:       {:.1}%", m.win_rate * 100.0);
-                println!("Winning Trades: {}", m.winning_trades);
-                println!("Losing Trades:  {}", m.losing_trades);
-                println!("Avg Win:        ${:.2}", m.avg_win);
-                println!("Avg Loss:       ${:.2}", m.avg_loss);
-                println!("Profit Factor:  {:.2}", m.profit_factor);
-
-                println!("\n--- Risk Metrics ---");
-                println!("Max Drawdown:   {:.1}%", m.max_drawdown * 100.0);
-                println!("Sharpe Ratio:   {:.2}", m.sharpe_ratio);
-                println!("Sortino Ratio:  {:.2}", m.sortino_ratio);
-                println!("Calmar Ratio:   {:.2}", m.calmar_ratio);
-
-                println!("\n--- Scoring ---");
-                println!("Composite Score:      {:.1}/100", m.composite_score());
-                println!(
-                    "Suggested Allocation: {:.1}%",
-                    m.suggested_allocation() * 100.0
-                );
-                println!(
-                    "Quality Trader:       {}",
-                    if m.is_quality_trader() { "Yes" } else { "No" }
-                );
+                report
+                    .field("Performance Metrics", "Total Trades", m.total_trades)
+                    .field("Performance Metrics", "Total Volume ($)", display::money(m.total_volume))
+                    .field("Performance Metrics", "Total P&L ($)", display::money(m.total_pnl))
+                    .field("Win/Loss", "Win Rate (%)", display::pct(m.win_rate))
+                    .field("Win/Loss", "Winning Trades", m.winning_trades)
+                    .field("Win/Loss", "Losing Trades", m.losing_trades)
+                    .field("Win/Loss", "Avg Win ($)", display::money(m.avg_win))
+                    .field("Win/Loss", "Avg Loss ($)", display::money(m.avg_loss))
+                    .field("Win/Loss", "Profit Factor", display::profit_factor(m.profit_factor))
+                    .field("Risk Metrics", "Max Drawdown (%)", display::pct(m.max_drawdown))
+                    .field(
+                        "Risk Metrics",
+                        &ratios.label("Sharpe Ratio", TRADER_PERIODS_PER_YEAR),
+                        format!("{:.2}", ratios.rescale(m.sharpe_ratio, TRADER_PERIODS_PER_YEAR)),
+                    )
+                    .field(
+                        "Risk Metrics",
+                        &ratios.label("Sortino Ratio", TRADER_PERIODS_PER_YEAR),
+                        format!("{:.2}", ratios.rescale(m.sortino_ratio, TRADER_PERIODS_PER_YEAR)),
+                    )
+                    .field("Risk Metrics", "Calmar Ratio", format!("{:.2}", m.calmar_ratio))
+                    .field("Scoring", "Composite Score", format!("{:.1}", m.composite_score(app_config.trading.min_trades_for_score)))
+                    .field("Scoring", "Suggested Allocation (%)", display::pct(m.suggested_allocation()))
+                    .field("Scoring", "Quality Trader", m.is_quality_trader(app_config.trading.min_trades_for_quality));
             }
 
-            println!("\n--- Open Positions ({}) ---", trader.positions.len());
-            for pos in &trader.positions {
-                println!(
-                    "  {} {} @ {:.3} (P&L: ${:.2})",
-                    pos.market_title, pos.outcome, pos.average_price, pos.unrealized_pnl
-                );
-            }
+            let rows = trader
+                .positions
+                .iter()
+                .map(|pos| {
+                    vec![
+                        pos.market_title.clone(),
+                        pos.outcome.clone(),
+                        display::price(pos.average_price),
+                        display::money(pos.unrealized_pnl),
+                    ]
+                })
+                .collect();
+            report.table("Open Positions", &["market", "outcome", "avg_price", "unrealized_pnl"], rows);
+
+            output::emit(&report, output.as_deref())?;
         }
 
         Commands::Run {
             portfolio,
             interval,
+            poll_jitter,
             dry_run,
+            cancel_on_exit,
+            auto_sync_portfolio,
+            include_maker_trades,
+            skip_doctor,
+            max_runtime,
+            restart_policy,
+            reset_peak,
+            order_cost,
+            order_cost_pct,
+            batch_writes,
+            max_executions_per_tick,
+            statsd_addr,
+            order_confirm_grace,
         } => {
             info!(
                 portfolio = portfolio,
@@ -343,14 +731,32 @@ async fn main() -> Result<()> {
             let bot_config = BotConfig {
                 portfolio_value: Decimal::try_from(portfolio)?,
                 poll_interval_secs: interval,
+                poll_jitter_pct: poll_jitter / 100.0,
                 dry_run,
-                trading_config: TradingConfig::default(),
-                strategy_config: StrategyConfig::default(),
+                cancel_on_exit,
+                auto_sync_portfolio,
+                require_doctor_pass: !skip_doctor,
+                price_fetch_concurrency: DEFAULT_PRICE_CONCURRENCY,
+                restart_policy,
+                reset_peak_equity: reset_peak,
+                max_runtime,
+                order_cost_usdc: Decimal::try_from(order_cost)?,
+                order_cost_rate: Decimal::try_from(order_cost_pct / 100.0)?,
+                batch_db_writes: batch_writes,
+                max_executions_per_tick,
+                statsd_addr,
+                order_confirm_grace,
+                trading_config: TradingConfig {
+                    include_maker_trades: include_maker_trades || app_config.trading.include_maker_trades,
+                    ..app_config.trading.clone()
+                },
+                strategy_config: app_config.strategy.clone(),
                 database_url: cli.database.clone(),
             };
 
             // Create and initialize the bot
             let mut bot = Bot::new(bot_config).await?;
+            bot.preflight().await?;
             bot.initialize().await?;
 
             println!("\n=== Polymarket Copy-Trading Bot ===");
@@ -370,29 +776,140 @@ async fn main() -> Result<()> {
             println!("\n{}", stats);
         }
 
-        Commands::Config => {
-            let config = TradingConfig::default();
-            let strategy = StrategyConfig::default();
+        Commands::SimulateLive { portfolio, interval } => {
+            let addresses = db.get_tracked_addresses().await?;
+            if addresses.is_empty() {
+                println!("No traders being tracked. Use 'polycopier track <address>' first.");
+                return Ok(());
+            }
+
+            let bot_config = BotConfig {
+                portfolio_value: Decimal::try_from(portfolio)?,
+                poll_interval_secs: interval,
+                trading_config: app_config.trading.clone(),
+                strategy_config: app_config.strategy.clone(),
+                database_url: cli.database.clone(),
+                ..BotConfig::default()
+            };
+
+            let mut bot = Bot::simulate_live(bot_config).await?;
+            bot.initialize().await?;
+
+            println!("\n=== Simulate Live ===");
+            println!("Portfolio value: ${}", portfolio);
+            println!("Polling interval: {}s", interval);
+            println!("Prices: live order books (no orders are placed)");
+            println!("Tracked traders: {}", addresses.len());
+            println!("\nPress Ctrl+C to stop.\n");
+
+            if let Err(e) = bot.run().await {
+                tracing::error!(error = %e, "Bot error");
+            }
+
+            let stats = bot.get_stats().await;
+            println!("\n{}", stats);
+        }
+
+        Commands::Doctor => {
+            let clob = match ClobClient::from_env() {
+                Ok(client) => Some(client),
+                Err(e) => {
+                    println!("Trading credentials: {}", e);
+                    None
+                }
+            };
+            // The allowance must cover the last session's portfolio at full allocation
+            let portfolio = db.get_bot_state().await.map(|s| s.portfolio_value).unwrap_or(0.0);
+            let max_exposure = Decimal::try_from(portfolio).unwrap_or(Decimal::ZERO)
+                * app_config.trading.max_portfolio_allocation;
+            let checks = doctor::run_checks(&db, &DataClient::new()?, clob.as_ref(), max_exposure).await;
+
+            println!("\n=== Doctor ===");
+            for check in &checks {
+                println!("  {}", check);
+            }
+
+            let failures = doctor::critical_failures(&checks);
+            if !failures.is_empty() {
+                anyhow::bail!("{} critical check(s) failed. Live trading will refuse to start.", failures.len());
+            }
+            println!("\nAll critical checks passed. Live trading is allowed.");
+        }
+
+        Commands::Config { action: Some(ConfigAction::Set { key, value }) } => {
+            let mut updated = app_config.clone();
+            updated.set(&key, &value)?;
+            updated.save(&cli.config)?;
+            println!("Set {} = {} in {}", key, value, cli.config.display());
+        }
+
+        Commands::Config { action: None } => {
+            let config = &app_config.trading;
+            let strategy = &app_config.strategy;
+
+            if cli.config.exists() {
+                println!("Config file: {}", cli.config.display());
+            } else {
+                println!("Config file: {} (not found, using defaults)", cli.config.display());
+            }
 
             println!("\n=== Trading Configuration ===\n");
             println!("Position Sizing:");
             println!("  Method:               {}", config.sizing_method);
             println!("  Kelly Fraction:       {}", config.kelly_fraction);
+            if config.use_conviction_scaling {
+                println!("  Conviction Scaling:   up to {}x", config.max_conviction_multiplier);
+            } else {
+                println!("  Conviction Scaling:   off");
+            }
             println!("  Max Portfolio Alloc:  {}%", config.max_portfolio_allocation * Decimal::from(100));
+            if config.allocation_ramp_days > 0 {
+                println!(
+                    "  Allocation Ramp:      {}% -> max over {} days",
+                    config.allocation_ramp_start * Decimal::from(100),
+                    config.allocation_ramp_days
+                );
+            } else {
+                println!("  Allocation Ramp:      off");
+            }
             println!("  Max Single Position:  {}%", config.max_single_position * Decimal::from(100));
             println!("  Min Trade Size:       ${}", config.min_trade_size);
             println!("  Max Trade Size:       ${}", config.max_trade_size);
 
+            println!("\nSizing Examples ({}):", config.sizing_method);
+            let sizer = PositionSizer::new(config.clone());
+            for example in SIZING_EXAMPLES {
+                println!(
+                    "  ${} portfolio copying a ${} trade from a ${} trader ({:.0}% win rate, {:.0}% drawdown): ${}",
+                    example.portfolio_value,
+                    example.source_trade_size,
+                    example.source_portfolio_value,
+                    example.win_rate * 100.0,
+                    example.max_drawdown * 100.0,
+                    example.size(&sizer).round_dp(2)
+                );
+            }
+
             println!("\nRisk Management:");
             println!("  Max Drawdown:         {}%", config.max_drawdown_pct * Decimal::from(100));
             println!("  Slippage Tolerance:   {}%", config.slippage_tolerance * Decimal::from(100));
+            let sources: Vec<String> = config.price_sources.iter().map(|s| s.to_string()).collect();
+            println!("  Price Sources:        {} (stale after {}s)", sources.join(" > "), config.max_price_staleness_secs);
 
             println!("\nTrader Requirements:");
             println!("  Min Win Rate:         {:.0}%", config.min_win_rate * 100.0);
             println!("  Min Trades:           {}", config.min_trades);
+            println!("  Min Trades to Score:  {} (quality: {})", config.min_trades_for_score, config.min_trades_for_quality);
+            println!("  Max Metrics Age:      {}s", config.max_metrics_age_secs);
+            println!("  Inactive After:       {} days (auto-pause: {})", config.inactive_after_days, config.auto_pause_inactive);
             println!("  Min Profit:           ${}", config.min_profit);
             println!("  Max Trader MDD:       {:.0}%", config.max_trader_mdd * 100.0);
             println!("  Min Sharpe:           {:.1}", config.min_sharpe);
+            match config.metrics_half_life_days {
+                Some(days) => println!("  Metrics Half-Life:    {}d", days),
+                None => println!("  Metrics Half-Life:    off (unweighted)"),
+            }
+            println!("  Copy Maker Fills:     {}", config.include_maker_trades);
 
             println!("\n=== Strategy Configuration ===\n");
             println!("Entry Rules:");
@@ -400,70 +917,227 @@ async fn main() -> Result<()> {
             println!("  Min Entry Price:      {}", strategy.min_entry_price);
             println!("  Max Entry Price:      {}", strategy.max_entry_price);
             println!("  Max Entry Slippage:   {}%", strategy.max_entry_slippage * dec!(100));
+            println!("  Slippage Depth Ref:   {}", strategy.slippage_reference_depth
+                .map(|d| format!("${}", d))
+                .unwrap_or_else(|| "off".to_string()));
+            println!("  Add Slippage Decay:   {}%", strategy.add_slippage_decay * dec!(100));
+            println!("  Max Book Fraction:    {}", strategy.max_book_fraction
+                .map(|f| format!("{}%", f * dec!(100)))
+                .unwrap_or_else(|| "off".to_string()));
+            println!("  Max Volume Fraction:  {}", strategy.max_volume_fraction
+                .map(|f| format!("{}% of 24h", f * dec!(100)))
+                .unwrap_or_else(|| "off".to_string()));
             println!("  Min Trader Score:     {}", strategy.min_trader_score);
+            println!("  Min Source Trade:     ${}", strategy.min_source_trade_usdc);
+            println!("  New Markets Only:     {}", strategy.new_markets_only);
+            println!("  Source P&L Filter:    {}", strategy.source_position_pnl_filter);
+            println!("  Entry Patience:       {}", strategy.entry_patience_secs
+                .map(|s| format!("{}s", s))
+                .unwrap_or_else(|| "off".to_string()));
+            println!("  Copy Delay:           {}", strategy.copy_delay_secs
+                .map(|s| format!("{}s", s))
+                .unwrap_or_else(|| "off".to_string()));
 
             println!("\nExit Rules:");
             println!("  Take Profit:          {}%", strategy.take_profit_pct * dec!(100));
             println!("  Stop Loss:            {}%", strategy.stop_loss_pct * dec!(100));
+            println!("  Trailing Stop:        {}", strategy.trailing_stop_pct
+                .map(|t| format!("{}% below peak", t * dec!(100)))
+                .unwrap_or_else(|| "off".to_string()));
+            println!("  Exit Price Scaling:   {}", strategy.exit_price_scaling);
+            match strategy.hard_take_profit {
+                Some(hard) => println!("  Hard Take Profit:     {}%", hard * dec!(100)),
+                None => println!("  Hard Take Profit:     off"),
+            }
+            match strategy.exit_price_extreme {
+                Some((low, high)) => println!("  Extreme Price Exit:   <= {} or >= {}", low, high),
+                None => println!("  Extreme Price Exit:   off"),
+            }
             println!("  Max Holding Period:   {}h", strategy.max_holding_hours);
+            println!("  Min Hold Time:        {}s", strategy.min_hold_secs);
             println!("  Follow Trader Exits:  {}", strategy.follow_trader_exits);
+            println!("  Source Hedges:        {} (backtest and paper only)", strategy.source_hedge_policy);
+            println!("  Holdings Max Age:     {}s", strategy.holdings_max_age_secs);
 
             println!("\nPortfolio Risk:");
             println!("  Max Drawdown:         {}%", strategy.max_portfolio_drawdown * dec!(100));
+            match strategy.portfolio_take_profit {
+                Some(target) => println!("  Take-Profit Target:   {}%", target * dec!(100)),
+                None => println!("  Take-Profit Target:   off"),
+            }
             println!("  Max Positions:        {}", strategy.max_concurrent_positions);
             println!("  Max Single Market:    {}%", strategy.max_single_market_exposure * dec!(100));
+            if strategy.auto_prune_losers {
+                println!("  Prune Losing Traders: {} at -${} over {} days",
+                    strategy.prune_action, strategy.prune_loss_usdc, strategy.prune_window_days);
+            } else {
+                println!("  Prune Losing Traders: off");
+            }
+
+            let formatting = &app_config.display;
+            println!("\n=== Display ===\n");
+            println!("  Money Precision:      {}", formatting.money_precision);
+            println!("  Price Precision:      {}", formatting.price_precision);
+            println!("  Percent Precision:    {}", formatting.percent_precision);
+            println!("  Rounding:             {}", formatting.rounding);
+        }
+
+        Commands::Close { market_id, outcome, dry_run } => {
+            if db.get_bot_state().await.is_ok_and(|state| state.is_live(chrono::Utc::now())) {
+                anyhow::bail!("The bot is running; stop it before closing positions by hand");
+            }
+
+            let config = BotConfig {
+                database_url: cli.database.clone(),
+                trading_config: app_config.trading.clone(),
+                strategy_config: app_config.strategy.clone(),
+                dry_run,
+                ..BotConfig::default()
+            };
+            if dry_run {
+                let bot = Bot::simulate_live(config).await?;
+                let realized = bot.preview_close(&market_id, &outcome).await?;
+                println!(
+                    "[DRY RUN] Closing {} {} now would realize P&L ${}",
+                    market_id,
+                    outcome,
+                    display::money(realized)
+                );
+                return Ok(());
+            }
+
+            let mut bot = Bot::new(config).await?;
+            bot.initialize().await?;
+            let realized = bot.close_manually(&market_id, &outcome).await?;
+            println!("Closed {} {}: realized P&L ${}", market_id, outcome, display::money(realized));
         }
 
-        Commands::Status => {
-            // Load bot state from database
-            let bot_state = match db.get_bot_state().await {
-                Ok(state) => state,
-                Err(_) => {
+        Commands::DryRunSummary { order_cost, order_cost_pct, output } => {
+            let simulated = db.get_copy_trades_with_status(CopyTradeStatus::Simulated).await?;
+            if simulated.is_empty() {
+                println!("No simulated trades recorded. Run 'polycopier run --dry-run' first.");
+                return Ok(());
+            }
+
+            let summary = DryRunSummary::from_trades(
+                &simulated,
+                Decimal::try_from(order_cost)?,
+                Decimal::try_from(order_cost_pct / 100.0)?,
+            );
+            output::emit(&summary.to_report(), output.as_deref())?;
+        }
+
+        Commands::Status { output, compact, width } => {
+            let Some(snapshot) = StatusSnapshot::load(&db).await? else {
+                if compact {
+                    println!("no session");
+                } else {
                     println!("No bot session found. Run 'polycopier run' to start the bot.");
-                    return Ok(());
                 }
+                return Ok(());
             };
 
-            let (total, executed, failed) = db.get_copy_trade_stats().await.unwrap_or((0, 0, 0));
-            let max_dd = db.calculate_max_drawdown().await.unwrap_or(0.0);
-            let addresses = db.get_tracked_addresses().await?;
+            if compact {
+                println!("{}", snapshot.compact(width));
+            } else {
+                output::emit(&snapshot.to_report(), output.as_deref())?;
+            }
+        }
+
+        Commands::Positions { aging } => {
             let positions = db.get_open_positions().await?;
+            if positions.is_empty() {
+                println!("No open positions.");
+                return Ok(());
+            }
 
-            println!("\n=== Bot Status ===");
-            println!("Running:          {}", if bot_state.is_running { "Yes" } else { "No" });
-            println!("Started:          {}", bot_state.started_at);
-            println!("Last Poll:        {}", bot_state.last_poll_at.unwrap_or_else(|| "Never".to_string()));
-
-            println!("\n=== Portfolio ===");
-            println!("Value:            ${:.2}", bot_state.portfolio_value);
-            println!("Exposure:         ${:.2}", bot_state.current_exposure);
-            println!("Total P&L:        ${:.2}", bot_state.total_pnl);
-            println!("Max Drawdown:     {:.2}%", max_dd * 100.0);
-
-            println!("\n=== Trading ===");
-            println!("Tracked Traders:  {}", addresses.len());
-            println!("Open Positions:   {}", positions.len());
-            println!("Total Trades:     {}", total);
-            println!("Executed:         {}", executed);
-            println!("Failed:           {}", failed);
-
-            if !positions.is_empty() {
-                println!("\n=== Open Positions ===");
+            if !aging {
+                println!("\n=== Open Positions ({}) ===", positions.len());
                 for pos in &positions {
-                    let pnl_sign = if pos.unrealized_pnl >= 0.0 { "+" } else { "" };
                     println!(
-                        "  {} {} @ {:.3} -> {:.3} ({}${:.2})",
+                        "  {} {} {} {:.2} @ {} -> {} (${})",
                         truncate(&pos.market_id, 20),
+                        pos.side,
                         pos.outcome,
-                        pos.entry_price,
-                        pos.current_price,
-                        pnl_sign,
-                        pos.unrealized_pnl
+                        pos.size,
+                        display::price(pos.entry_price),
+                        display::price(pos.current_price),
+                        display::money(pos.unrealized_pnl)
                     );
                 }
+                return Ok(());
+            }
+
+            let strategy = Strategy::new(app_config.strategy.clone());
+            let max_hours = app_config.strategy.max_holding_hours;
+            let strategy_positions: Vec<_> = positions.iter().map(Bot::convert_position).collect();
+
+            println!("\n=== Position Aging ({}) ===", positions.len());
+            println!(
+                "{:<22} {:<6} {:>10} {:>9} {:>9} {:>9} {:>6}",
+                "Market", "Side", "Held", "Return", "To Stop", "To Target", "Risk"
+            );
+            for (pos, risk) in strategy.aging_report(&strategy_positions) {
+                println!(
+                    "{:<22} {:<6} {:>10} {:>8}% {:>8}% {:>8}% {:>6.2}",
+                    truncate(&pos.market_id, 20),
+                    pos.side,
+                    format!("{}h/{}h", risk.holding_hours, max_hours),
+                    display::pct(risk.return_pct),
+                    display::pct(risk.distance_to_stop),
+                    display::pct(risk.distance_to_target),
+                    risk.risk_score
+                );
             }
         }
 
+        Commands::BackfillPnl => {
+            let (backfilled, skipped) = db.backfill_realized_pnl().await?;
+            println!("Backfilled realized P&L for {} closed position(s).", backfilled);
+            if skipped > 0 {
+                println!(
+                    "Skipped {} position(s) with no recorded exit or marked price.",
+                    skipped
+                );
+            }
+
+            let closed = db.get_closed_positions().await?;
+            let realized: f64 = closed.iter().filter_map(|p| p.realized_pnl).sum();
+            println!("Realized P&L across {} closed position(s): ${}", closed.len(), display::money(realized));
+        }
+
+        Commands::Ledger { format, output } => {
+            let closed = db.get_closed_positions().await?;
+            match format {
+                LedgerFormat::Report => output::emit(&ledger::ledger_report(&closed), output.as_deref())?,
+                LedgerFormat::TaxCsv => {
+                    let csv = ledger::tax_report(&closed).render(output::Format::Csv)?;
+                    match output {
+                        None => print!("{}", csv),
+                        Some(path) => {
+                            std::fs::write(&path, csv)
+                                .with_context(|| format!("Failed to write tax lots to {}", path.display()))?;
+                            println!("Wrote tax lots to {}", path.display());
+                        }
+                    }
+                }
+            }
+        }
+
+        Commands::Attribution { by, output } => {
+            let closed = db.get_closed_positions().await?;
+            let rows = match by {
+                AttributionKey::Trader => attribution::attribute(&closed, |p| {
+                    p.source_trader.clone().unwrap_or_else(|| "-".to_string())
+                }),
+                AttributionKey::Category => {
+                    let categories = attribution::market_categories(&db, &ClobClient::read_only()?, &closed).await?;
+                    attribution::attribute(&closed, |p| categories[&p.market_id].clone())
+                }
+            };
+            output::emit(&attribution::attribution_report(by, &rows), output.as_deref())?;
+        }
+
         Commands::Backtest {
             trader,
             capital,
@@ -472,6 +1146,16 @@ async fn main() -> Result<()> {
             fee,
             all,
             relaxed,
+            validate_only,
+            market,
+            outcome,
+            no_cache,
+            refresh_cache,
+            ratios,
+            settle_at_resolution,
+            net_returns,
+            output,
+            save_results,
         } => {
             info!(
                 capital = capital,
@@ -484,7 +1168,7 @@ async fn main() -> Result<()> {
 
             // Build strategy config (relaxed mode widens filters)
             let strategy_config = if relaxed {
-                let mut cfg = StrategyConfig::default();
+                let mut cfg = app_config.strategy.clone();
                 cfg.min_entry_price = dec!(0.01);        // Allow 1%-99% prices
                 cfg.max_entry_price = dec!(0.99);
                 cfg.max_portfolio_drawdown = dec!(0.50); // Allow 50% drawdown
@@ -492,19 +1176,33 @@ async fn main() -> Result<()> {
                 cfg.max_single_market_exposure = dec!(0.50); // Allow 50% per market
                 cfg
             } else {
-                StrategyConfig::default()
+                app_config.strategy.clone()
             };
 
             // Build backtest config
             let backtest_config = BacktestConfig {
                 initial_capital: Decimal::try_from(capital)?,
-                trading_config: TradingConfig::default(),
+                trading_config: app_config.trading.clone(),
                 strategy_config,
                 slippage: Decimal::try_from(slippage / 100.0)?,
                 fee_rate: Decimal::try_from(fee / 100.0)?,
                 lookback_trades: lookback,
+                market_filter: market.clone(),
+                outcome_filter: outcome.clone(),
+                cache_dir: (!no_cache).then(|| PathBuf::from(DEFAULT_CACHE_DIR)),
+                refresh_cache,
+                ratio_basis: ratios,
+                settle_at_resolution,
+                net_trade_returns: net_returns,
+                ..BacktestConfig::default()
             };
 
+            if validate_only {
+                Backtester::new(backtest_config)?;
+                println!("Backtest configuration is valid.");
+                return Ok(());
+            }
+
             let backtester = Backtester::new(backtest_config)?;
 
             if all {
@@ -521,9 +1219,22 @@ async fn main() -> Result<()> {
                 println!("Lookback: {} trades per trader", lookback);
                 println!("Slippage: {}%", slippage);
                 println!("Fee: {}%", fee);
+                if let Some(market) = &market {
+                    println!("Market: {}", market);
+                }
+                if let Some(outcome) = &outcome {
+                    println!("Outcome: {}", outcome);
+                }
                 println!("\nFetching historical data...\n");
 
                 let results = backtester.run_multiple_traders(&addresses).await?;
+                if let Some(path) = &save_results {
+                    results.save(path)?;
+                }
+                if let Some(path) = &output {
+                    output::emit(&results.to_report(), Some(path))?;
+                    return Ok(());
+                }
                 println!("{}", results);
 
                 // Show top trades
@@ -533,11 +1244,12 @@ async fn main() -> Result<()> {
                     sorted.sort_by(|a, b| b.pnl.partial_cmp(&a.pnl).unwrap_or(std::cmp::Ordering::Equal));
                     for trade in sorted.iter().take(5) {
                         println!(
-                            "  {} {} | P&L: ${:.2} ({:.1}%)",
+                            "  {} {} | P&L: ${} ({}% {})",
                             truncate(&trade.market_id, 25),
                             trade.outcome,
-                            trade.pnl,
-                            trade.return_pct * dec!(100)
+                            display::money(trade.pnl),
+                            display::pct(trade.return_pct),
+                            results.trade_return_basis()
                         );
                     }
 
@@ -545,11 +1257,12 @@ async fn main() -> Result<()> {
                     for trade in sorted.iter().rev().take(5) {
                         if trade.pnl < Decimal::ZERO {
                             println!(
-                                "  {} {} | P&L: ${:.2} ({:.1}%)",
+                                "  {} {} | P&L: ${} ({}% {})",
                                 truncate(&trade.market_id, 25),
                                 trade.outcome,
-                                trade.pnl,
-                                trade.return_pct * dec!(100)
+                                display::money(trade.pnl),
+                                display::pct(trade.return_pct),
+                                results.trade_return_basis()
                             );
                         }
                     }
@@ -562,9 +1275,22 @@ async fn main() -> Result<()> {
                 println!("Lookback: {} trades", lookback);
                 println!("Slippage: {}%", slippage);
                 println!("Fee: {}%", fee);
+                if let Some(market) = &market {
+                    println!("Market: {}", market);
+                }
+                if let Some(outcome) = &outcome {
+                    println!("Outcome: {}", outcome);
+                }
                 println!("\nFetching historical data...\n");
 
                 let results = backtester.run_single_trader(&address).await?;
+                if let Some(path) = &save_results {
+                    results.save(path)?;
+                }
+                if let Some(path) = &output {
+                    output::emit(&results.to_report(), Some(path))?;
+                    return Ok(());
+                }
                 println!("{}", results);
 
                 // Show trade breakdown by exit reason
@@ -577,7 +1303,7 @@ async fn main() -> Result<()> {
 
                 println!("\n--- Trades by Exit Reason ---");
                 for (reason, (count, pnl)) in by_reason {
-                    println!("  {:<20} {:>3} trades  ${:.2}", reason, count, pnl);
+                    println!("  {:<20} {:>3} trades  ${}", reason, count, display::money(pnl));
                 }
             } else {
                 println!("Please specify a trader address with --trader or use --all to backtest all tracked traders.");
@@ -587,11 +1313,18 @@ async fn main() -> Result<()> {
             }
         }
 
+        Commands::BacktestDiff { a, b, output } => {
+            let diff = BacktestDiff::from_files(&a, &b)?;
+            output::emit(&diff.to_report(), output.as_deref())?;
+        }
+
         Commands::Paper {
             capital,
             interval,
             slippage,
             fee,
+            price_concurrency,
+            max_runtime,
         } => {
             info!(
                 capital = capital,
@@ -609,13 +1342,15 @@ async fn main() -> Result<()> {
             // Configure paper trader
             let paper_config = PaperConfig {
                 initial_capital: Decimal::try_from(capital)?,
-                trading_config: TradingConfig::default(),
-                strategy_config: StrategyConfig::default(),
+                trading_config: app_config.trading.clone(),
+                strategy_config: app_config.strategy.clone(),
                 slippage: Decimal::try_from(slippage / 100.0)?,
                 fee_rate: Decimal::try_from(fee / 100.0)?,
             };
 
             let mut paper_trader = PaperTrader::new(paper_config);
+            let price_client = ClobClient::read_only()?;
+            let price_chain = app_config.trading.price_chain();
 
             println!("\n=== Paper Trading Mode ===");
             println!("Capital: ${}", capital);
@@ -630,12 +1365,21 @@ async fn main() -> Result<()> {
             for addr in &addresses {
                 let _ = engine.add_trader(addr.clone()).await;
             }
+            for trader in engine.get_tracked_traders().await {
+                paper_trader.seed_source_holdings(&trader.address, &trader.positions);
+            }
 
             // Paper trading loop
             let mut last_poll = std::time::Instant::now();
             let poll_duration = std::time::Duration::from_secs(interval);
+            let deadline = max_runtime.map(|runtime| std::time::Instant::now() + runtime);
 
             loop {
+                if deadline.is_some_and(|d| std::time::Instant::now() >= d) {
+                    println!("\n\nMax runtime reached, stopping paper trading...");
+                    break;
+                }
+
                 if last_poll.elapsed() >= poll_duration {
                     // Fetch new trades from tracked traders
                     for addr in &addresses {
@@ -661,22 +1405,18 @@ async fn main() -> Result<()> {
                         }
                     }
 
-                    // Update equity with current prices (simplified - use entry prices)
-                    let prices: std::collections::HashMap<String, Decimal> = paper_trader
-                        .positions
-                        .iter()
-                        .map(|(k, p)| (k.clone(), p.entry_price))
-                        .collect();
+                    // Update equity with current prices
+                    let prices = paper_prices(&paper_trader, &price_client, &price_chain, price_concurrency).await;
                     paper_trader.update_equity(&prices);
 
                     // Show status
                     let stats = paper_trader.get_stats(&prices);
                     println!(
-                        "[{}] Equity: ${:.2} | P&L: ${:.2} ({:.2}%) | Positions: {} | Trades: {}",
+                        "[{}] Equity: ${} | P&L: ${} ({}%) | Positions: {} | Trades: {}",
                         chrono::Local::now().format("%H:%M:%S"),
-                        stats.current_equity,
-                        stats.total_pnl,
-                        stats.return_pct * dec!(100),
+                        display::money(stats.current_equity),
+                        display::money(stats.total_pnl),
+                        display::pct(stats.return_pct),
                         stats.open_positions,
                         stats.completed_trades
                     );
@@ -695,11 +1435,7 @@ async fn main() -> Result<()> {
             }
 
             // Show final stats
-            let prices: std::collections::HashMap<String, Decimal> = paper_trader
-                .positions
-                .iter()
-                .map(|(k, p)| (k.clone(), p.entry_price))
-                .collect();
+            let prices = paper_prices(&paper_trader, &price_client, &price_chain, price_concurrency).await;
             let stats = paper_trader.get_stats(&prices);
             println!("{}", stats);
         }
@@ -708,6 +1444,52 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Parse a duration like `90s`, `30m`, `6h` or `1d` (bare numbers are seconds).
+fn parse_duration(s: &str) -> Result<std::time::Duration, String> {
+    let s = s.trim();
+    let (value, unit) = s.split_at(s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len()));
+    let value: f64 = value.parse().map_err(|_| format!("Invalid duration: {}", s))?;
+    let secs = match unit {
+        "" | "s" => value,
+        "m" => value * 60.0,
+        "h" => value * 3600.0,
+        "d" => value * 86400.0,
+        _ => return Err(format!("Unknown duration unit {:?} (use s, m, h or d)", unit)),
+    };
+    std::time::Duration::try_from_secs_f64(secs).map_err(|e| format!("Invalid duration {}: {}", s, e))
+}
+
+/// Mark paper positions at live order book mids, falling back to entry price.
+async fn paper_prices(
+    paper_trader: &PaperTrader,
+    clob: &ClobClient,
+    chain: &PriceChain,
+    concurrency: usize,
+) -> std::collections::HashMap<String, Decimal> {
+    let keys = paper_trader.positions.keys().cloned().collect();
+    fetch_prices(keys, concurrency, |key| async move {
+        let pos = &paper_trader.positions[&key];
+        let quote = chain.quote(&key, |source| paper_price_from(clob, pos, source)).await?;
+        Ok(quote.price)
+    })
+    .await
+}
+
+/// Price of a paper position from one source, and when it was observed.
+async fn paper_price_from(
+    clob: &ClobClient,
+    pos: &SimulatedPosition,
+    source: PriceSource,
+) -> Result<(Decimal, chrono::DateTime<chrono::Utc>)> {
+    match source {
+        PriceSource::Stream => anyhow::bail!("Paper trading has no market stream"),
+        PriceSource::ClobBook => Ok((clob.outcome_mid_price(&pos.market_id, &pos.outcome).await?, chrono::Utc::now())),
+        PriceSource::DataApi => anyhow::bail!("Paper trading tracks no trader holdings"),
+        // Paper positions are never re-marked, so the entry price is the last known one
+        PriceSource::LastStored => Ok((pos.entry_price, pos.entry_time)),
+    }
+}
+
 /// Truncate a string with ellipsis if too long.
 fn truncate(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
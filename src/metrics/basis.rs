@@ -0,0 +1,59 @@
+//! Reporting basis for Sharpe/Sortino ratios: annualized or raw per-period.
+
+use serde::{Deserialize, Serialize};
+
+/// Periods per year used to annualize trader metrics (daily P&L).
+pub const TRADER_PERIODS_PER_YEAR: f64 = 365.0;
+
+/// Periods per year used to annualize backtest equity-curve returns.
+pub const BACKTEST_PERIODS_PER_YEAR: f64 = 252.0;
+
+/// How risk ratios are reported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum RatioBasis {
+    /// Scaled by the square root of periods per year
+    #[default]
+    Annualized,
+    /// Mean over deviation of the per-period returns, unscaled
+    Raw,
+}
+
+impl RatioBasis {
+    /// Express a ratio annualized over `periods_per_year` in this basis.
+    pub fn rescale(self, ratio: f64, periods_per_year: f64) -> f64 {
+        match self {
+            Self::Annualized => ratio,
+            Self::Raw => ratio / periods_per_year.sqrt(),
+        }
+    }
+
+    /// Ratio name labeled with its basis, e.g. "Sharpe Ratio (annualized, 365 periods/yr)".
+    pub fn label(self, name: &str, periods_per_year: f64) -> String {
+        match self {
+            Self::Annualized => format!("{} (annualized, {} periods/yr)", name, periods_per_year),
+            Self::Raw => format!("{} (raw, per period)", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_returns_report_differently_by_basis() {
+        let sharpe = 1.5;
+
+        let annualized = RatioBasis::Annualized.rescale(sharpe, TRADER_PERIODS_PER_YEAR);
+        let raw = RatioBasis::Raw.rescale(sharpe, TRADER_PERIODS_PER_YEAR);
+        assert_eq!(annualized, sharpe);
+        assert!((raw * TRADER_PERIODS_PER_YEAR.sqrt() - annualized).abs() < 1e-9);
+        assert_ne!(raw, annualized);
+
+        let annualized_label = RatioBasis::Annualized.label("Sharpe Ratio", TRADER_PERIODS_PER_YEAR);
+        let raw_label = RatioBasis::Raw.label("Sharpe Ratio", TRADER_PERIODS_PER_YEAR);
+        assert_eq!(annualized_label, "Sharpe Ratio (annualized, 365 periods/yr)");
+        assert_eq!(raw_label, "Sharpe Ratio (raw, per period)");
+    }
+}
@@ -1,12 +1,28 @@
 //! Calculator for trader performance metrics: MDD, Sharpe ratio, win rate, etc.
 
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
-use statrs::statistics::Statistics;
 
+use super::TRADER_PERIODS_PER_YEAR;
 use crate::models::{Trade, TradeSide, TraderMetrics};
 
+/// Profit factor reported when there are wins but no losses, and the cap on any
+/// other value, so it stays finite in scores, JSON and comparisons.
+pub const MAX_PROFIT_FACTOR: f64 = 100.0;
+
+/// Gross profit over gross loss, capped at [`MAX_PROFIT_FACTOR`]; 0 with no profit.
+pub fn profit_factor(gross_profit: Decimal, gross_loss: Decimal) -> f64 {
+    if gross_profit <= Decimal::ZERO {
+        return 0.0;
+    }
+    if gross_loss <= Decimal::ZERO {
+        return MAX_PROFIT_FACTOR;
+    }
+    let ratio = gross_profit.to_f64().unwrap_or(0.0) / gross_loss.to_f64().unwrap_or(1.0);
+    ratio.min(MAX_PROFIT_FACTOR)
+}
+
 /// Calculator for computing trader performance metrics.
 pub struct MetricsCalculator;
 
@@ -16,9 +32,36 @@ impl MetricsCalculator {
     /// Requires resolved trades (trades where we know the final outcome)
     /// to accurately compute win/loss statistics.
     pub fn calculate(address: &str, trades: &[Trade], resolved_pnls: &[Decimal]) -> TraderMetrics {
+        Self::calculate_with_weights(address, trades, resolved_pnls, &vec![1.0; resolved_pnls.len()])
+    }
+
+    /// Like `calculate`, but each resolved P&L is weighted by its age at `resolved_at`,
+    /// halving every `half_life`, so win rate, P&L and Sharpe/Sortino favor recent results.
+    pub fn calculate_decayed(
+        address: &str,
+        trades: &[Trade],
+        resolved: &[(DateTime<Utc>, Decimal)],
+        half_life: Duration,
+    ) -> TraderMetrics {
+        let now = Utc::now();
+        let half_life_secs = half_life.num_seconds().max(1) as f64;
+        let weights: Vec<f64> = resolved
+            .iter()
+            .map(|(at, _)| 0.5_f64.powf((now - *at).num_seconds().max(0) as f64 / half_life_secs))
+            .collect();
+        let pnls: Vec<Decimal> = resolved.iter().map(|(_, pnl)| *pnl).collect();
+        Self::calculate_with_weights(address, trades, &pnls, &weights)
+    }
+
+    fn calculate_with_weights(
+        address: &str,
+        trades: &[Trade],
+        resolved_pnls: &[Decimal],
+        weights: &[f64],
+    ) -> TraderMetrics {
         let mut metrics = TraderMetrics::new(address.to_string());
 
-        if trades.is_empty() {
+        if trades.is_empty() && resolved_pnls.is_empty() {
             return metrics;
         }
 
@@ -29,7 +72,7 @@ impl MetricsCalculator {
 
         // Calculate P&L metrics from resolved trades
         if !resolved_pnls.is_empty() {
-            Self::calculate_pnl_metrics(&mut metrics, resolved_pnls);
+            Self::calculate_pnl_metrics(&mut metrics, resolved_pnls, weights);
         }
 
         // Calculate time-based metrics
@@ -43,17 +86,30 @@ impl MetricsCalculator {
     }
 
     /// Calculate P&L-related metrics from resolved trade outcomes.
-    fn calculate_pnl_metrics(metrics: &mut TraderMetrics, pnls: &[Decimal]) {
+    ///
+    /// Win rate, total P&L and Sharpe/Sortino use the per-P&L `weights` (all 1 when unweighted).
+    fn calculate_pnl_metrics(metrics: &mut TraderMetrics, pnls: &[Decimal], weights: &[f64]) {
         let (wins, losses): (Vec<_>, Vec<_>) =
             pnls.iter().partition(|&&p| p > Decimal::ZERO);
 
         metrics.winning_trades = wins.len() as u32;
         metrics.losing_trades = losses.len() as u32;
-        metrics.total_pnl = pnls.iter().copied().sum();
+        metrics.total_pnl = pnls
+            .iter()
+            .zip(weights)
+            .map(|(pnl, w)| *pnl * Decimal::try_from(*w).unwrap_or(Decimal::ONE))
+            .sum();
 
         // Win rate
-        if !pnls.is_empty() {
-            metrics.win_rate = wins.len() as f64 / pnls.len() as f64;
+        let total_weight: f64 = weights.iter().sum();
+        if total_weight > 0.0 {
+            let win_weight: f64 = pnls
+                .iter()
+                .zip(weights)
+                .filter(|(pnl, _)| **pnl > Decimal::ZERO)
+                .map(|(_, w)| w)
+                .sum();
+            metrics.win_rate = win_weight / total_weight;
         }
 
         // Average win/loss
@@ -69,12 +125,7 @@ impl MetricsCalculator {
         // Profit factor
         let gross_profit: Decimal = wins.iter().copied().sum();
         let gross_loss: Decimal = losses.iter().copied().map(|l: Decimal| l.abs()).sum();
-        if gross_loss > Decimal::ZERO {
-            metrics.profit_factor = gross_profit
-                .to_f64()
-                .unwrap_or(0.0)
-                / gross_loss.to_f64().unwrap_or(1.0);
-        }
+        metrics.profit_factor = profit_factor(gross_profit, gross_loss);
 
         // Expectancy
         if !pnls.is_empty() {
@@ -83,7 +134,7 @@ impl MetricsCalculator {
 
         // Calculate drawdown and risk metrics
         Self::calculate_drawdown(metrics, pnls);
-        Self::calculate_sharpe_sortino(metrics, pnls);
+        Self::calculate_sharpe_sortino(metrics, pnls, weights);
     }
 
     /// Calculate maximum drawdown from P&L series.
@@ -133,40 +184,40 @@ impl MetricsCalculator {
     }
 
     /// Calculate Sharpe and Sortino ratios.
-    fn calculate_sharpe_sortino(metrics: &mut TraderMetrics, pnls: &[Decimal]) {
+    fn calculate_sharpe_sortino(metrics: &mut TraderMetrics, pnls: &[Decimal], weights: &[f64]) {
         if pnls.len() < 2 {
             return;
         }
 
-        let returns: Vec<f64> = pnls
+        let (returns, weights): (Vec<f64>, Vec<f64>) = pnls
             .iter()
-            .filter_map(|p| p.to_f64())
-            .collect();
+            .zip(weights)
+            .filter_map(|(p, w)| Some((p.to_f64()?, *w)))
+            .unzip();
 
         if returns.is_empty() {
             return;
         }
 
-        let mean = returns.clone().mean();
-        let std_dev = returns.clone().std_dev();
+        let (mean, std_dev) = weighted_mean_std(&returns, &weights);
 
         // Sharpe ratio (assuming 0% risk-free rate)
-        // Annualized assuming daily returns and 365 trading days
+        // Annualized assuming daily returns
         if std_dev > 0.0 {
-            metrics.sharpe_ratio = (mean / std_dev) * (365.0_f64).sqrt();
+            metrics.sharpe_ratio = (mean / std_dev) * TRADER_PERIODS_PER_YEAR.sqrt();
         }
 
         // Sortino ratio (using downside deviation)
-        let negative_returns: Vec<f64> = returns
+        let (negative_returns, negative_weights): (Vec<f64>, Vec<f64>) = returns
             .iter()
-            .filter(|&&r| r < 0.0)
-            .copied()
-            .collect();
+            .zip(&weights)
+            .filter(|(r, _)| **r < 0.0)
+            .unzip();
 
         if !negative_returns.is_empty() {
-            let downside_dev = negative_returns.std_dev();
+            let (_, downside_dev) = weighted_mean_std(&negative_returns, &negative_weights);
             if downside_dev > 0.0 {
-                metrics.sortino_ratio = (mean / downside_dev) * (365.0_f64).sqrt();
+                metrics.sortino_ratio = (mean / downside_dev) * TRADER_PERIODS_PER_YEAR.sqrt();
             }
         }
     }
@@ -233,6 +284,20 @@ impl MetricsCalculator {
     }
 }
 
+/// Weighted mean and standard deviation, with the sample (n - 1) correction generalized to
+/// reliability weights. NaN deviation when there's too little weight to estimate it.
+fn weighted_mean_std(values: &[f64], weights: &[f64]) -> (f64, f64) {
+    let v1: f64 = weights.iter().sum();
+    let v2: f64 = weights.iter().map(|w| w * w).sum();
+    let mean = values.iter().zip(weights).map(|(x, w)| x * w).sum::<f64>() / v1;
+    let denom = v1 - v2 / v1;
+    if denom <= 0.0 {
+        return (mean, f64::NAN);
+    }
+    let variance = values.iter().zip(weights).map(|(x, w)| w * (x - mean).powi(2)).sum::<f64>() / denom;
+    (mean, variance.sqrt())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -257,6 +322,33 @@ mod tests {
         assert!((metrics.win_rate - 0.6).abs() < 0.001);
     }
 
+    #[test]
+    fn test_no_losses_profit_factor_is_capped_and_displayable() {
+        let metrics = MetricsCalculator::calculate("0x123", &[], &[dec!(100), dec!(50), dec!(25)]);
+        assert!(metrics.profit_factor.is_finite());
+        assert_eq!(metrics.profit_factor, MAX_PROFIT_FACTOR);
+        assert_eq!(crate::display::profit_factor(metrics.profit_factor), ">100");
+        assert!(serde_json::to_string(&metrics).unwrap().contains("\"profit_factor\":100.0"));
+
+        let mixed = MetricsCalculator::calculate("0x123", &[], &[dec!(100), dec!(-40)]);
+        assert_eq!(crate::display::profit_factor(mixed.profit_factor), "2.50");
+        assert_eq!(profit_factor(Decimal::ZERO, Decimal::ZERO), 0.0);
+    }
+
+    #[test]
+    fn test_lower_min_trades_scores_newer_trader() {
+        let pnls: Vec<Decimal> = (0..12).map(|i| if i % 4 == 0 { dec!(-20) } else { dec!(50) }).collect();
+        let mut metrics = MetricsCalculator::calculate("0x123", &[], &pnls);
+        metrics.total_trades = 12;
+
+        let strict = crate::trading::TradingConfig { min_trades_for_score: 20, ..Default::default() };
+        assert_eq!(metrics.composite_score(strict.min_trades_for_score), 0.0);
+
+        let relaxed = crate::trading::TradingConfig { min_trades_for_score: 12, ..strict };
+        assert!(metrics.composite_score(relaxed.min_trades_for_score) > 0.0);
+        assert!(!metrics.is_quality_trader(relaxed.min_trades_for_quality));
+    }
+
     #[test]
     fn test_calculate_drawdown() {
         // Simulate a drawdown scenario
@@ -276,4 +368,37 @@ mod tests {
         assert!(metrics.max_drawdown > 0.65 && metrics.max_drawdown < 0.68);
         assert_eq!(metrics.max_drawdown_usdc, dec!(100));
     }
+
+    #[test]
+    fn test_decay_favors_strong_recent_performance() {
+        let now = Utc::now();
+        // A year ago: mostly losses. This month: mostly wins.
+        let mut resolved = Vec::new();
+        for i in 0..10 {
+            let pnl = if i % 5 == 0 { dec!(20) } else { dec!(-40) };
+            resolved.push((now - Duration::days(365) + Duration::hours(i), pnl));
+        }
+        for i in 0..10 {
+            let pnl = if i % 5 == 0 { dec!(-20) } else { dec!(60) };
+            resolved.push((now - Duration::days(10) + Duration::hours(i), pnl));
+        }
+        let pnls: Vec<Decimal> = resolved.iter().map(|(_, p)| *p).collect();
+
+        let mut flat = MetricsCalculator::calculate("0x123", &[], &pnls);
+        let mut decayed = MetricsCalculator::calculate_decayed("0x123", &[], &resolved, Duration::days(30));
+        // No trades are passed in, so count the resolved ones to clear the trade-count gate
+        flat.total_trades = pnls.len() as u32;
+        decayed.total_trades = pnls.len() as u32;
+
+        assert!((flat.win_rate - 0.5).abs() < 1e-9);
+        assert!(decayed.win_rate > 0.75);
+        assert!(decayed.sharpe_ratio > flat.sharpe_ratio);
+        assert!(decayed.composite_score(10) > flat.composite_score(10));
+
+        // Same-age P&Ls decay equally, matching the unweighted result
+        let fresh: Vec<_> = pnls.iter().map(|p| (now, *p)).collect();
+        let even = MetricsCalculator::calculate_decayed("0x123", &[], &fresh, Duration::days(30));
+        assert!((even.win_rate - flat.win_rate).abs() < 1e-9);
+        assert!((even.sharpe_ratio - flat.sharpe_ratio).abs() < 1e-6);
+    }
 }
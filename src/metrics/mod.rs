@@ -1,5 +1,7 @@
 //! Metrics calculation for trader performance analysis.
 
+mod basis;
 mod calculator;
 
-pub use calculator::MetricsCalculator;
+pub use basis::{RatioBasis, BACKTEST_PERIODS_PER_YEAR, TRADER_PERIODS_PER_YEAR};
+pub use calculator::{profit_factor, MetricsCalculator, MAX_PROFIT_FACTOR};
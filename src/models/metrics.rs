@@ -4,6 +4,12 @@ use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+/// Default trade count below which a trader's composite score is 0.
+pub const DEFAULT_MIN_TRADES_FOR_SCORE: u32 = 10;
+
+/// Default trade count required to count as a quality trader.
+pub const DEFAULT_MIN_TRADES_FOR_QUALITY: u32 = 20;
+
 /// Comprehensive performance metrics for a trader.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraderMetrics {
@@ -124,8 +130,10 @@ impl TraderMetrics {
     /// - Low drawdown: 25%
     /// - Profitability: 15%
     /// - Recent momentum: 10%
-    pub fn composite_score(&self) -> f64 {
-        if self.total_trades < 10 {
+    ///
+    /// Traders with fewer than `min_trades` trades score 0.
+    pub fn composite_score(&self, min_trades: u32) -> f64 {
+        if self.total_trades < min_trades {
             return 0.0;
         }
 
@@ -161,31 +169,38 @@ impl TraderMetrics {
             return 0.0;
         }
 
-        // Kelly criterion: f* = (bp - q) / b
-        // where b = avg_win/avg_loss, p = win_rate, q = 1 - p
-        let avg_win_f64: f64 = self.avg_win.try_into().unwrap_or(0.0);
-        let avg_loss_f64: f64 = self.avg_loss.try_into().unwrap_or(1.0);
+        // Cap at 25% Kelly for safety and apply drawdown penalty
+        let capped_kelly = self.kelly().min(0.25);
+        let drawdown_penalty = 1.0 - self.max_drawdown;
 
-        if avg_loss_f64 <= 0.0 {
-            return 0.0;
-        }
+        capped_kelly * drawdown_penalty
+    }
 
-        let b = avg_win_f64 / avg_loss_f64;
+    /// Full Kelly fraction: f* = (bp - q) / b, where b = avg_win/avg_loss,
+    /// p = win_rate, q = 1 - p. Never negative.
+    ///
+    /// With wins but no losses b is unbounded and f* tends to p, rather than
+    /// treating a perfect record as having no edge.
+    pub fn kelly(&self) -> f64 {
+        let avg_win: f64 = self.avg_win.try_into().unwrap_or(0.0);
+        let avg_loss: f64 = self.avg_loss.try_into().unwrap_or(0.0);
         let p = self.win_rate;
         let q = 1.0 - p;
 
-        let kelly = (b * p - q) / b;
-
-        // Cap at 25% Kelly for safety and apply drawdown penalty
-        let capped_kelly = kelly.max(0.0).min(0.25);
-        let drawdown_penalty = 1.0 - self.max_drawdown;
+        if avg_win <= 0.0 {
+            return 0.0;
+        }
+        if avg_loss <= 0.0 {
+            return p;
+        }
 
-        capped_kelly * drawdown_penalty
+        let b = avg_win / avg_loss;
+        ((b * p - q) / b).max(0.0)
     }
 
-    /// Check if metrics indicate a trader worth following.
-    pub fn is_quality_trader(&self) -> bool {
-        self.total_trades >= 20
+    /// Check if metrics indicate a trader worth following, with at least `min_trades` trades.
+    pub fn is_quality_trader(&self, min_trades: u32) -> bool {
+        self.total_trades >= min_trades
             && self.win_rate >= 0.52
             && self.sharpe_ratio >= 0.3
             && self.max_drawdown <= 0.5
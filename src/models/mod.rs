@@ -9,5 +9,5 @@ mod market;
 pub use trade::{Trade, TradeSide};
 pub use trader::Trader;
 pub use position::Position;
-pub use metrics::TraderMetrics;
+pub use metrics::{TraderMetrics, DEFAULT_MIN_TRADES_FOR_QUALITY, DEFAULT_MIN_TRADES_FOR_SCORE};
 pub use market::Market;
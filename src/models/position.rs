@@ -20,6 +20,10 @@ pub struct Position {
     /// Outcome token held (e.g., "Yes", "No")
     pub outcome: String,
 
+    /// Index of the outcome within its market, when known
+    #[serde(default)]
+    pub outcome_index: Option<i32>,
+
     /// Number of outcome tokens held
     pub size: Decimal,
 
@@ -65,6 +69,7 @@ impl Position {
             market_id,
             market_title: String::new(),
             outcome,
+            outcome_index: None,
             size,
             average_price: price,
             current_price: price,
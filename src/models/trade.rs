@@ -24,7 +24,8 @@ impl TradeSide {
 /// Individual trade record from Polymarket.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Trade {
-    /// Unique trade identifier (typically tx_hash + log_index)
+    /// Unique trade identifier: tx_hash + timestamp + the fill's token, side, size and
+    /// price, so fills sharing a transaction stay distinct and stable across fetches
     pub id: String,
 
     /// Trader's wallet address
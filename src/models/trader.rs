@@ -1,11 +1,12 @@
 //! Trader model representing a Polymarket trader profile.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 use super::metrics::TraderMetrics;
 use super::position::Position;
+use super::trade::{Trade, TradeSide};
 
 /// Trader profile with metrics and tracking status.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,13 +30,25 @@ pub struct Trader {
     #[serde(default)]
     pub is_tracked: bool,
 
+    /// Tracked, but new trades aren't copied until resumed
+    #[serde(default)]
+    pub is_paused: bool,
+
     /// When we started tracking this trader
     pub tracking_since: Option<DateTime<Utc>>,
 
+    /// Time of the newest trade seen from this trader
+    #[serde(default)]
+    pub last_trade_seen_at: Option<DateTime<Utc>>,
+
     /// Current open positions
     #[serde(default)]
     pub positions: Vec<Position>,
 
+    /// When `positions` was last refreshed from the API
+    #[serde(default)]
+    pub positions_as_of: Option<DateTime<Utc>>,
+
     /// Calculated performance metrics
     pub metrics: Option<TraderMetrics>,
 
@@ -57,8 +70,11 @@ impl Trader {
             profile_image: String::new(),
             bio: String::new(),
             is_tracked: false,
+            is_paused: false,
             tracking_since: None,
+            last_trade_seen_at: None,
             positions: Vec::new(),
+            positions_as_of: None,
             metrics: None,
             allocation_weight: Decimal::ONE,
         }
@@ -81,14 +97,56 @@ impl Trader {
         self.tracking_since = Some(Utc::now());
     }
 
+    /// Record a trade seen at `at`, keeping the newest.
+    pub fn saw_trade_at(&mut self, at: DateTime<Utc>) {
+        if !matches!(self.last_trade_seen_at, Some(seen) if seen >= at) {
+            self.last_trade_seen_at = Some(at);
+        }
+    }
+
+    /// Whether no trade has been seen for longer than `window`, counting from when
+    /// tracking started if none has been seen at all.
+    pub fn is_inactive(&self, now: DateTime<Utc>, window: Duration) -> bool {
+        self.last_trade_seen_at
+            .or(self.tracking_since)
+            .is_some_and(|since| now - since > window)
+    }
+
+    /// Replace current holdings with a fresh snapshot.
+    pub fn set_positions(&mut self, positions: Vec<Position>) {
+        self.positions = positions;
+        self.positions_as_of = Some(Utc::now());
+    }
+
+    /// Whether this trader held `trade`'s market before making it. Holdings fetched
+    /// after the trade already include it, so its own shares don't count then.
+    pub fn held_market_before(&self, trade: &Trade) -> bool {
+        let fetched_after = self.positions_as_of.is_none_or(|as_of| as_of >= trade.timestamp);
+        self.positions.iter().filter(|p| p.market_id == trade.market_id).any(|p| {
+            let includes_trade = fetched_after
+                && matches!(trade.side, TradeSide::Buy)
+                && p.outcome.eq_ignore_ascii_case(&trade.outcome);
+            p.size > if includes_trade { trade.size } else { Decimal::ZERO }
+        })
+    }
+
+    /// Unrealized P&L of this trader's position in a market outcome, as of the last refresh.
+    pub fn position_pnl(&self, market_id: &str, outcome: &str) -> Option<Decimal> {
+        let position = self
+            .positions
+            .iter()
+            .find(|p| p.market_id == market_id && p.outcome.eq_ignore_ascii_case(outcome))?;
+        Some((position.current_price - position.average_price) * position.size)
+    }
+
     /// Stop tracking this trader.
     pub fn stop_tracking(&mut self) {
         self.is_tracked = false;
     }
 
-    /// Get trader's composite score for ranking.
-    pub fn score(&self) -> f64 {
-        self.metrics.as_ref().map(|m| m.composite_score()).unwrap_or(0.0)
+    /// Get trader's composite score for ranking (0 below `min_trades` trades).
+    pub fn score(&self, min_trades: u32) -> f64 {
+        self.metrics.as_ref().map(|m| m.composite_score(min_trades)).unwrap_or(0.0)
     }
 
     /// Check if trader meets minimum requirements for copying.
@@ -0,0 +1,244 @@
+//! Report output: render a command's report as text, JSON, CSV or Markdown,
+//! to stdout or to a file whose extension picks the format.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+
+/// Output format, inferred from a file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl Format {
+    /// Pick a format from the path's extension, defaulting to text.
+    pub fn from_path(path: &Path) -> Self {
+        let ext = path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase);
+        match ext.as_deref() {
+            Some("json") => Self::Json,
+            Some("csv") => Self::Csv,
+            Some("md") | Some("markdown") => Self::Markdown,
+            _ => Self::Text,
+        }
+    }
+}
+
+/// Named group of key/value fields.
+#[derive(Debug, Clone)]
+pub struct Section {
+    pub name: String,
+    pub fields: Vec<(String, String)>,
+}
+
+/// Named table with a header row.
+#[derive(Debug, Clone)]
+pub struct Table {
+    pub name: String,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// A command's report: titled sections of fields, followed by tables.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub title: String,
+    pub sections: Vec<Section>,
+    pub tables: Vec<Table>,
+}
+
+impl Report {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self { title: title.into(), sections: Vec::new(), tables: Vec::new() }
+    }
+
+    /// Add a field to the named section, creating the section on first use.
+    pub fn field(&mut self, section: &str, key: &str, value: impl ToString) -> &mut Self {
+        let index = match self.sections.iter().position(|s| s.name == section) {
+            Some(i) => i,
+            None => {
+                self.sections.push(Section { name: section.to_string(), fields: Vec::new() });
+                self.sections.len() - 1
+            }
+        };
+        self.sections[index].fields.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Add a table.
+    pub fn table(&mut self, name: &str, headers: &[&str], rows: Vec<Vec<String>>) -> &mut Self {
+        self.tables.push(Table {
+            name: name.to_string(),
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows,
+        });
+        self
+    }
+
+    /// Render the report in the given format.
+    pub fn render(&self, format: Format) -> Result<String> {
+        Ok(match format {
+            Format::Text => self.to_text(),
+            Format::Json => serde_json::to_string_pretty(&self.to_json())? + "\n",
+            Format::Csv => self.to_csv(),
+            Format::Markdown => self.to_markdown(),
+        })
+    }
+
+    fn to_text(&self) -> String {
+        let mut out = format!("\n=== {} ===\n", self.title);
+        for section in &self.sections {
+            out += &format!("\n--- {} ---\n", section.name);
+            let width = section.fields.iter().map(|(k, _)| k.len()).max().unwrap_or(0) + 1;
+            for (key, value) in &section.fields {
+                out += &format!("{:<width$} {}\n", format!("{}:", key), value, width = width);
+            }
+        }
+        for table in &self.tables {
+            out += &format!("\n--- {} ({}) ---\n", table.name, table.rows.len());
+            for row in &table.rows {
+                out += &format!("  {}\n", row.join(" | "));
+            }
+        }
+        out
+    }
+
+    fn to_json(&self) -> Value {
+        let mut root = Map::new();
+        root.insert("title".to_string(), Value::String(self.title.clone()));
+        for section in &self.sections {
+            let fields = section
+                .fields
+                .iter()
+                .map(|(k, v)| (k.clone(), json_scalar(v)))
+                .collect();
+            root.insert(section.name.clone(), Value::Object(fields));
+        }
+        for table in &self.tables {
+            let rows = table
+                .rows
+                .iter()
+                .map(|row| {
+                    let cells = table.headers.iter().cloned().zip(row.iter().map(|c| json_scalar(c)));
+                    Value::Object(cells.collect())
+                })
+                .collect();
+            root.insert(table.name.clone(), Value::Array(rows));
+        }
+        Value::Object(root)
+    }
+
+    fn to_csv(&self) -> String {
+        let mut out = String::new();
+        if !self.sections.is_empty() {
+            out += "section,field,value\n";
+            for section in &self.sections {
+                for (key, value) in &section.fields {
+                    out += &csv_row(&[&section.name, key, value]);
+                }
+            }
+        }
+        for table in &self.tables {
+            if !out.is_empty() {
+                out += "\n";
+            }
+            let headers: Vec<&str> = table.headers.iter().map(String::as_str).collect();
+            out += &csv_row(&headers);
+            for row in &table.rows {
+                let cells: Vec<&str> = row.iter().map(String::as_str).collect();
+                out += &csv_row(&cells);
+            }
+        }
+        out
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n", self.title);
+        for section in &self.sections {
+            out += &format!("\n## {}\n\n| Field | Value |\n| --- | --- |\n", section.name);
+            for (key, value) in &section.fields {
+                out += &format!("| {} | {} |\n", key, value.replace('|', "\\|"));
+            }
+        }
+        for table in &self.tables {
+            out += &format!("\n## {}\n\n| {} |\n", table.name, table.headers.join(" | "));
+            out += &format!("|{}\n", " --- |".repeat(table.headers.len()));
+            for row in &table.rows {
+                let cells: Vec<String> = row.iter().map(|c| c.replace('|', "\\|")).collect();
+                out += &format!("| {} |\n", cells.join(" | "));
+            }
+        }
+        out
+    }
+}
+
+/// Print the report as text, or write it to `path` in the format its extension implies.
+pub fn emit(report: &Report, path: Option<&Path>) -> Result<()> {
+    match path {
+        None => print!("{}", report.render(Format::Text)?),
+        Some(path) => {
+            let contents = report.render(Format::from_path(path))?;
+            std::fs::write(path, contents)
+                .with_context(|| format!("Failed to write report to {}", path.display()))?;
+            println!("Wrote {} to {}", report.title, path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Numbers and booleans stay typed in JSON; everything else is a string.
+fn json_scalar(value: &str) -> Value {
+    if let Ok(b) = value.parse::<bool>() {
+        return Value::Bool(b);
+    }
+    match value.parse::<f64>().ok().and_then(serde_json::Number::from_f64) {
+        Some(n) => Value::Number(n),
+        None => Value::String(value.to_string()),
+    }
+}
+
+fn csv_row(cells: &[&str]) -> String {
+    let escaped: Vec<String> = cells
+        .iter()
+        .map(|c| {
+            if c.contains([',', '"', '\n']) {
+                format!("\"{}\"", c.replace('"', "\"\""))
+            } else {
+                c.to_string()
+            }
+        })
+        .collect();
+    escaped.join(",") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_output_file_is_parseable() {
+        let mut report = Report::new("Bot Status");
+        report.field("Portfolio", "Value ($)", "1000.00").field("Portfolio", "Running", true);
+        report.table("Open Positions", &["market", "outcome", "pnl"], vec![
+            vec!["0xabc".to_string(), "Yes, really".to_string(), "-1.50".to_string()],
+        ]);
+
+        let path = std::env::temp_dir().join(format!("polycopier-report-{}.json", uuid::Uuid::new_v4()));
+        emit(&report, Some(&path)).unwrap();
+
+        let parsed: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(parsed["title"], "Bot Status");
+        assert_eq!(parsed["Portfolio"]["Value ($)"], 1000.0);
+        assert_eq!(parsed["Portfolio"]["Running"], true);
+        assert_eq!(parsed["Open Positions"][0]["outcome"], "Yes, really");
+
+        assert_eq!(Format::from_path(Path::new("r.CSV")), Format::Csv);
+        assert!(report.render(Format::Csv).unwrap().contains("\"Yes, really\""));
+
+        std::fs::remove_file(&path).ok();
+    }
+}
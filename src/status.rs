@@ -0,0 +1,193 @@
+//! Bot status, as a full report or a one-line summary for status bars.
+
+use anyhow::Result;
+use chrono::Utc;
+
+use crate::db::{BotState, Database, StoredPosition};
+use crate::display;
+use crate::output::Report;
+
+/// Default maximum width of the compact status line.
+pub const DEFAULT_COMPACT_WIDTH: usize = 60;
+
+/// Everything `status` shows, loaded once for either format.
+pub struct StatusSnapshot {
+    pub state: BotState,
+    pub run_state: &'static str,
+    pub gross_exposure: f64,
+    pub net_exposure: f64,
+    pub max_drawdown: f64,
+    pub tracked_traders: usize,
+    pub positions: Vec<StoredPosition>,
+    /// (total, executed, failed) copy trades
+    pub trade_counts: (i64, i64, i64),
+}
+
+impl StatusSnapshot {
+    /// Load the current status; `None` if no bot session has been recorded.
+    pub async fn load(db: &Database) -> Result<Option<Self>> {
+        let Ok(state) = db.get_bot_state().await else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self {
+            run_state: state.run_state(Utc::now()),
+            state,
+            gross_exposure: db.get_gross_exposure().await?,
+            net_exposure: db.get_net_exposure().await?,
+            max_drawdown: db.calculate_max_drawdown().await.unwrap_or(0.0),
+            tracked_traders: db.get_tracked_addresses().await?.len(),
+            positions: db.get_open_positions().await?,
+            trade_counts: db.get_copy_trade_stats().await.unwrap_or((0, 0, 0)),
+        }))
+    }
+
+    /// Total P&L as a fraction of the starting portfolio value.
+    fn pnl_pct(&self) -> f64 {
+        let start = self.state.portfolio_value - self.state.total_pnl;
+        if start > 0.0 {
+            self.state.total_pnl / start
+        } else {
+            0.0
+        }
+    }
+
+    /// Full multi-section status report.
+    pub fn to_report(&self) -> Report {
+        let state = &self.state;
+        let (total, executed, failed) = self.trade_counts;
+
+        let mut report = Report::new("Bot Status");
+        report
+            .field("Bot", "State", self.run_state)
+            .field("Bot", "Heartbeat", state.heartbeat_at.as_deref().unwrap_or("Never"))
+            .field("Bot", "Started", &state.started_at)
+            .field("Bot", "Last Poll", state.last_poll_at.as_deref().unwrap_or("Never"))
+            .field("Portfolio", "Value ($)", display::money(state.portfolio_value))
+            .field("Portfolio", "Exposure ($)", display::money(state.current_exposure))
+            .field("Portfolio", "Gross Exposure ($)", display::money(self.gross_exposure))
+            .field("Portfolio", "Net Exposure ($)", display::money(self.net_exposure))
+            .field("Portfolio", "Total P&L ($)", display::money(state.total_pnl))
+            .field("Portfolio", "Max Drawdown (%)", display::pct(self.max_drawdown))
+            .field("Trading", "Tracked Traders", self.tracked_traders)
+            .field("Trading", "Open Positions", self.positions.len())
+            .field("Trading", "Total Trades", total)
+            .field("Trading", "Executed", executed)
+            .field("Trading", "Failed", failed);
+
+        let rows = self
+            .positions
+            .iter()
+            .map(|pos| {
+                vec![
+                    pos.market_id.clone(),
+                    pos.outcome.clone(),
+                    display::price(pos.entry_price),
+                    display::price(pos.current_price),
+                    display::money(pos.unrealized_pnl),
+                ]
+            })
+            .collect();
+        report.table(
+            "Open Positions",
+            &["market_id", "outcome", "entry_price", "current_price", "unrealized_pnl"],
+            rows,
+        );
+        report
+    }
+
+    /// One `|`-separated line, e.g. `P&L +$123.45 (2.1%) | pos 4 | dd 3.2% | running`.
+    /// Trailing fields are dropped whole to fit `width`; the first is cut if it alone doesn't.
+    pub fn compact(&self, width: usize) -> String {
+        let pnl = self.state.total_pnl;
+        let sign = if pnl < 0.0 { "-" } else { "+" };
+        let run_state = self.run_state.split_whitespace().next().unwrap_or_default().to_lowercase();
+        let fields = [
+            format!("P&L {}${} ({:.1}%)", sign, display::money(pnl.abs()), self.pnl_pct() * 100.0),
+            format!("pos {}", self.positions.len()),
+            format!("dd {:.1}%", self.max_drawdown * 100.0),
+            run_state,
+        ];
+
+        let mut line = String::new();
+        for field in &fields {
+            let next = if line.is_empty() { field.clone() } else { format!("{} | {}", line, field) };
+            if next.chars().count() > width {
+                break;
+            }
+            line = next;
+        }
+        if line.is_empty() {
+            line = fields[0].chars().take(width).collect();
+        }
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(total_pnl: f64, positions: usize, max_drawdown: f64) -> StatusSnapshot {
+        let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let position = StoredPosition {
+            id: 1,
+            market_id: "0xmarket".to_string(),
+            market_title: String::new(),
+            outcome: "Yes".to_string(),
+            side: "BUY".to_string(),
+            size: 10.0,
+            entry_price: 0.5,
+            current_price: 0.5,
+            unrealized_pnl: 0.0,
+            source_trader: None,
+            opened_at: now.clone(),
+            closed_at: None,
+            exit_price: None,
+            realized_pnl: None,
+            exit_reason: None,
+            peak_price: None,
+            entries: 1,
+            costs: None,
+        };
+
+        StatusSnapshot {
+            state: BotState {
+                id: 1,
+                portfolio_value: 6000.0 + total_pnl,
+                current_exposure: 0.0,
+                total_pnl,
+                total_trades: 0,
+                peak_equity: 6000.0,
+                is_running: true,
+                last_poll_at: None,
+                started_at: now.clone(),
+                updated_at: now.clone(),
+                heartbeat_at: Some(now),
+                poll_interval_secs: 60,
+                live_started_at: None,
+            },
+            run_state: "Running",
+            gross_exposure: 0.0,
+            net_exposure: 0.0,
+            max_drawdown,
+            tracked_traders: 1,
+            positions: vec![position; positions],
+            trade_counts: (0, 0, 0),
+        }
+    }
+
+    #[test]
+    fn test_compact_status_orders_pnl_positions_drawdown() {
+        let line = snapshot(123.45, 4, 0.032).compact(DEFAULT_COMPACT_WIDTH);
+        assert_eq!(line, "P&L +$123.45 (2.1%) | pos 4 | dd 3.2% | running");
+
+        let pnl = line.find("P&L").unwrap();
+        let pos = line.find("pos").unwrap();
+        let dd = line.find("dd").unwrap();
+        assert!(pnl < pos && pos < dd);
+
+        // Narrow bars drop whole fields from the end
+        assert_eq!(snapshot(-50.0, 2, 0.1).compact(30), "P&L -$50.00 (-0.8%) | pos 2");
+    }
+}
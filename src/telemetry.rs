@@ -0,0 +1,133 @@
+//! Run-loop metrics, defined once for any exporter, and a StatsD/DogStatsD UDP emitter.
+//!
+//! Nothing is sent unless the bot is started with `--statsd-addr`.
+
+use anyhow::{Context, Result};
+use tokio::net::UdpSocket;
+use tracing::debug;
+
+/// Prefix for every metric name.
+pub const NAMESPACE: &str = "polycopier";
+
+/// How a metric's values combine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    /// Current value, replacing the last one
+    Gauge,
+    /// Increment since the last report
+    Counter,
+    /// Duration in milliseconds
+    Timing,
+}
+
+impl MetricKind {
+    fn statsd_type(self) -> &'static str {
+        match self {
+            Self::Gauge => "g",
+            Self::Counter => "c",
+            Self::Timing => "ms",
+        }
+    }
+}
+
+/// A named metric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Metric {
+    pub name: &'static str,
+    pub kind: MetricKind,
+}
+
+pub const PORTFOLIO_VALUE: Metric = Metric { name: "portfolio_value_usdc", kind: MetricKind::Gauge };
+pub const EXPOSURE: Metric = Metric { name: "exposure_usdc", kind: MetricKind::Gauge };
+pub const TOTAL_PNL: Metric = Metric { name: "total_pnl_usdc", kind: MetricKind::Gauge };
+pub const OPEN_POSITIONS: Metric = Metric { name: "open_positions", kind: MetricKind::Gauge };
+pub const TRADES_EXECUTED: Metric = Metric { name: "trades_executed", kind: MetricKind::Counter };
+pub const TRADES_FAILED: Metric = Metric { name: "trades_failed", kind: MetricKind::Counter };
+pub const TICK_LATENCY: Metric = Metric { name: "tick_latency", kind: MetricKind::Timing };
+
+/// Format samples as one StatsD packet, a `namespace.name:value|type` line each.
+///
+/// StatsD reads a signed gauge as a change to the last value, so negative gauges are
+/// sent as a reset to zero followed by the decrement.
+pub fn statsd_packet(samples: &[(Metric, f64)]) -> String {
+    let mut lines = Vec::new();
+    for (metric, value) in samples {
+        let kind = metric.kind.statsd_type();
+        if metric.kind == MetricKind::Gauge && *value < 0.0 {
+            lines.push(format!("{}.{}:0|{}", NAMESPACE, metric.name, kind));
+        }
+        lines.push(format!("{}.{}:{}|{}", NAMESPACE, metric.name, value, kind));
+    }
+    lines.join("\n")
+}
+
+/// Pushes metrics to a StatsD (or DogStatsD) collector over UDP.
+pub struct StatsdEmitter {
+    socket: UdpSocket,
+}
+
+impl StatsdEmitter {
+    /// Open a socket to the collector at `addr` (host:port).
+    pub async fn connect(addr: &str) -> Result<Self> {
+        let target = tokio::net::lookup_host(addr)
+            .await
+            .with_context(|| format!("Invalid StatsD address {}", addr))?
+            .next()
+            .with_context(|| format!("StatsD address {} did not resolve", addr))?;
+        let local = if target.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+        let socket = UdpSocket::bind(local).await.context("Failed to open StatsD socket")?;
+        socket.connect(target).await.context("Failed to connect StatsD socket")?;
+        Ok(Self { socket })
+    }
+
+    /// Send samples best-effort; a missing collector never interrupts trading.
+    pub async fn send(&self, samples: &[(Metric, f64)]) {
+        if samples.is_empty() {
+            return;
+        }
+        if let Err(e) = self.socket.send(statsd_packet(samples).as_bytes()).await {
+            debug!(error = %e, "Failed to send StatsD metrics");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statsd_packet_formats_core_metrics() {
+        let packet = statsd_packet(&[
+            (PORTFOLIO_VALUE, 1000.5),
+            (EXPOSURE, 250.0),
+            (TOTAL_PNL, -12.25),
+            (OPEN_POSITIONS, 3.0),
+            (TRADES_EXECUTED, 2.0),
+            (TRADES_FAILED, 0.0),
+            (TICK_LATENCY, 184.0),
+        ]);
+
+        let lines: Vec<&str> = packet.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "polycopier.portfolio_value_usdc:1000.5|g",
+                "polycopier.exposure_usdc:250|g",
+                "polycopier.total_pnl_usdc:0|g",
+                "polycopier.total_pnl_usdc:-12.25|g",
+                "polycopier.open_positions:3|g",
+                "polycopier.trades_executed:2|c",
+                "polycopier.trades_failed:0|c",
+                "polycopier.tick_latency:184|ms",
+            ]
+        );
+        for line in lines {
+            let (name, rest) = line.split_once(':').unwrap();
+            let (value, kind) = rest.split_once('|').unwrap();
+            assert!(!name.contains([':', '|', '@']));
+            assert!(value.parse::<f64>().is_ok());
+            assert!(["g", "c", "ms"].contains(&kind));
+        }
+        assert!(statsd_packet(&[]).is_empty());
+    }
+}
@@ -0,0 +1,359 @@
+//! Test harness: a mock Polymarket server serving canned Data, CLOB and Gamma API responses.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use wiremock::matchers::{method, path, path_regex, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::api::{ClobClient, DataClient};
+use crate::clock::Clock;
+
+/// Well-known development key (Hardhat account #0), never funded on Polygon.
+pub const TEST_PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+/// Largest ERC-20 approval, as granted by an unlimited `approve`.
+const U256_MAX: &str = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+/// Mock server standing in for every Polymarket API the bot talks to.
+pub struct MockPolymarket {
+    server: MockServer,
+}
+
+impl MockPolymarket {
+    /// Start a new mock server on a random local port.
+    pub async fn start() -> Self {
+        Self { server: MockServer::start().await }
+    }
+
+    /// Drop all mounted responses and recorded requests.
+    pub async fn reset(&self) {
+        self.server.reset().await;
+    }
+
+    /// Number of order submissions received so far.
+    pub async fn order_submissions(&self) -> usize {
+        self.server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|r| r.method.as_str() == "POST" && r.url.path() == "/order")
+            .count()
+    }
+
+    /// Bodies of the order submissions received so far, in order.
+    pub async fn submitted_orders(&self) -> Vec<Value> {
+        self.server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|r| r.method.as_str() == "POST" && r.url.path() == "/order")
+            .filter_map(|r| serde_json::from_slice(&r.body).ok())
+            .collect()
+    }
+
+    /// Number of trade history requests received so far.
+    pub async fn trade_fetches(&self) -> usize {
+        self.server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|r| r.method.as_str() == "GET" && r.url.path() == "/trades")
+            .count()
+    }
+
+    /// Number of market info requests received so far.
+    pub async fn market_fetches(&self) -> usize {
+        self.server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|r| r.method.as_str() == "GET" && r.url.path().starts_with("/markets/"))
+            .count()
+    }
+
+    /// Data API client pointed at the mock server.
+    pub fn data_client(&self) -> DataClient {
+        DataClient::with_base_url(self.server.uri()).expect("data client")
+    }
+
+    /// CLOB client pointed at the mock server for both CLOB and Gamma endpoints.
+    pub fn clob_client(&self) -> ClobClient {
+        ClobClient::new(TEST_PRIVATE_KEY, "key", "c2VjcmV0", "passphrase", 137)
+            .expect("clob client")
+            .with_urls(self.server.uri(), self.server.uri())
+    }
+
+    /// Serve a leaderboard page.
+    pub async fn mount_leaderboard(&self, entries: Vec<Value>) {
+        Mock::given(method("GET"))
+            .and(path("/v1/leaderboard"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(entries))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Serve a trader's open positions.
+    pub async fn mount_positions(&self, address: &str, positions: Vec<Value>) {
+        Mock::given(method("GET"))
+            .and(path("/positions"))
+            .and(query_param("user", address))
+            .respond_with(ResponseTemplate::new(200).set_body_json(positions))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Serve a trader's trade history. With `times`, the response is only served
+    /// that many times, letting later mounts simulate newly appearing trades.
+    pub async fn mount_trades(&self, address: &str, trades: Vec<Value>, times: Option<u64>) {
+        let mut mock = Mock::given(method("GET"))
+            .and(path("/trades"))
+            .and(query_param("user", address))
+            .respond_with(ResponseTemplate::new(200).set_body_json(trades));
+        if let Some(n) = times {
+            mock = mock.up_to_n_times(n);
+        }
+        mock.mount(&self.server).await;
+    }
+
+    /// Serve Gamma market info for a two-outcome market.
+    pub async fn mount_market(&self, condition_id: &str, resolved: bool) {
+        let body = json!({
+            "conditionId": condition_id,
+            "questionId": format!("{}-question", condition_id),
+            "tokens": [
+                { "tokenId": "1", "outcome": "Yes", "winner": resolved },
+                { "tokenId": "2", "outcome": "No", "winner": false }
+            ]
+        });
+        Mock::given(method("GET"))
+            .and(path(format!("/markets/{}", condition_id)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Serve a market that resolved to `winner` and closed at `closed_at`, a day after
+    /// its scheduled end.
+    pub async fn mount_resolution(&self, condition_id: &str, winner: &str, closed_at: DateTime<Utc>) {
+        let tokens: Vec<Value> = ["Yes", "No"]
+            .iter()
+            .enumerate()
+            .map(|(i, outcome)| json!({ "tokenId": (i + 1).to_string(), "outcome": outcome, "winner": *outcome == winner }))
+            .collect();
+        let body = json!({
+            "conditionId": condition_id,
+            "questionId": format!("{}-question", condition_id),
+            "tokens": tokens,
+            "endDate": (closed_at - chrono::Duration::days(1)).to_rfc3339(),
+            "closedTime": closed_at.format("%Y-%m-%d %H:%M:%S+00").to_string()
+        });
+        Mock::given(method("GET"))
+            .and(path(format!("/markets/{}", condition_id)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Accept a cancel-all request, asserting (when the server drops) how many were received.
+    pub async fn expect_cancel_all(&self, times: u64) {
+        Mock::given(method("DELETE"))
+            .and(path("/orders"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "canceled": [] })))
+            .expect(times)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Accept order submissions after `delay`, asserting (when the server drops) how many were received.
+    pub async fn expect_order_submission(&self, delay: Duration, times: u64) {
+        Mock::given(method("POST"))
+            .and(path("/order"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "orderId": "0xmock", "success": true, "status": "live" }))
+                    .set_delay(delay),
+            )
+            .expect(times)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Reject every order submission with a server error.
+    pub async fn reject_order_submissions(&self) {
+        Mock::given(method("POST"))
+            .and(path("/order"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("internal error"))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Serve `order` for any direct order lookup by id.
+    pub async fn mount_order_lookup(&self, order: Value) {
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/order/[^/]+$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(order))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Serve the wallet's open orders.
+    pub async fn mount_open_orders(&self, orders: Vec<Value>) {
+        Mock::given(method("GET"))
+            .and(path("/orders"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(orders))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Serve the wallet's USDC collateral balance.
+    pub async fn mount_balance(&self, usdc: f64) {
+        self.mount_balance_allowance(usdc, None).await;
+    }
+
+    /// Serve the wallet's USDC balance and exchange allowance (None = unlimited approval).
+    pub async fn mount_balance_allowance(&self, usdc: f64, allowance: Option<f64>) {
+        let base_units = |usdc: f64| ((usdc * 1_000_000.0).round() as u64).to_string();
+        let allowance = allowance.map_or_else(|| U256_MAX.to_string(), base_units);
+        Mock::given(method("GET"))
+            .and(path("/balance-allowance"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "balance": base_units(usdc),
+                "allowance": allowance
+            })))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Serve the wallet's USDC balance with no allowance field.
+    pub async fn mount_balance_without_allowance(&self, usdc: f64) {
+        Mock::given(method("GET"))
+            .and(path("/balance-allowance"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "balance": ((usdc * 1_000_000.0).round() as u64).to_string()
+            })))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Serve a wallet's total position value.
+    pub async fn mount_value(&self, address: &str, value: f64) {
+        Mock::given(method("GET"))
+            .and(path("/value"))
+            .and(query_param("user", address))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "value": value })))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Serve a one-level order book for a token.
+    pub async fn mount_order_book(&self, token_id: &str, bid: &str, ask: &str) {
+        self.mount_order_book_with_size(token_id, bid, ask, "500").await;
+    }
+
+    /// Serve a one-level order book for a token with `size` shares on each side.
+    pub async fn mount_order_book_with_size(&self, token_id: &str, bid: &str, ask: &str, size: &str) {
+        let body = json!({
+            "bids": [{ "price": bid, "size": size }],
+            "asks": [{ "price": ask, "size": size }],
+            "hash": "mock",
+            "timestamp": "0"
+        });
+        Mock::given(method("GET"))
+            .and(path("/book"))
+            .and(query_param("token_id", token_id))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&self.server)
+            .await;
+    }
+}
+
+/// Leaderboard entry as returned by `/v1/leaderboard`.
+pub fn leaderboard_entry(address: &str, pnl: f64) -> Value {
+    json!({
+        "rank": "1",
+        "proxyWallet": address,
+        "userName": "whale",
+        "vol": pnl * 10.0,
+        "pnl": pnl
+    })
+}
+
+/// Position as returned by `/positions`.
+pub fn position(market_id: &str, size: f64, price: f64) -> Value {
+    json!({
+        "conditionId": market_id,
+        "title": "Mock market",
+        "outcome": "Yes",
+        "outcomeIndex": 0,
+        "size": size,
+        "avgPrice": price,
+        "curPrice": price,
+        "initialValue": size * price,
+        "currentValue": size * price
+    })
+}
+
+/// Trade as returned by `/trades`.
+pub fn trade(address: &str, market_id: &str, tx_hash: &str, size: f64, price: f64, timestamp: i64) -> Value {
+    json!({
+        "proxyWallet": address,
+        "side": "BUY",
+        "conditionId": market_id,
+        "size": size,
+        "price": price,
+        "timestamp": timestamp,
+        "title": "Mock market",
+        "outcome": "Yes",
+        "outcomeIndex": 0,
+        "transactionHash": tx_hash
+    })
+}
+
+/// Resting order as returned by `/orders`.
+pub fn open_order(order_id: &str) -> Value {
+    json!({
+        "id": order_id,
+        "status": "LIVE",
+        "maker": "0x0000000000000000000000000000000000000000",
+        "side": "BUY",
+        "tokenId": "123",
+        "originalSize": "10",
+        "sizeMatched": "0",
+        "price": "0.5"
+    })
+}
+
+/// Clock that stands still until advanced, shared by every clone.
+#[derive(Debug, Clone)]
+pub struct MockClock(Arc<Mutex<DateTime<Utc>>>);
+
+impl MockClock {
+    /// Start the clock at `start`.
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self(Arc::new(Mutex::new(start)))
+    }
+
+    /// Move the clock forward by `by`.
+    pub fn advance(&self, by: chrono::Duration) {
+        *self.0.lock().unwrap() += by;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.0.lock().unwrap()
+    }
+}
+
+/// Fresh on-disk SQLite database URL (in-memory databases aren't shared across pool connections).
+pub fn temp_database_url() -> String {
+    let path = std::env::temp_dir().join(format!("polycopier-test-{}.db", uuid::Uuid::new_v4()));
+    format!("sqlite:{}?mode=rwc", path.display())
+}
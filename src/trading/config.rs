@@ -4,8 +4,12 @@ use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 
+use crate::api::{PriceChain, PriceSource, DEFAULT_PRICE_SOURCES};
+use crate::models::{DEFAULT_MIN_TRADES_FOR_QUALITY, DEFAULT_MIN_TRADES_FOR_SCORE};
+
 /// Configuration for trading and position sizing.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct TradingConfig {
     /// Maximum percentage of portfolio to allocate to all positions
     pub max_portfolio_allocation: Decimal,
@@ -13,6 +17,13 @@ pub struct TradingConfig {
     /// Maximum percentage of portfolio for a single position
     pub max_single_position: Decimal,
 
+    /// Ramp the allocation cap up to `max_portfolio_allocation` over this many days
+    /// from the bot's first live session (0 = full allocation from day one)
+    pub allocation_ramp_days: i64,
+
+    /// Allocation cap at the start of the ramp
+    pub allocation_ramp_start: Decimal,
+
     /// Minimum trade size in USDC
     pub min_trade_size: Decimal,
 
@@ -31,12 +42,34 @@ pub struct TradingConfig {
     /// Fraction of Kelly to use (0.0 to 1.0, typically 0.25)
     pub kelly_fraction: Decimal,
 
+    /// Scale copies by the source trade's share of the trader's portfolio, so
+    /// high-conviction bets get more of our capital than small nibbles
+    pub use_conviction_scaling: bool,
+
+    /// Cap on the conviction multiplier (and 1/cap its floor)
+    pub max_conviction_multiplier: Decimal,
+
     /// Minimum win rate required for a trader to copy
     pub min_win_rate: f64,
 
     /// Minimum number of trades for a trader to have
     pub min_trades: u32,
 
+    /// Traders with fewer trades get a composite score of 0
+    pub min_trades_for_score: u32,
+
+    /// Trades required before a trader counts as quality
+    pub min_trades_for_quality: u32,
+
+    /// Trader metrics older than this are recalculated before they're used for sizing
+    pub max_metrics_age_secs: i64,
+
+    /// Traders with no trades seen for this many days are flagged inactive
+    pub inactive_after_days: i64,
+
+    /// Pause traders once they're flagged inactive (otherwise just warn)
+    pub auto_pause_inactive: bool,
+
     /// Minimum profit in USDC for a trader
     pub min_profit: Decimal,
 
@@ -45,6 +78,25 @@ pub struct TradingConfig {
 
     /// Minimum Sharpe ratio for a trader
     pub min_sharpe: f64,
+
+    /// Weight trader metrics toward recent results, halving a result's weight every
+    /// this many days (None = all results count equally)
+    pub metrics_half_life_days: Option<f64>,
+
+    /// Also copy the trader's maker (limit order) fills, not just taker trades; each
+    /// resting order is copied once per poll at its limit price
+    pub include_maker_trades: bool,
+
+    /// When allocation capacity is tight, size intents from higher-scoring traders
+    /// first (otherwise traders are served in address order)
+    pub score_priority: bool,
+
+    /// Price sources to try, in order, when marking positions. Entries use only the
+    /// market sources (stream, clob_book) and never a stale price
+    pub price_sources: Vec<PriceSource>,
+
+    /// Prices older than this are only used when no source has a fresher one
+    pub max_price_staleness_secs: i64,
 }
 
 impl Default for TradingConfig {
@@ -52,17 +104,76 @@ impl Default for TradingConfig {
         Self {
             max_portfolio_allocation: dec!(0.5),  // Max 50% of capital
             max_single_position: dec!(0.1),       // Max 10% per position
+            allocation_ramp_days: 0,
+            allocation_ramp_start: dec!(0.1),
             min_trade_size: dec!(1.0),            // Min $1
             max_trade_size: dec!(1000.0),         // Max $1000
             max_drawdown_pct: dec!(0.2),          // Stop at 20% drawdown
             slippage_tolerance: dec!(0.02),       // 2% slippage
             sizing_method: "kelly".to_string(),
             kelly_fraction: dec!(0.25),           // Quarter Kelly
+            use_conviction_scaling: false,
+            max_conviction_multiplier: dec!(3),
             min_win_rate: 0.55,
             min_trades: 20,
+            min_trades_for_score: DEFAULT_MIN_TRADES_FOR_SCORE,
+            min_trades_for_quality: DEFAULT_MIN_TRADES_FOR_QUALITY,
+            max_metrics_age_secs: 3600,
+            inactive_after_days: 14,
+            auto_pause_inactive: false,
             min_profit: dec!(100.0),
             max_trader_mdd: 0.4,
             min_sharpe: 0.5,
+            metrics_half_life_days: None,
+            include_maker_trades: false,
+            score_priority: true,
+            price_sources: DEFAULT_PRICE_SOURCES.to_vec(),
+            max_price_staleness_secs: 300,
+        }
+    }
+}
+
+impl TradingConfig {
+    /// Price fallback chain built from `price_sources` and `max_price_staleness_secs`.
+    pub fn price_chain(&self) -> PriceChain {
+        PriceChain::new(self.price_sources.clone(), chrono::Duration::seconds(self.max_price_staleness_secs))
+    }
+
+    /// Allocation cap `elapsed` into live trading, growing linearly from
+    /// `allocation_ramp_start` to `max_portfolio_allocation` over the ramp.
+    pub fn allocation_cap(&self, elapsed: chrono::Duration) -> Decimal {
+        let max = self.max_portfolio_allocation;
+        let ramp = chrono::Duration::days(self.allocation_ramp_days);
+        if self.allocation_ramp_days <= 0 || elapsed >= ramp {
+            return max;
         }
+
+        let start = self.allocation_ramp_start.min(max);
+        let progress = Decimal::from(elapsed.num_seconds().max(0)) / Decimal::from(ramp.num_seconds());
+        start + (max - start) * progress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocation_cap_ramps_to_max() {
+        let config = TradingConfig {
+            max_portfolio_allocation: dec!(0.5),
+            allocation_ramp_days: 14,
+            allocation_ramp_start: dec!(0.1),
+            ..Default::default()
+        };
+
+        let day_one = config.allocation_cap(chrono::Duration::days(1));
+        assert!(day_one > dec!(0.1) && day_one < dec!(0.5));
+        assert_eq!(config.allocation_cap(chrono::Duration::zero()), dec!(0.1));
+        assert_eq!(config.allocation_cap(chrono::Duration::days(14)), dec!(0.5));
+        assert_eq!(config.allocation_cap(chrono::Duration::days(30)), dec!(0.5));
+
+        // No ramp configured: full allocation from the start
+        assert_eq!(TradingConfig::default().allocation_cap(chrono::Duration::zero()), dec!(0.5));
     }
 }
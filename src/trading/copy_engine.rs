@@ -2,10 +2,13 @@
 
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
@@ -13,17 +16,30 @@ use crate::api::DataClient;
 use crate::metrics::MetricsCalculator;
 use crate::models::{Trade, Trader, TraderMetrics};
 
-use super::{PositionSizer, TradingConfig};
+use super::{PositionSizer, TradingConfig, ZeroSizeReason};
 
 /// Represents a pending copy trade to be executed.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CopyTradeIntent {
     pub source_trader: String,
     pub source_trade: Trade,
     pub calculated_size: Decimal,
     pub created_at: DateTime<Utc>,
+    /// Whether the source already held the market before this trade, and its unrealized
+    /// P&L in the traded outcome, both read from the holdings snapshot the intent was
+    /// polled against, so a refresh mid-tick can't change them
+    #[serde(default)]
+    pub source_held_market: bool,
+    #[serde(default)]
+    pub source_position_pnl: Option<Decimal>,
 }
 
+/// Default number of trader loads in flight at once.
+pub const DEFAULT_LOAD_CONCURRENCY: usize = 4;
+
+/// Newest trade seen per trader: address -> (trade ID, timestamp).
+pub type Watermarks = HashMap<String, (String, DateTime<Utc>)>;
+
 /// Copy-trading engine state.
 pub struct CopyEngine {
     config: TradingConfig,
@@ -36,30 +52,55 @@ pub struct CopyEngine {
     // Last seen trade ID per trader (to detect new trades)
     last_seen_trades: Arc<RwLock<HashMap<String, String>>>,
 
+    // Newest trade (ID, timestamp) per trader; older trades are never re-emitted
+    watermarks: Arc<RwLock<Watermarks>>,
+
     // Our portfolio state
     portfolio_value: Arc<RwLock<Decimal>>,
     current_exposure: Arc<RwLock<Decimal>>,
 
+    // Fraction of the portfolio new positions may fill, ramped up on new accounts
+    allocation_cap: Arc<RwLock<Decimal>>,
+
     // Pending trades to execute
     pending_trades: Arc<RwLock<Vec<CopyTradeIntent>>>,
+
+    // New trades dropped because sizing produced nothing, by reason
+    zero_size_reasons: Arc<RwLock<HashMap<ZeroSizeReason, usize>>>,
+
+    // Traders already flagged as inactive, so each is reported once
+    inactive_traders: Arc<RwLock<HashSet<String>>>,
+
+    // Delay between per-trader requests within a poll cycle
+    poll_stagger: Arc<RwLock<Duration>>,
 }
 
 impl CopyEngine {
     /// Create a new copy engine.
     pub fn new(config: TradingConfig) -> Result<Self> {
-        let data_client = DataClient::new()?;
+        Ok(Self::with_data_client(config, DataClient::new()?))
+    }
+
+    /// Create a copy engine backed by a specific data client.
+    pub fn with_data_client(config: TradingConfig, data_client: DataClient) -> Self {
         let position_sizer = PositionSizer::new(config.clone());
+        let allocation_cap = Arc::new(RwLock::new(config.max_portfolio_allocation));
 
-        Ok(Self {
+        Self {
             config,
             data_client,
             position_sizer,
             tracked_traders: Arc::new(RwLock::new(HashMap::new())),
             last_seen_trades: Arc::new(RwLock::new(HashMap::new())),
+            watermarks: Arc::new(RwLock::new(HashMap::new())),
             portfolio_value: Arc::new(RwLock::new(Decimal::ZERO)),
             current_exposure: Arc::new(RwLock::new(Decimal::ZERO)),
+            allocation_cap,
             pending_trades: Arc::new(RwLock::new(Vec::new())),
-        })
+            zero_size_reasons: Arc::new(RwLock::new(HashMap::new())),
+            inactive_traders: Arc::new(RwLock::new(HashSet::new())),
+            poll_stagger: Arc::new(RwLock::new(Duration::ZERO)),
+        }
     }
 
     /// Set our portfolio value.
@@ -67,6 +108,26 @@ impl CopyEngine {
         *self.portfolio_value.write().await = value;
     }
 
+    /// Our portfolio value used as the sizing base.
+    pub async fn portfolio_value(&self) -> Decimal {
+        *self.portfolio_value.read().await
+    }
+
+    /// Set our current (net directional) exposure used to cap new position sizes.
+    pub async fn set_current_exposure(&self, exposure: Decimal) {
+        *self.current_exposure.write().await = exposure;
+    }
+
+    /// Set the fraction of the portfolio that total exposure is capped at.
+    pub async fn set_allocation_cap(&self, cap: Decimal) {
+        *self.allocation_cap.write().await = cap;
+    }
+
+    /// Set the delay between per-trader requests within a poll cycle.
+    pub async fn set_poll_stagger(&self, stagger: Duration) {
+        *self.poll_stagger.write().await = stagger;
+    }
+
     /// Add a trader to track.
     pub async fn add_trader(&self, address: String) -> Result<()> {
         let mut trader = Trader::new(address.clone());
@@ -74,14 +135,14 @@ impl CopyEngine {
 
         // Fetch initial data
         let positions = self.data_client.get_positions(&address, Some(100)).await?;
-        let trades = self.data_client.get_trades(&address, Some(200), None).await?;
+        let trades = self.fetch_trades(&address, Some(200)).await?;
 
-        trader.positions = positions;
+        trader.set_positions(positions);
+        if let Some(newest) = trades.iter().map(|t| t.timestamp).max() {
+            trader.saw_trade_at(newest);
+        }
 
-        // Calculate metrics (simplified - would need resolved P&Ls for accuracy)
-        let pnls: Vec<Decimal> = trades.iter().map(|_| Decimal::ZERO).collect(); // Placeholder
-        let metrics = MetricsCalculator::calculate(&address, &trades, &pnls);
-        trader.metrics = Some(metrics);
+        trader.metrics = Some(self.initial_metrics(&address, &trades));
 
         // Store last trade ID
         if let Some(last_trade) = trades.first() {
@@ -91,7 +152,7 @@ impl CopyEngine {
 
         // Check if trader meets requirements
         if let Some(ref m) = trader.metrics {
-            if !m.is_quality_trader() {
+            if !m.is_quality_trader(self.config.min_trades_for_quality) {
                 warn!(
                     address = %address,
                     win_rate = %m.win_rate,
@@ -116,9 +177,170 @@ impl CopyEngine {
         let mut last_seen = self.last_seen_trades.write().await;
         last_seen.remove(address);
 
+        self.watermarks.write().await.remove(address);
+
         info!(address = %address, "Removed trader from tracking");
     }
 
+    /// Restore a trader's persisted watermark; fetched trades older than it are skipped.
+    pub async fn restore_watermark(&self, address: &str, trade_id: String, trade_at: DateTime<Utc>) {
+        let mut watermarks = self.watermarks.write().await;
+        let newer = !matches!(watermarks.get(address), Some((_, at)) if *at >= trade_at);
+        if newer {
+            watermarks.insert(address.to_string(), (trade_id, trade_at));
+        }
+    }
+
+    /// Restore when tracking of a trader started and when they last traded, from an
+    /// earlier session, so inactivity counts across restarts.
+    pub async fn restore_activity(
+        &self,
+        address: &str,
+        tracking_since: Option<DateTime<Utc>>,
+        last_trade_seen_at: Option<DateTime<Utc>>,
+    ) {
+        if let Some(trader) = self.tracked_traders.write().await.get_mut(address) {
+            if tracking_since.is_some() {
+                trader.tracking_since = tracking_since;
+            }
+            if let Some(at) = last_trade_seen_at {
+                trader.saw_trade_at(at);
+            }
+        }
+    }
+
+    /// Time of the newest trade seen per trader, for those with any.
+    pub async fn last_trades_seen(&self) -> HashMap<String, DateTime<Utc>> {
+        self.tracked_traders
+            .read()
+            .await
+            .iter()
+            .filter_map(|(address, t)| Some((address.clone(), t.last_trade_seen_at?)))
+            .collect()
+    }
+
+    /// Current watermark (newest seen trade ID and timestamp) per trader.
+    pub async fn watermarks(&self) -> Watermarks {
+        self.watermarks.read().await.clone()
+    }
+
+    /// Fetch a trader's recent trades, including maker fills if configured.
+    async fn fetch_trades(&self, address: &str, limit: Option<u32>) -> Result<Vec<Trade>> {
+        self.data_client
+            .get_trades_filtered(address, limit, None, !self.config.include_maker_trades)
+            .await
+    }
+
+    /// Metrics from a trader's recent trades, as calculated when they're first loaded.
+    fn initial_metrics(&self, address: &str, trades: &[Trade]) -> TraderMetrics {
+        // Simplified - would need resolved P&Ls for accuracy
+        let resolved: Vec<_> = trades.iter().map(|t| (t.timestamp, Decimal::ZERO)).collect(); // Placeholder
+        self.calculate_metrics(address, trades, &resolved)
+    }
+
+    /// Trader metrics, time-decayed when a metrics half-life is configured.
+    fn calculate_metrics(&self, address: &str, trades: &[Trade], resolved: &[(DateTime<Utc>, Decimal)]) -> TraderMetrics {
+        match self.config.metrics_half_life_days {
+            Some(days) => {
+                let half_life = chrono::Duration::seconds((days * 86_400.0) as i64);
+                MetricsCalculator::calculate_decayed(address, trades, resolved, half_life)
+            }
+            None => {
+                let pnls: Vec<Decimal> = resolved.iter().map(|(_, pnl)| *pnl).collect();
+                MetricsCalculator::calculate(address, trades, &pnls)
+            }
+        }
+    }
+
+    /// Current price of a market outcome from any tracked trader's holdings, with the
+    /// time those holdings were fetched.
+    pub async fn trader_quote(&self, market_id: &str, outcome: &str) -> Option<(Decimal, DateTime<Utc>)> {
+        let traders = self.tracked_traders.read().await;
+        traders
+            .values()
+            .filter_map(|t| {
+                let position = t
+                    .positions
+                    .iter()
+                    .find(|p| p.market_id == market_id && p.outcome.eq_ignore_ascii_case(outcome))?;
+                Some((position.current_price, t.positions_as_of?))
+            })
+            .max_by_key(|(_, as_of)| *as_of)
+    }
+
+    /// Load several traders with at most `concurrency` loads in flight.
+    ///
+    /// Returns each address with its load result, in the order given.
+    pub async fn add_traders(&self, addresses: &[String], concurrency: usize) -> Vec<(String, Result<()>)> {
+        stream::iter(addresses.iter().cloned())
+            .map(|address| async move {
+                let result = self.add_trader(address.clone()).await;
+                if let Err(e) = &result {
+                    warn!(address = %address, error = %e, "Failed to load trader");
+                }
+                (address, result)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Mark exactly the traders in `paused` as paused.
+    pub async fn set_paused_traders(&self, paused: &[String]) {
+        let mut traders = self.tracked_traders.write().await;
+        for (address, trader) in traders.iter_mut() {
+            trader.is_paused = paused.contains(address);
+        }
+    }
+
+    /// Flag traders with no trade seen in `inactive_after_days`, returning the newly
+    /// flagged ones; a flag clears once the trader trades again.
+    pub async fn detect_inactive_traders(&self, now: DateTime<Utc>) -> Vec<String> {
+        let window = chrono::Duration::days(self.config.inactive_after_days);
+        let traders = self.tracked_traders.read().await;
+        let mut flagged = self.inactive_traders.write().await;
+
+        let mut newly_flagged = Vec::new();
+        for (address, trader) in traders.iter() {
+            if !trader.is_inactive(now, window) {
+                flagged.remove(address);
+            } else if flagged.insert(address.clone()) {
+                newly_flagged.push(address.clone());
+            }
+        }
+        newly_flagged.sort();
+        newly_flagged
+    }
+
+    /// Refresh tracked traders' holdings; a failed fetch keeps that trader's previous snapshot.
+    pub async fn refresh_holdings(&self) {
+        let mut traders = self.tracked_traders.write().await;
+        for (address, trader) in traders.iter_mut() {
+            match self.data_client.get_positions(address, Some(100)).await {
+                Ok(positions) => trader.set_positions(positions),
+                Err(e) => warn!(address = %address, error = %e, "Failed to refresh trader holdings"),
+            }
+        }
+    }
+
+    /// Market IDs held per tracked trader, with the time of the oldest snapshot.
+    pub async fn holdings_snapshot(&self) -> (HashMap<String, Vec<String>>, Option<DateTime<Utc>>) {
+        let traders = self.tracked_traders.read().await;
+        let holdings = traders
+            .iter()
+            .map(|(address, t)| {
+                let markets = t.positions.iter().map(|p| p.market_id.clone()).collect();
+                (address.clone(), markets)
+            })
+            .collect();
+        let as_of = traders
+            .values()
+            .map(|t| t.positions_as_of)
+            .min()
+            .flatten();
+        (holdings, as_of)
+    }
+
     /// Get all tracked traders.
     pub async fn get_tracked_traders(&self) -> Vec<Trader> {
         let traders = self.tracked_traders.read().await;
@@ -126,53 +348,103 @@ impl CopyEngine {
     }
 
     /// Poll for new trades from tracked traders.
+    ///
+    /// Traders are visited in `poll_order`, so intents are ordered deterministically.
+    /// Sizing constraints are applied cumulatively in that order, so with
+    /// `score_priority` the best-scoring traders claim remaining capacity first.
     pub async fn poll_for_trades(&self) -> Result<Vec<CopyTradeIntent>> {
+        // Metrics size the intents below
+        self.refresh_stale_metrics().await;
+
         let traders = self.tracked_traders.read().await;
         let mut last_seen = self.last_seen_trades.write().await;
+        let mut watermarks = self.watermarks.write().await;
 
         let mut new_intents = Vec::new();
+        let mut seen_at = Vec::new();
+        let stagger = *self.poll_stagger.read().await;
+
+        // Exposure committed by intents earlier in this cycle
+        let mut committed = Decimal::ZERO;
+
+        for (i, (address, trader)) in poll_order(&traders, self.config.score_priority, self.config.min_trades_for_score)
+            .into_iter()
+            .enumerate()
+        {
+            if i > 0 && !stagger.is_zero() {
+                tokio::time::sleep(stagger).await;
+            }
 
-        for (address, trader) in traders.iter() {
-            let trades = self
-                .data_client
-                .get_trades(address, Some(10), None)
-                .await?;
+            let trades = self.fetch_trades(address, Some(10)).await?;
 
             if trades.is_empty() {
                 continue;
             }
 
             let last_seen_id = last_seen.get(address).cloned();
+            let watermark = watermarks.get(address).map(|(_, at)| *at);
 
-            // Find new trades
+            // Find new trades, never going back past the watermark
             let new_trades: Vec<_> = trades
                 .into_iter()
                 .take_while(|t| Some(&t.id) != last_seen_id.as_ref())
+                .filter(|t| !matches!(watermark, Some(at) if t.timestamp < at))
                 .collect();
 
             if !new_trades.is_empty() {
                 // Update last seen
                 if let Some(newest) = new_trades.first() {
+                    seen_at.push((address.clone(), newest.timestamp));
                     last_seen.insert(address.clone(), newest.id.clone());
+                    if !matches!(watermark, Some(at) if at >= newest.timestamp) {
+                        watermarks.insert(address.clone(), (newest.id.clone(), newest.timestamp));
+                    }
+                }
+
+                // Paused traders' trades are consumed without copying, so resuming doesn't replay them
+                if trader.is_paused {
+                    debug!(trader = %address, trades = new_trades.len(), "Trader paused, not copying");
+                    continue;
                 }
 
+                let new_trades = if self.config.include_maker_trades {
+                    merge_maker_fills(new_trades)
+                } else {
+                    new_trades
+                };
+
                 // Calculate copy trade sizes
                 let portfolio = *self.portfolio_value.read().await;
                 let exposure = *self.current_exposure.read().await;
+                let allocation_cap = *self.allocation_cap.read().await;
                 let source_value = trader.total_position_value();
 
                 for trade in new_trades {
-                    let size = self.position_sizer.calculate_size(
+                    let sizing = self.position_sizer.size_with_reason(
                         trade.amount_usdc,
                         source_value,
                         portfolio,
                         trader.metrics.as_ref(),
-                        exposure,
+                        exposure + committed,
+                        allocation_cap,
                     );
+                    let size = sizing.size;
+
+                    if let Some(reason) = sizing.zero_reason {
+                        info!(
+                            trader = %address,
+                            market = %trade.market_id,
+                            reason = %reason,
+                            "Copy trade sized to zero"
+                        );
+                        *self.zero_size_reasons.write().await.entry(reason).or_default() += 1;
+                    } else if size > Decimal::ZERO {
+                        committed += size;
 
-                    if size > Decimal::ZERO {
                         let intent = CopyTradeIntent {
                             source_trader: address.clone(),
+                            source_held_market: trader.held_market_before(&trade),
+                            source_position_pnl: trader.position_pnl(&trade.market_id, &trade.outcome),
                             source_trade: trade,
                             calculated_size: size,
                             created_at: Utc::now(),
@@ -192,6 +464,14 @@ impl CopyEngine {
             }
         }
 
+        drop((watermarks, last_seen, traders));
+        let mut traders = self.tracked_traders.write().await;
+        for (address, at) in seen_at {
+            if let Some(trader) = traders.get_mut(&address) {
+                trader.saw_trade_at(at);
+            }
+        }
+
         // Store pending trades
         let mut pending = self.pending_trades.write().await;
         pending.extend(new_intents.clone());
@@ -216,30 +496,71 @@ impl CopyEngine {
         let mut traders = self.tracked_traders.write().await;
 
         for (address, trader) in traders.iter_mut() {
-            debug!(address = %address, "Refreshing trader metrics");
+            self.refresh_trader(address, trader).await?;
+        }
 
-            let positions = self.data_client.get_positions(address, Some(100)).await?;
-            let trades = self.data_client.get_trades(address, Some(500), None).await?;
+        Ok(())
+    }
 
-            trader.positions = positions;
+    /// Refresh metrics older than `max_metrics_age_secs` (or missing), so sizing
+    /// never uses stale numbers. A failed refresh keeps the previous metrics.
+    pub async fn refresh_stale_metrics(&self) {
+        let max_age = chrono::Duration::seconds(self.config.max_metrics_age_secs);
+        let now = Utc::now();
+        let mut traders = self.tracked_traders.write().await;
 
-            // Recalculate metrics
-            let pnls: Vec<Decimal> = vec![]; // Would need resolved trade data
-            let metrics = MetricsCalculator::calculate(address, &trades, &pnls);
-            trader.metrics = Some(metrics);
+        for (address, trader) in traders.iter_mut() {
+            if trader.metrics.as_ref().is_some_and(|m| now - m.calculated_at <= max_age) {
+                continue;
+            }
+            if let Err(e) = self.refresh_trader(address, trader).await {
+                warn!(address = %address, error = %e, "Failed to refresh stale trader metrics");
+            }
         }
+    }
+
+    /// Re-fetch one trader's holdings and history and recalculate its metrics.
+    async fn refresh_trader(&self, address: &str, trader: &mut Trader) -> Result<()> {
+        debug!(address = %address, "Refreshing trader metrics");
+
+        let positions = self.data_client.get_positions(address, Some(100)).await?;
+        let trades = self.fetch_trades(address, Some(500)).await?;
 
+        trader.set_positions(positions);
+
+        // Recalculate metrics
+        let resolved = vec![]; // Would need resolved trade data
+        let metrics = self.calculate_metrics(address, &trades, &resolved);
+        trader.metrics = Some(metrics);
         Ok(())
     }
 
-    /// Discover and optionally add top traders.
-    pub async fn discover_traders(&self, min_pnl: f64, limit: usize) -> Result<Vec<Trader>> {
+    /// Discover top traders, scored from their recent trades (at most `concurrency`
+    /// fetches in flight) and sorted by composite score, best first.
+    ///
+    /// Traders whose trades fail to load keep no metrics and sort last.
+    pub async fn discover_traders(&self, min_pnl: f64, limit: usize, concurrency: usize) -> Result<Vec<Trader>> {
         let traders = self
             .data_client
             .discover_top_traders(min_pnl, "MONTH", limit)
             .await?;
 
         info!(count = traders.len(), "Discovered traders from leaderboard");
+
+        let mut traders: Vec<Trader> = stream::iter(traders)
+            .map(|mut trader| async move {
+                match self.fetch_trades(&trader.address, Some(200)).await {
+                    Ok(trades) => trader.metrics = Some(self.initial_metrics(&trader.address, &trades)),
+                    Err(e) => warn!(address = %trader.address, error = %e, "Failed to score discovered trader"),
+                }
+                trader
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+
+        let min_trades = self.config.min_trades_for_score;
+        traders.sort_by(|a, b| b.score(min_trades).total_cmp(&a.score(min_trades)));
         Ok(traders)
     }
 
@@ -249,6 +570,7 @@ impl CopyEngine {
         let pending = self.pending_trades.read().await;
         let portfolio = *self.portfolio_value.read().await;
         let exposure = *self.current_exposure.read().await;
+        let zero_size_reasons = self.zero_size_reasons.read().await.clone();
 
         let avg_win_rate = traders
             .values()
@@ -271,6 +593,7 @@ impl CopyEngine {
             current_exposure: exposure,
             avg_trader_win_rate: avg_win_rate,
             avg_trader_sharpe: avg_sharpe,
+            zero_size_reasons,
         }
     }
 
@@ -285,6 +608,20 @@ impl CopyEngine {
     }
 }
 
+/// Order in which traders are polled: composite score descending (if `by_score`), then address.
+fn poll_order(traders: &HashMap<String, Trader>, by_score: bool, min_trades: u32) -> Vec<(&String, &Trader)> {
+    let mut ordered: Vec<_> = traders.iter().collect();
+    ordered.sort_by(|(a_addr, a), (b_addr, b)| {
+        let score = if by_score {
+            b.score(min_trades).total_cmp(&a.score(min_trades))
+        } else {
+            std::cmp::Ordering::Equal
+        };
+        score.then_with(|| a_addr.cmp(b_addr))
+    });
+    ordered
+}
+
 /// Engine statistics.
 #[derive(Debug, Clone)]
 pub struct EngineStats {
@@ -294,4 +631,346 @@ pub struct EngineStats {
     pub current_exposure: Decimal,
     pub avg_trader_win_rate: f64,
     pub avg_trader_sharpe: f64,
+    /// New trades not copied because sizing produced nothing, by reason
+    pub zero_size_reasons: HashMap<ZeroSizeReason, usize>,
+}
+
+/// Fold the fills of each resting (maker) order into one trade at the source's
+/// effective entry.
+///
+/// A limit order fills piecemeal at its limit price, so non-taker fills sharing a
+/// market, outcome, side and price are summed and copied once. Taker trades pass
+/// through untouched. Order is kept (newest first); a merged trade carries its newest
+/// fill's ID and timestamp.
+fn merge_maker_fills(trades: Vec<Trade>) -> Vec<Trade> {
+    let mut merged: Vec<Trade> = Vec::with_capacity(trades.len());
+    for trade in trades {
+        let order = merged.iter_mut().find(|m| {
+            !trade.is_taker
+                && !m.is_taker
+                && m.market_id == trade.market_id
+                && m.outcome == trade.outcome
+                && m.side == trade.side
+                && m.price == trade.price
+        });
+        match order {
+            Some(order) => {
+                order.size += trade.size;
+                order.amount_usdc += trade.amount_usdc;
+                order.fee_usdc += trade.fee_usdc;
+            }
+            None => merged.push(trade),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DEFAULT_MIN_TRADES_FOR_SCORE;
+    use crate::testing::{self, MockPolymarket};
+
+    #[test]
+    fn test_poll_order_is_deterministic() {
+        let mut strong = Trader::new("0xcc".to_string());
+        strong.metrics = Some(TraderMetrics {
+            total_trades: 50,
+            win_rate: 0.7,
+            sharpe_ratio: 1.5,
+            ..TraderMetrics::default()
+        });
+
+        let build = |addresses: &[&str]| {
+            let mut map = HashMap::new();
+            for addr in addresses {
+                let trader = if *addr == "0xcc" { strong.clone() } else { Trader::new(addr.to_string()) };
+                map.insert(addr.to_string(), trader);
+            }
+            map
+        };
+
+        let first = build(&["0xaa", "0xbb", "0xcc"]);
+        let second = build(&["0xcc", "0xbb", "0xaa"]);
+        let order = |map: &HashMap<String, Trader>| {
+            poll_order(map, true, DEFAULT_MIN_TRADES_FOR_SCORE).into_iter().map(|(a, _)| a.clone()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(order(&first), order(&second));
+        assert_eq!(order(&first), vec!["0xcc", "0xaa", "0xbb"]);
+    }
+
+    #[tokio::test]
+    async fn test_high_score_trader_funded_first_under_tight_cap() {
+        let weak = "0x1111111111111111111111111111111111111111";
+        let strong = "0x9999999999999999999999999999999999999999";
+        let now = Utc::now().timestamp();
+
+        let mock = MockPolymarket::start().await;
+        for (trader, tx) in [(weak, "0xweak"), (strong, "0xstrong")] {
+            mock.mount_positions(trader, vec![testing::position("0xmarket", 2000.0, 0.5)]).await;
+            mock.mount_trades(trader, vec![], Some(1)).await;
+            mock.mount_trades(
+                trader,
+                vec![testing::trade(trader, "0xmarket", tx, 100.0, 0.5, now)],
+                None,
+            ).await;
+        }
+
+        // $50 of total capacity; each copy wants $50
+        let config = TradingConfig {
+            sizing_method: "equal".to_string(),
+            max_portfolio_allocation: Decimal::new(5, 2),
+            ..TradingConfig::default()
+        };
+        let engine = CopyEngine::with_data_client(config, mock.data_client());
+        engine.set_portfolio_value(Decimal::from(1000)).await;
+        engine.add_trader(weak.to_string()).await.unwrap();
+        engine.add_trader(strong.to_string()).await.unwrap();
+
+        engine.tracked_traders.write().await.get_mut(strong).unwrap().metrics = Some(TraderMetrics {
+            total_trades: 50,
+            win_rate: 0.7,
+            sharpe_ratio: 1.5,
+            ..TraderMetrics::default()
+        });
+
+        let intents = engine.poll_for_trades().await.unwrap();
+        assert_eq!(intents.len(), 1);
+        assert_eq!(intents[0].source_trader, strong);
+        assert_eq!(intents[0].calculated_size, Decimal::from(50));
+    }
+
+    #[tokio::test]
+    async fn test_maker_fills_copied_once_at_limit_price() {
+        let trader = "0x4444444444444444444444444444444444444444";
+        let now = Utc::now().timestamp();
+
+        let mock = MockPolymarket::start().await;
+        mock.mount_positions(trader, vec![]).await;
+        mock.mount_trades(trader, vec![], Some(1)).await;
+        // Three fills of one resting bid at 0.40, and an unrelated fill at 0.45
+        mock.mount_trades(
+            trader,
+            vec![
+                testing::trade(trader, "0xmarket", "0xfill3", 30.0, 0.4, now),
+                testing::trade(trader, "0xmarket", "0xother", 10.0, 0.45, now - 5),
+                testing::trade(trader, "0xmarket", "0xfill2", 20.0, 0.4, now - 10),
+                testing::trade(trader, "0xmarket", "0xfill1", 50.0, 0.4, now - 20),
+            ],
+            None,
+        ).await;
+
+        let config = TradingConfig {
+            sizing_method: "equal".to_string(),
+            include_maker_trades: true,
+            ..TradingConfig::default()
+        };
+        let engine = CopyEngine::with_data_client(config, mock.data_client());
+        engine.set_portfolio_value(Decimal::from(10_000)).await;
+        engine.add_trader(trader.to_string()).await.unwrap();
+
+        let intents = engine.poll_for_trades().await.unwrap();
+        let fills: Vec<_> = intents
+            .iter()
+            .map(|i| (i.source_trade.price, i.source_trade.size, i.source_trade.timestamp.timestamp()))
+            .collect();
+        assert_eq!(
+            fills,
+            [(Decimal::new(4, 1), Decimal::from(100), now), (Decimal::new(45, 2), Decimal::from(10), now - 5)]
+        );
+        assert_eq!(intents[0].source_trade.amount_usdc, Decimal::from(40));
+    }
+
+    #[tokio::test]
+    async fn test_add_traders_reports_failed_loads() {
+        let good = ["0x7777777777777777777777777777777777777777", "0x8888888888888888888888888888888888888888"];
+        let bad = "0xbad";
+
+        let mock = MockPolymarket::start().await;
+        for trader in good {
+            mock.mount_positions(trader, vec![]).await;
+            mock.mount_trades(trader, vec![], None).await;
+        }
+
+        let engine = CopyEngine::with_data_client(TradingConfig::default(), mock.data_client());
+        let addresses = vec![good[0].to_string(), bad.to_string(), good[1].to_string()];
+        let loaded = engine.add_traders(&addresses, 2).await;
+
+        let failed: Vec<(&str, bool)> = loaded.iter().map(|(a, r)| (a.as_str(), r.is_err())).collect();
+        assert_eq!(failed, [(good[0], false), (bad, true), (good[1], false)]);
+        assert_eq!(engine.get_tracked_traders().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_stale_metrics_are_refreshed_before_sizing() {
+        let trader = "0x9999999999999999999999999999999999999999";
+        let mock = MockPolymarket::start().await;
+        mock.mount_positions(trader, vec![]).await;
+        mock.mount_trades(trader, vec![], None).await;
+
+        let config = TradingConfig { max_metrics_age_secs: 3600, ..TradingConfig::default() };
+        let engine = CopyEngine::with_data_client(config, mock.data_client());
+        engine.add_trader(trader.to_string()).await.unwrap();
+
+        let day_old = Utc::now() - chrono::Duration::days(1);
+        engine.tracked_traders.write().await.get_mut(trader).unwrap().metrics = Some(TraderMetrics {
+            calculated_at: day_old,
+            ..TraderMetrics::default()
+        });
+
+        engine.poll_for_trades().await.unwrap();
+
+        let traders = engine.get_tracked_traders().await;
+        let calculated_at = traders[0].metrics.as_ref().unwrap().calculated_at;
+        assert!(calculated_at > day_old + chrono::Duration::hours(23));
+    }
+
+    #[tokio::test]
+    async fn test_paused_trader_generates_no_intents() {
+        let paused = "0x5555555555555555555555555555555555555555";
+        let active = "0x6666666666666666666666666666666666666666";
+        let now = Utc::now().timestamp();
+
+        let mock = MockPolymarket::start().await;
+        for (trader, tx) in [(paused, "0xpaused"), (active, "0xactive")] {
+            mock.mount_positions(trader, vec![testing::position("0xmarket", 2000.0, 0.5)]).await;
+            mock.mount_trades(trader, vec![], Some(1)).await;
+            mock.mount_trades(
+                trader,
+                vec![testing::trade(trader, "0xmarket", tx, 100.0, 0.5, now)],
+                None,
+            ).await;
+        }
+
+        let config = TradingConfig {
+            sizing_method: "equal".to_string(),
+            ..TradingConfig::default()
+        };
+        let engine = CopyEngine::with_data_client(config, mock.data_client());
+        engine.set_portfolio_value(Decimal::from(1000)).await;
+        engine.add_trader(paused.to_string()).await.unwrap();
+        engine.add_trader(active.to_string()).await.unwrap();
+        engine.set_paused_traders(&[paused.to_string()]).await;
+
+        let intents = engine.poll_for_trades().await.unwrap();
+        assert_eq!(intents.len(), 1);
+        assert_eq!(intents[0].source_trader, active);
+
+        // Trades seen while paused aren't replayed on resume
+        engine.set_paused_traders(&[]).await;
+        assert!(engine.poll_for_trades().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_trade_zeroed_by_exposure_cap_reports_reason() {
+        let trader = "0x7777777777777777777777777777777777777777";
+        let now = Utc::now().timestamp();
+
+        let mock = MockPolymarket::start().await;
+        mock.mount_positions(trader, vec![testing::position("0xmarket", 2000.0, 0.5)]).await;
+        mock.mount_trades(trader, vec![], Some(1)).await;
+        mock.mount_trades(trader, vec![testing::trade(trader, "0xmarket", "0xtx", 100.0, 0.5, now)], None).await;
+
+        let config = TradingConfig {
+            sizing_method: "equal".to_string(),
+            ..TradingConfig::default()
+        };
+        let engine = CopyEngine::with_data_client(config, mock.data_client());
+        engine.set_portfolio_value(Decimal::from(1000)).await;
+        engine.set_current_exposure(Decimal::from(1000)).await;
+        engine.add_trader(trader.to_string()).await.unwrap();
+
+        assert!(engine.poll_for_trades().await.unwrap().is_empty());
+        let stats = engine.get_stats().await;
+        assert_eq!(stats.zero_size_reasons, HashMap::from([(ZeroSizeReason::ExposureCap, 1)]));
+    }
+
+    #[tokio::test]
+    async fn test_trader_without_recent_trades_is_flagged_inactive() {
+        let idle = "0x8888888888888888888888888888888888888888";
+        let active = "0x9999999999999999999999999999999999999999";
+        let now = Utc::now();
+
+        let mock = MockPolymarket::start().await;
+        for (trader, days_ago) in [(idle, 30), (active, 1)] {
+            let at = (now - chrono::Duration::days(days_ago)).timestamp();
+            mock.mount_positions(trader, vec![]).await;
+            mock.mount_trades(trader, vec![testing::trade(trader, "0xmarket", trader, 100.0, 0.5, at)], None).await;
+        }
+
+        let engine = CopyEngine::with_data_client(TradingConfig::default(), mock.data_client());
+        engine.add_trader(idle.to_string()).await.unwrap();
+        engine.add_trader(active.to_string()).await.unwrap();
+
+        assert_eq!(engine.detect_inactive_traders(now).await, vec![idle.to_string()]);
+        // Reported once, not on every check
+        assert!(engine.detect_inactive_traders(now).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_discovered_traders_are_ordered_by_composite_score() {
+        let sparse = "0xaaaa000000000000000000000000000000000000";
+        let active = "0xbbbb000000000000000000000000000000000000";
+        let now = Utc::now().timestamp();
+
+        let mock = MockPolymarket::start().await;
+        // The leaderboard ranks the sparse trader first
+        mock.mount_leaderboard(vec![
+            testing::leaderboard_entry(sparse, 9000.0),
+            testing::leaderboard_entry(active, 1000.0),
+        ]).await;
+        for (trader, count) in [(sparse, 3), (active, 15)] {
+            let trades = (0..count)
+                .map(|i| testing::trade(trader, "0xmarket", &format!("{}-{}", trader, i), 10.0, 0.5, now - i * 60))
+                .collect();
+            mock.mount_trades(trader, trades, None).await;
+        }
+
+        let engine = CopyEngine::with_data_client(TradingConfig::default(), mock.data_client());
+        let traders = engine.discover_traders(0.0, 10, 2).await.unwrap();
+
+        let order: Vec<&str> = traders.iter().map(|t| t.address.as_str()).collect();
+        assert_eq!(order, [active, sparse]);
+        let min_trades = DEFAULT_MIN_TRADES_FOR_SCORE;
+        assert!(traders[0].score(min_trades) > traders[1].score(min_trades));
+    }
+
+    #[tokio::test]
+    async fn test_trades_older_than_restored_watermark_are_skipped() {
+        let trader = "0x2222222222222222222222222222222222222222";
+        let market = "0xmarket";
+        let now = Utc::now();
+
+        let mock = MockPolymarket::start().await;
+        mock.mount_positions(trader, vec![testing::position(market, 2000.0, 0.5)]).await;
+        // Nothing visible at startup, then a page mixing pre- and post-restart trades
+        mock.mount_trades(trader, vec![], Some(1)).await;
+        mock.mount_trades(
+            trader,
+            vec![
+                testing::trade(trader, market, "0xafter", 100.0, 0.5, now.timestamp() - 10),
+                testing::trade(trader, market, "0xbefore", 100.0, 0.5, now.timestamp() - 3600),
+            ],
+            None,
+        ).await;
+
+        let config = TradingConfig {
+            sizing_method: "equal".to_string(),
+            ..TradingConfig::default()
+        };
+        let engine = CopyEngine::with_data_client(config, mock.data_client());
+        engine.set_portfolio_value(Decimal::from(1000)).await;
+        engine.add_trader(trader.to_string()).await.unwrap();
+        engine
+            .restore_watermark(trader, "0xprevious".to_string(), now - chrono::Duration::minutes(10))
+            .await;
+
+        let intents = engine.poll_for_trades().await.unwrap();
+        assert_eq!(intents.len(), 1);
+        assert_eq!(intents[0].source_trade.transaction_hash, "0xafter");
+
+        let (newest, _) = engine.watermarks().await.remove(trader).unwrap();
+        assert!(newest.starts_with("0xafter"));
+    }
 }
@@ -6,9 +6,10 @@ mod position_sizer;
 mod strategy;
 
 pub use config::TradingConfig;
-pub use copy_engine::{CopyEngine, CopyTradeIntent, EngineStats};
-pub use position_sizer::{PositionSizer, SizingMethod};
+pub use copy_engine::{CopyEngine, CopyTradeIntent, EngineStats, DEFAULT_LOAD_CONCURRENCY};
+pub use position_sizer::{PositionSizer, SizingMethod, ZeroSizeReason, SIZING_EXAMPLES};
 pub use strategy::{
-    EntryValidation, ExitReason, ExitSignal, ExitUrgency, PortfolioState, PositionRisk,
-    Strategy, StrategyConfig, StrategyPosition,
+    EntryQuote, EntryValidation, ExitReason, ExitSignal, ExitUrgency, PortfolioState, PositionRisk,
+    PruneAction, SourceHedgePolicy, SourcePnlFilter, Strategy, StrategyConfig, StrategyPosition,
+    MAX_ENTRY_PATIENCE_SECS,
 };
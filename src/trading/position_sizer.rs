@@ -3,6 +3,7 @@
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal_macros::dec;
+use tracing::debug;
 
 use crate::models::TraderMetrics;
 use super::TradingConfig;
@@ -31,6 +32,105 @@ impl SizingMethod {
     }
 }
 
+/// Why sizing produced no trade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ZeroSizeReason {
+    /// Our portfolio value isn't positive
+    NoPortfolio,
+    /// The sizing method found no edge (e.g. Kelly on a losing record)
+    NoEdge,
+    /// `max_portfolio_allocation` (or its ramped-down cap) is already used up
+    ExposureCap,
+    /// The size left after constraints is below `min_trade_size`
+    BelowMinimum,
+}
+
+impl std::fmt::Display for ZeroSizeReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::NoPortfolio => "no portfolio value",
+            Self::NoEdge => "no edge",
+            Self::ExposureCap => "exposure cap",
+            Self::BelowMinimum => "below minimum size",
+        })
+    }
+}
+
+/// A position size, with the reason it's zero when sizing produced nothing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SizingResult {
+    pub size: Decimal,
+    pub zero_reason: Option<ZeroSizeReason>,
+}
+
+impl SizingResult {
+    fn sized(size: Decimal) -> Self {
+        Self { size, zero_reason: None }
+    }
+
+    fn zero(reason: ZeroSizeReason) -> Self {
+        Self { size: Decimal::ZERO, zero_reason: Some(reason) }
+    }
+}
+
+/// Source trade size, as a fraction of the trader's portfolio, treated as an ordinary bet.
+const CONVICTION_REFERENCE: Decimal = dec!(0.05);
+
+/// A hypothetical copy trade, sized with the live config to show what it means in practice.
+#[derive(Debug, Clone, Copy)]
+pub struct SizingExample {
+    pub portfolio_value: Decimal,
+    pub source_portfolio_value: Decimal,
+    pub source_trade_size: Decimal,
+    pub win_rate: f64,
+    /// Average win as a multiple of the average loss
+    pub win_loss_ratio: Decimal,
+    pub max_drawdown: f64,
+}
+
+/// Worked examples shown by `config`: a trader with a modest edge, and one without.
+pub const SIZING_EXAMPLES: [SizingExample; 2] = [
+    SizingExample {
+        portfolio_value: dec!(1000),
+        source_portfolio_value: dec!(2000),
+        source_trade_size: dec!(200),
+        win_rate: 0.6,
+        win_loss_ratio: dec!(1),
+        max_drawdown: 0.2,
+    },
+    SizingExample {
+        portfolio_value: dec!(1000),
+        source_portfolio_value: dec!(2000),
+        source_trade_size: dec!(200),
+        win_rate: 0.45,
+        win_loss_ratio: dec!(1),
+        max_drawdown: 0.35,
+    },
+];
+
+impl SizingExample {
+    /// Trader metrics with the example's record.
+    pub fn metrics(&self) -> TraderMetrics {
+        let mut metrics = TraderMetrics::new("example".to_string());
+        metrics.win_rate = self.win_rate;
+        metrics.avg_win = self.win_loss_ratio;
+        metrics.avg_loss = Decimal::ONE;
+        metrics.max_drawdown = self.max_drawdown;
+        metrics
+    }
+
+    /// Size `sizer` would give this trade, starting with no exposure.
+    pub fn size(&self, sizer: &PositionSizer) -> Decimal {
+        sizer.calculate_size(
+            self.source_trade_size,
+            self.source_portfolio_value,
+            self.portfolio_value,
+            Some(&self.metrics()),
+            Decimal::ZERO,
+        )
+    }
+}
+
 /// Calculator for optimal position sizes.
 pub struct PositionSizer {
     config: TradingConfig,
@@ -63,6 +163,29 @@ impl PositionSizer {
         trader_metrics: Option<&TraderMetrics>,
         current_exposure: Decimal,
     ) -> Decimal {
+        self.size_with_reason(
+            source_trade_size,
+            source_portfolio_value,
+            our_portfolio_value,
+            trader_metrics,
+            current_exposure,
+            self.config.max_portfolio_allocation,
+        )
+        .size
+    }
+
+    /// Like [`calculate_size`](Self::calculate_size), but also says which constraint
+    /// zeroed the size when there's no trade. Total exposure is capped at `max_allocation`
+    /// of our portfolio rather than the configured maximum, so callers can ramp it.
+    pub fn size_with_reason(
+        &self,
+        source_trade_size: Decimal,
+        source_portfolio_value: Decimal,
+        our_portfolio_value: Decimal,
+        trader_metrics: Option<&TraderMetrics>,
+        current_exposure: Decimal,
+        max_allocation: Decimal,
+    ) -> SizingResult {
         // Base multiplier from portfolio ratio
         let base_multiplier = if source_portfolio_value > Decimal::ZERO {
             our_portfolio_value / source_portfolio_value
@@ -82,9 +205,10 @@ impl PositionSizer {
             }
             SizingMethod::Equal => raw_size,
         };
+        let sized = sized * self.conviction_multiplier(source_trade_size, source_portfolio_value);
 
         // Apply constraints
-        self.apply_constraints(sized, our_portfolio_value, current_exposure)
+        self.apply_constraints(sized, our_portfolio_value, current_exposure, max_allocation)
     }
 
     /// Kelly criterion position sizing.
@@ -104,16 +228,13 @@ impl PositionSizer {
             return base_size * dec!(0.1); // Conservative if no metrics
         };
 
-        if m.win_rate < 0.5 || m.avg_loss.is_zero() {
+        if m.win_rate < 0.5 {
             return Decimal::ZERO; // No edge, don't bet
         }
 
-        let p = m.win_rate;
-        let q = 1.0 - p;
-        let b = m.avg_win.to_f64().unwrap_or(1.0) / m.avg_loss.to_f64().unwrap_or(1.0);
-
-        let kelly = (p * b - q) / b;
-
+        // A record with no losses counts as a strong (not zero) edge; the Kelly
+        // fraction and base size below still cap it
+        let kelly = m.kelly();
         if kelly <= 0.0 {
             return Decimal::ZERO;
         }
@@ -132,6 +253,21 @@ impl PositionSizer {
         kelly_size.min(base_size)
     }
 
+    /// Conviction multiplier: 1x for a trade worth [`CONVICTION_REFERENCE`] of the source
+    /// portfolio, proportionally more or less around it, clamped to
+    /// `[1 / max_conviction_multiplier, max_conviction_multiplier]`.
+    fn conviction_multiplier(&self, source_trade_size: Decimal, source_portfolio_value: Decimal) -> Decimal {
+        if !self.config.use_conviction_scaling || source_portfolio_value <= Decimal::ZERO {
+            return Decimal::ONE;
+        }
+
+        let cap = self.config.max_conviction_multiplier.max(Decimal::ONE);
+        let conviction = source_trade_size / source_portfolio_value;
+        let multiplier = (conviction / CONVICTION_REFERENCE).clamp(Decimal::ONE / cap, cap);
+        debug!(conviction = %conviction, multiplier = %multiplier, "Conviction scaling");
+        multiplier
+    }
+
     /// Fixed fraction position sizing.
     fn fixed_fraction_size(&self, portfolio_value: Decimal) -> Decimal {
         portfolio_value * self.config.max_single_position
@@ -170,7 +306,19 @@ impl PositionSizer {
         size: Decimal,
         portfolio_value: Decimal,
         current_exposure: Decimal,
-    ) -> Decimal {
+        max_allocation: Decimal,
+    ) -> SizingResult {
+        if portfolio_value <= Decimal::ZERO {
+            debug!(portfolio = %portfolio_value, "No trade: portfolio value is not positive");
+            return SizingResult::zero(ZeroSizeReason::NoPortfolio);
+        }
+
+        // Checked before the minimum below, which would otherwise lift it to a trade
+        if size <= Decimal::ZERO {
+            debug!(method = ?self.method, "No trade: sizing method found no edge");
+            return SizingResult::zero(ZeroSizeReason::NoEdge);
+        }
+
         let mut final_size = size;
 
         // Min/max trade size
@@ -182,19 +330,25 @@ impl PositionSizer {
         final_size = final_size.min(max_position);
 
         // Max portfolio allocation constraint
-        let max_total = portfolio_value * self.config.max_portfolio_allocation;
+        let max_total = portfolio_value * max_allocation;
         let remaining_capacity = max_total - current_exposure;
         if remaining_capacity <= Decimal::ZERO {
-            return Decimal::ZERO;
+            debug!(
+                exposure = %current_exposure,
+                max_total = %max_total,
+                "No trade: portfolio allocation exhausted"
+            );
+            return SizingResult::zero(ZeroSizeReason::ExposureCap);
         }
         final_size = final_size.min(remaining_capacity);
 
         // Final sanity check
-        if final_size < self.config.min_trade_size {
-            return Decimal::ZERO;
+        if final_size <= Decimal::ZERO || final_size < self.config.min_trade_size {
+            debug!(size = %final_size, "No trade: size below minimum after constraints");
+            return SizingResult::zero(ZeroSizeReason::BelowMinimum);
         }
 
-        final_size
+        SizingResult::sized(final_size)
     }
 
     /// Calculate aggregate size when copying multiple traders for the same market.
@@ -210,7 +364,7 @@ impl PositionSizer {
         // Weight by composite score
         let total_score: f64 = trader_allocations
             .iter()
-            .map(|(_, m)| m.composite_score())
+            .map(|(_, m)| m.composite_score(self.config.min_trades_for_score))
             .sum();
 
         if total_score <= 0.0 {
@@ -220,7 +374,7 @@ impl PositionSizer {
         let mut weighted_size = Decimal::ZERO;
 
         for (base_size, metrics) in trader_allocations {
-            let weight = metrics.composite_score() / total_score;
+            let weight = metrics.composite_score(self.config.min_trades_for_score) / total_score;
             weighted_size += *base_size * Decimal::try_from(weight).unwrap_or(Decimal::ZERO);
         }
 
@@ -256,6 +410,30 @@ mod tests {
         assert!(size < dec!(100)); // Less than source due to Kelly
     }
 
+    #[test]
+    fn test_all_wins_with_no_losses_sizes_positive_and_capped() {
+        let sizer = PositionSizer::new(TradingConfig::default());
+
+        let mut metrics = TraderMetrics::new("0x123".to_string());
+        metrics.total_trades = 20;
+        metrics.winning_trades = 20;
+        metrics.win_rate = 1.0;
+        metrics.avg_win = dec!(50);
+        metrics.avg_loss = Decimal::ZERO;
+
+        assert_eq!(metrics.kelly(), 1.0);
+        let size = sizer.kelly_size(dec!(100), Some(&metrics), dec!(1000));
+        assert!(size > Decimal::ZERO);
+        assert!(size <= dec!(100));
+        // Quarter Kelly of $1000, with no drawdown, exceeds the $100 base: capped at base
+        assert_eq!(size, dec!(100));
+        assert_eq!(metrics.suggested_allocation(), 0.25);
+
+        // Neither wins nor losses is still no edge
+        metrics.avg_win = Decimal::ZERO;
+        assert_eq!(sizer.kelly_size(dec!(100), Some(&metrics), dec!(1000)), Decimal::ZERO);
+    }
+
     #[test]
     fn test_constraints() {
         let config = TradingConfig {
@@ -277,4 +455,77 @@ mod tests {
         assert!(size <= dec!(100)); // 10% of $1000
         assert!(size <= dec!(50));  // Max trade size
     }
+
+    #[test]
+    fn test_conviction_scaling_favors_large_source_bets() {
+        let config = TradingConfig {
+            sizing_method: "equal".to_string(),
+            max_single_position: Decimal::ONE,
+            max_portfolio_allocation: Decimal::ONE,
+            max_trade_size: dec!(100000),
+            use_conviction_scaling: true,
+            ..Default::default()
+        };
+        let scaled = PositionSizer::new(config.clone());
+        let flat = PositionSizer::new(TradingConfig { use_conviction_scaling: false, ..config });
+
+        // Source portfolio $100k, ours $10k: a 2% nibble and a 20% conviction bet
+        let copy_ratio = |sizer: &PositionSizer, source_trade: Decimal| {
+            sizer.calculate_size(source_trade, dec!(100000), dec!(10000), None, Decimal::ZERO) / source_trade
+        };
+
+        assert_eq!(copy_ratio(&flat, dec!(2000)), copy_ratio(&flat, dec!(20000)));
+        let nibble = copy_ratio(&scaled, dec!(2000));
+        let conviction = copy_ratio(&scaled, dec!(20000));
+        assert!(conviction > nibble, "{} <= {}", conviction, nibble);
+        // 20% is 4x the reference, capped at 3x; 2% is 0.4x
+        assert_eq!(conviction, dec!(0.3));
+        assert_eq!(nibble, dec!(0.04));
+    }
+
+    #[test]
+    fn test_losses_never_produce_negative_size() {
+        let config = TradingConfig {
+            min_trade_size: Decimal::ZERO,
+            ..Default::default()
+        };
+        let sizer = PositionSizer::new(config);
+
+        // Portfolio wiped out by realized losses
+        for portfolio in [dec!(-500), Decimal::ZERO] {
+            let size = sizer.calculate_size(dec!(100), dec!(10000), portfolio, None, Decimal::ZERO);
+            assert_eq!(size, Decimal::ZERO);
+        }
+
+        // Exposure already beyond the allocation cap
+        let size = sizer.calculate_size(dec!(100), dec!(10000), dec!(1000), None, dec!(5000));
+        assert_eq!(size, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_sizing_examples_match_calculate_size() {
+        for method in ["kelly", "fixed", "risk_parity", "equal"] {
+            let sizer = PositionSizer::new(TradingConfig {
+                sizing_method: method.to_string(),
+                ..Default::default()
+            });
+
+            for example in &SIZING_EXAMPLES {
+                let mut metrics = TraderMetrics::new("direct".to_string());
+                metrics.win_rate = example.win_rate;
+                metrics.avg_win = example.win_loss_ratio;
+                metrics.avg_loss = Decimal::ONE;
+                metrics.max_drawdown = example.max_drawdown;
+
+                let direct = sizer.calculate_size(
+                    example.source_trade_size,
+                    example.source_portfolio_value,
+                    example.portfolio_value,
+                    Some(&metrics),
+                    Decimal::ZERO,
+                );
+                assert_eq!(example.size(&sizer), direct, "{} {:?}", method, example);
+            }
+        }
+    }
 }
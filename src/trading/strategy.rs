@@ -5,6 +5,8 @@
 //! - When to exit positions (profit targets, stop losses, time-based)
 //! - Portfolio-level risk management
 
+use std::sync::Arc;
+
 use chrono::{DateTime, Duration, Utc};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
@@ -12,10 +14,78 @@ use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
-use crate::models::TraderMetrics;
+use crate::clock::{Clock, SystemClock};
+use crate::models::{TraderMetrics, DEFAULT_MIN_TRADES_FOR_SCORE};
+
+/// Longest allowed entry patience window, in seconds.
+pub const MAX_ENTRY_PATIENCE_SECS: i64 = 60;
+
+/// Which source buys to copy, by the P&L of the source's existing position.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourcePnlFilter {
+    /// Copy regardless of the source's position
+    #[default]
+    Any,
+    /// Momentum: only add where the source is already winning
+    ProfitOnly,
+    /// Mean reversion: only buy the dip where the source is underwater
+    LossOnly,
+}
+
+impl std::fmt::Display for SourcePnlFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Any => "any",
+            Self::ProfitOnly => "profit_only",
+            Self::LossOnly => "loss_only",
+        })
+    }
+}
+
+/// What to do with a source's opposite-side trade that doesn't shrink the position we copied.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceHedgePolicy {
+    /// Keep our position and skip the trade
+    #[default]
+    Ignore,
+    /// Close our position and copy the trade as a new, inverse position
+    Flip,
+}
+
+impl std::fmt::Display for SourceHedgePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Ignore => "ignore",
+            Self::Flip => "flip",
+        })
+    }
+}
+
+/// What to do with a tracked trader whose copied trades keep losing money.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PruneAction {
+    /// Stop copying new trades but keep tracking (reversible with `resume`)
+    #[default]
+    Pause,
+    /// Stop tracking the trader altogether
+    Untrack,
+}
+
+impl std::fmt::Display for PruneAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Pause => "pause",
+            Self::Untrack => "untrack",
+        })
+    }
+}
 
 /// Trading strategy configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct StrategyConfig {
     // === Entry Rules ===
     /// Maximum age of a trade to copy (seconds)
@@ -30,12 +100,55 @@ pub struct StrategyConfig {
     /// Maximum slippage from source trade price
     pub max_entry_slippage: Decimal,
 
+    /// Book depth (USDC) at or below which the full `max_entry_slippage` applies;
+    /// deeper books get a proportionally tighter tolerance (None = fixed)
+    pub slippage_reference_depth: Option<Decimal>,
+
+    /// Floor for the depth-adjusted slippage tolerance
+    pub min_entry_slippage: Decimal,
+
+    /// Fraction of the slippage tolerance given up with each earlier entry into the
+    /// market still held, so repeated adds must fill closer to the source (0 = no tightening)
+    pub add_slippage_decay: Decimal,
+
+    /// Cap orders at this fraction of the book depth (USDC) within the slippage band, so
+    /// we never sweep the book (None = uncapped). Needs a live order book; backtests skip it
+    pub max_book_fraction: Option<Decimal>,
+
+    /// Cap orders at this fraction of the market's 24h volume (USDC), so we stay a small
+    /// part of its flow (None = uncapped). Backtests have no historical volume and skip it
+    pub max_volume_fraction: Option<Decimal>,
+
+    /// Maximum adverse slippage of the actual fill versus the validated price (None = unchecked)
+    pub max_realized_slippage: Option<Decimal>,
+
+    /// Immediately close positions whose fill exceeded `max_realized_slippage` (otherwise just alert)
+    pub close_on_bad_fill: bool,
+
     /// Minimum trader composite score (0-100)
     pub min_trader_score: f64,
 
+    /// Seconds to rest a limit order at our validated price before crossing
+    /// the spread with a market order (None = cross immediately). The wait runs
+    /// inside the tick, so it's capped at `MAX_ENTRY_PATIENCE_SECS`.
+    pub entry_patience_secs: Option<i64>,
+
+    /// Seconds to wait after seeing a source trade before copying it, re-checking
+    /// price and slippage afterwards (None = copy immediately)
+    pub copy_delay_secs: Option<i64>,
+
     /// Only copy trades if trader is in profit overall
     pub require_profitable_trader: bool,
 
+    /// Only copy entries into markets the source didn't already hold (skip adds)
+    pub new_markets_only: bool,
+
+    /// Only copy buys where the source's existing position is in profit or in a loss
+    pub source_position_pnl_filter: SourcePnlFilter,
+
+    /// Skip source trades below this notional (USDC) as dust
+    pub min_source_trade_usdc: Decimal,
+
     /// Only copy trades in markets with sufficient liquidity
     pub min_market_liquidity: Decimal,
 
@@ -46,12 +159,39 @@ pub struct StrategyConfig {
     /// Stop loss percentage (e.g., 0.1 = 10% loss)
     pub stop_loss_pct: Decimal,
 
+    /// Exit once the price gives back this fraction of its best level since entry
+    /// (e.g., 0.1 = 10% below the peak for a buy; None = off)
+    pub trailing_stop_pct: Option<Decimal>,
+
+    /// How strongly to tighten take-profit/stop-loss by the price room left
+    /// toward 1 (upside) or 0 (downside) from entry (0 = flat, 1 = fully scaled)
+    pub exit_price_scaling: Decimal,
+
+    /// Bank any position up this much (e.g. 0.8 = 80%) immediately, before every other
+    /// exit rule including the minimum hold and trader following (None = off)
+    pub hard_take_profit: Option<Decimal>,
+
+    /// Exit once the price leaves this (low, high) band, e.g. (0.02, 0.98), where little
+    /// upside is left against the full downside (None = off)
+    pub exit_price_extreme: Option<(Decimal, Decimal)>,
+
     /// Maximum holding period in hours
     pub max_holding_hours: i64,
 
+    /// Seconds after entry during which only portfolio-risk exits apply
+    pub min_hold_secs: i64,
+
     /// Exit if source trader exits
     pub follow_trader_exits: bool,
 
+    /// Handling of opposite-side source trades that aren't exits of the source's position.
+    /// Only the backtester and paper trader replay source trades against our positions;
+    /// the live bot exits on trader holdings instead and ignores this.
+    pub source_hedge_policy: SourceHedgePolicy,
+
+    /// Ignore trader-exit signals when the holdings snapshot is older than this (seconds)
+    pub holdings_max_age_secs: i64,
+
     /// Exit positions that approach market resolution
     pub exit_before_resolution_hours: i64,
 
@@ -59,6 +199,9 @@ pub struct StrategyConfig {
     /// Maximum portfolio drawdown before halting (0-1)
     pub max_portfolio_drawdown: Decimal,
 
+    /// Close everything and pause once total return from starting capital reaches this (None = off)
+    pub portfolio_take_profit: Option<Decimal>,
+
     /// Maximum number of concurrent positions
     pub max_concurrent_positions: usize,
 
@@ -70,6 +213,19 @@ pub struct StrategyConfig {
 
     /// Cool-off period after a losing trade (seconds)
     pub loss_cooloff_secs: i64,
+
+    // === Trader Pruning ===
+    /// Act on traders whose copied trades lost at least `prune_loss_usdc` over the window
+    pub auto_prune_losers: bool,
+
+    /// Realized loss (USDC) on a trader's copied positions that triggers pruning
+    pub prune_loss_usdc: Decimal,
+
+    /// How far back closed positions count towards a trader's loss (days)
+    pub prune_window_days: i64,
+
+    /// Whether a losing trader is paused or untracked
+    pub prune_action: PruneAction,
 }
 
 impl Default for StrategyConfig {
@@ -80,23 +236,49 @@ impl Default for StrategyConfig {
             min_entry_price: dec!(0.05),      // Don't buy below 5%
             max_entry_price: dec!(0.95),      // Don't buy above 95%
             max_entry_slippage: dec!(0.03),   // 3% slippage tolerance
+            slippage_reference_depth: None,   // Fixed tolerance
+            min_entry_slippage: dec!(0.005),  // Never demand better than 0.5%
+            add_slippage_decay: dec!(0),      // Same tolerance for every add
+            max_book_fraction: None,          // Take whatever the book has
+            max_volume_fraction: None,        // Ignore daily volume
+            max_realized_slippage: None,      // Don't check fills
+            close_on_bad_fill: false,         // Alert only
             min_trader_score: 40.0,           // Minimum composite score
+            entry_patience_secs: None,        // Cross the spread immediately
+            copy_delay_secs: None,            // Copy as soon as a trade is seen
             require_profitable_trader: true,
+            new_markets_only: false,
+            source_position_pnl_filter: SourcePnlFilter::Any,
+            min_source_trade_usdc: dec!(0),   // Copy any size
             min_market_liquidity: dec!(1000), // $1000 min liquidity
 
             // Exit rules
             take_profit_pct: dec!(0.25),      // 25% profit target
             stop_loss_pct: dec!(0.15),        // 15% stop loss
+            trailing_stop_pct: None,          // Fixed stop only
+            exit_price_scaling: dec!(0),      // Flat percentages
+            hard_take_profit: None,           // No override
+            exit_price_extreme: None,         // Hold through extreme prices
             max_holding_hours: 168,           // 7 days max hold
+            min_hold_secs: 0,                 // Exit rules apply immediately
             follow_trader_exits: true,
+            source_hedge_policy: SourceHedgePolicy::Ignore, // Keep our position
+            holdings_max_age_secs: 600,       // 10 minutes
             exit_before_resolution_hours: 24, // Exit 24h before resolution
 
             // Portfolio risk
             max_portfolio_drawdown: dec!(0.20),  // 20% max DD
+            portfolio_take_profit: None,         // No portfolio-level target
             max_concurrent_positions: 10,
             max_single_market_exposure: dec!(0.25), // 25% max in one market
             min_trade_interval_secs: 60,         // 1 min between trades
             loss_cooloff_secs: 300,              // 5 min after loss
+
+            // Trader pruning
+            auto_prune_losers: false,
+            prune_loss_usdc: dec!(50),
+            prune_window_days: 14,
+            prune_action: PruneAction::Pause,
         }
     }
 }
@@ -111,8 +293,12 @@ pub struct StrategyPosition {
     pub current_price: Decimal,
     pub size: Decimal,
     pub unrealized_pnl: Decimal,
+    /// Best price since entry: the high for a buy, the low for a sell
+    pub peak_price: Decimal,
     pub opened_at: DateTime<Utc>,
     pub source_trader: Option<String>,
+    /// Fills the position was built from: 1 when opened, plus one per add
+    pub entries: u32,
 }
 
 impl StrategyPosition {
@@ -124,14 +310,43 @@ impl StrategyPosition {
         (self.current_price - self.entry_price) / self.entry_price
     }
 
+    /// Fraction of `peak_price` the current price has given back.
+    pub fn drawdown_from_peak(&self) -> Decimal {
+        if self.peak_price.is_zero() {
+            return Decimal::ZERO;
+        }
+        let given_back = if self.side.eq_ignore_ascii_case("sell") {
+            self.current_price - self.peak_price
+        } else {
+            self.peak_price - self.current_price
+        };
+        (given_back / self.peak_price).max(Decimal::ZERO)
+    }
+
     /// Check if position is profitable.
     pub fn is_profitable(&self) -> bool {
         self.unrealized_pnl > Decimal::ZERO
     }
 
-    /// Get holding duration.
-    pub fn holding_duration(&self) -> Duration {
-        Utc::now() - self.opened_at
+    /// Get holding duration as of `now`.
+    pub fn holding_duration(&self, now: DateTime<Utc>) -> Duration {
+        now - self.opened_at
+    }
+}
+
+/// The source trade being copied and the price we'd enter at.
+#[derive(Debug, Clone, Copy)]
+pub struct EntryQuote {
+    pub source_trade_time: DateTime<Utc>,
+    pub current_price: Decimal,
+    pub source_price: Decimal,
+    /// USDC on the side we'd take; tightens slippage tolerance in deep markets
+    pub book_depth: Option<Decimal>,
+}
+
+impl EntryQuote {
+    pub fn new(source_trade_time: DateTime<Utc>, current_price: Decimal, source_price: Decimal) -> Self {
+        Self { source_trade_time, current_price, source_price, book_depth: None }
     }
 }
 
@@ -152,6 +367,11 @@ impl EntryValidation {
         }
     }
 
+    /// Reason without its specific values (e.g. "Price too high"), for aggregation.
+    pub fn category(&self) -> &str {
+        self.reason.split(':').next().unwrap_or_default().trim()
+    }
+
     pub fn deny(reason: impl Into<String>) -> Self {
         Self {
             allowed: false,
@@ -173,15 +393,39 @@ pub struct ExitSignal {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ExitReason {
     TakeProfit,
+    HardTakeProfit,
+    ExtremePrice,
     StopLoss,
+    TrailingStop,
     MaxHoldingPeriod,
     TraderExited,
     MarketResolution,
     PortfolioRisk,
+    PortfolioTakeProfit,
     ManualClose,
     None,
 }
 
+impl ExitReason {
+    /// Stable name recorded with closed positions.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TakeProfit => "take_profit",
+            Self::HardTakeProfit => "hard_take_profit",
+            Self::ExtremePrice => "extreme_price",
+            Self::StopLoss => "stop_loss",
+            Self::TrailingStop => "trailing_stop",
+            Self::MaxHoldingPeriod => "max_holding_period",
+            Self::TraderExited => "trader_exited",
+            Self::MarketResolution => "market_resolution",
+            Self::PortfolioRisk => "portfolio_risk",
+            Self::PortfolioTakeProfit => "portfolio_take_profit",
+            Self::ManualClose => "manual_close",
+            Self::None => "none",
+        }
+    }
+}
+
 /// How urgently to exit.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ExitUrgency {
@@ -209,15 +453,40 @@ pub struct PortfolioState {
     pub last_loss_at: Option<DateTime<Utc>>,
 }
 
+impl PortfolioState {
+    /// Current equity: starting capital plus realized and unrealized P&L.
+    ///
+    /// Since cash is starting capital plus realized P&L minus the cost basis of
+    /// open positions, this also equals cash plus their market value, with shorts
+    /// (sold for their cost basis, owed back at market) counting negative in both.
+    pub fn equity(&self) -> Decimal {
+        self.total_value + self.realized_pnl + self.unrealized_pnl
+    }
+}
+
 /// Trading strategy engine.
 pub struct Strategy {
     config: StrategyConfig,
+    min_trades_for_score: u32,
+    clock: Arc<dyn Clock>,
 }
 
 impl Strategy {
     /// Create a new strategy with configuration.
     pub fn new(config: StrategyConfig) -> Self {
-        Self { config }
+        Self { config, min_trades_for_score: DEFAULT_MIN_TRADES_FOR_SCORE, clock: Arc::new(SystemClock) }
+    }
+
+    /// Read the current time from `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Score traders with fewer than `min_trades` trades as 0 (see `TradingConfig::min_trades_for_score`).
+    pub fn with_min_trades_for_score(mut self, min_trades: u32) -> Self {
+        self.min_trades_for_score = min_trades;
+        self
     }
 
     /// Create with default configuration.
@@ -238,17 +507,16 @@ impl Strategy {
     /// (uses current time), or pass a simulated time for backtesting.
     pub fn validate_entry(
         &self,
-        source_trade_time: DateTime<Utc>,
-        current_price: Decimal,
-        source_price: Decimal,
+        quote: &EntryQuote,
         proposed_size: Decimal,
         trader_metrics: Option<&TraderMetrics>,
         portfolio: &PortfolioState,
         market_positions: &[StrategyPosition],
         reference_time: Option<DateTime<Utc>>,
     ) -> EntryValidation {
+        let EntryQuote { source_trade_time, current_price, source_price, book_depth } = *quote;
         // Check trade age (skip for backtesting when reference_time equals trade time)
-        let now = reference_time.unwrap_or_else(Utc::now);
+        let now = reference_time.unwrap_or_else(|| self.clock.now());
         let trade_age = now - source_trade_time;
         if trade_age.num_seconds() > self.config.max_trade_age_secs {
             return EntryValidation::deny(format!(
@@ -258,37 +526,14 @@ impl Strategy {
             ));
         }
 
-        // Check price bounds
-        if current_price < self.config.min_entry_price {
-            return EntryValidation::deny(format!(
-                "Price too low: {} < {}",
-                current_price, self.config.min_entry_price
-            ));
-        }
-        if current_price > self.config.max_entry_price {
-            return EntryValidation::deny(format!(
-                "Price too high: {} > {}",
-                current_price, self.config.max_entry_price
-            ));
-        }
-
-        // Check slippage from source trade
-        let slippage = if source_price > Decimal::ZERO {
-            ((current_price - source_price) / source_price).abs()
-        } else {
-            Decimal::ZERO
-        };
-        if slippage > self.config.max_entry_slippage {
-            return EntryValidation::deny(format!(
-                "Slippage too high: {}% > {}%",
-                slippage * dec!(100),
-                self.config.max_entry_slippage * dec!(100)
-            ));
+        let prior_entries = market_positions.iter().map(|p| p.entries as usize).sum();
+        if let Some(validation) = self.check_entry_price(current_price, source_price, book_depth, prior_entries) {
+            return validation;
         }
 
         // Check trader quality
         if let Some(metrics) = trader_metrics {
-            let score = metrics.composite_score();
+            let score = metrics.composite_score(self.min_trades_for_score);
             if score < self.config.min_trader_score {
                 return EntryValidation::deny(format!(
                     "Trader score too low: {:.1} < {}",
@@ -327,7 +572,7 @@ impl Strategy {
 
         // Check trade interval (anti-churn)
         if let Some(last_trade) = portfolio.last_trade_at {
-            let since_last = (Utc::now() - last_trade).num_seconds();
+            let since_last = (self.clock.now() - last_trade).num_seconds();
             if since_last < self.config.min_trade_interval_secs {
                 return EntryValidation::deny(format!(
                     "Too soon after last trade: {}s < {}s",
@@ -338,7 +583,7 @@ impl Strategy {
 
         // Check loss cool-off
         if let Some(last_loss) = portfolio.last_loss_at {
-            let since_loss = (Utc::now() - last_loss).num_seconds();
+            let since_loss = (self.clock.now() - last_loss).num_seconds();
             if since_loss < self.config.loss_cooloff_secs {
                 return EntryValidation::deny(format!(
                     "In loss cool-off period: {}s remaining",
@@ -350,6 +595,134 @@ impl Strategy {
         EntryValidation::allow(proposed_size)
     }
 
+    /// Slippage tolerance for a market with the given book depth.
+    ///
+    /// Thin books (at or below the reference depth) get the full `max_entry_slippage`;
+    /// deeper books scale it down by `reference / depth`, floored at `min_entry_slippage`.
+    pub fn effective_slippage_tolerance(&self, book_depth: Option<Decimal>) -> Decimal {
+        let max = self.config.max_entry_slippage;
+        let (Some(reference), Some(depth)) = (self.config.slippage_reference_depth, book_depth) else {
+            return max;
+        };
+        if depth <= reference || depth <= Decimal::ZERO {
+            return max;
+        }
+
+        (max * reference / depth).max(self.config.min_entry_slippage.min(max))
+    }
+
+    /// Slippage tolerance for an entry into a market whose open positions were built
+    /// from `prior_entries` fills.
+    ///
+    /// Each add gives up `add_slippage_decay` of the remaining depth-adjusted tolerance,
+    /// floored at `min_entry_slippage`.
+    pub fn slippage_budget(&self, book_depth: Option<Decimal>, prior_entries: usize) -> Decimal {
+        let base = self.effective_slippage_tolerance(book_depth);
+        if prior_entries == 0 {
+            return base;
+        }
+
+        let keep = Decimal::ONE - self.config.add_slippage_decay;
+        let tightened = (0..prior_entries).fold(base, |tolerance, _| tolerance * keep);
+        tightened.max(self.config.min_entry_slippage.min(base))
+    }
+
+    /// Cap an order at `max_book_fraction` of `band_depth`, the book depth (USDC)
+    /// within the slippage band.
+    pub fn cap_to_book_depth(&self, size: Decimal, band_depth: Decimal) -> Decimal {
+        match self.config.max_book_fraction {
+            Some(fraction) => size.min(band_depth * fraction),
+            None => size,
+        }
+    }
+
+    /// Cap an order at `max_volume_fraction` of the market's 24h volume (USDC).
+    pub fn cap_to_volume(&self, size: Decimal, volume_24h: Decimal) -> Decimal {
+        match self.config.max_volume_fraction {
+            Some(fraction) => size.min(volume_24h * fraction),
+            None => size,
+        }
+    }
+
+    /// Adverse slippage of a fill versus the validated price (positive = worse for us).
+    pub fn realized_slippage(&self, is_buy: bool, validated_price: Decimal, fill_price: Decimal) -> Decimal {
+        if validated_price.is_zero() {
+            return Decimal::ZERO;
+        }
+        let diff = if is_buy { fill_price - validated_price } else { validated_price - fill_price };
+        diff / validated_price
+    }
+
+    /// Whether a fill slipped beyond `max_realized_slippage`.
+    pub fn is_bad_fill(&self, is_buy: bool, validated_price: Decimal, fill_price: Decimal) -> bool {
+        matches!(
+            self.config.max_realized_slippage,
+            Some(max) if self.realized_slippage(is_buy, validated_price, fill_price) > max
+        )
+    }
+
+    /// Whether a source trade is large enough to be worth copying.
+    pub fn meets_min_source_size(&self, source_amount_usdc: Decimal) -> bool {
+        source_amount_usdc >= self.config.min_source_trade_usdc
+    }
+
+    /// Whether a source buy should be copied given whether the source already held the market.
+    pub fn allows_source_entry(&self, source_already_held: bool) -> bool {
+        !(self.config.new_markets_only && source_already_held)
+    }
+
+    /// Whether a source buy should be copied given the P&L of the source's position in
+    /// that outcome (None if the last holdings snapshot had no such position, which
+    /// passes neither the profit nor the loss filter).
+    pub fn allows_source_pnl(&self, source_pnl: Option<Decimal>) -> bool {
+        match self.config.source_position_pnl_filter {
+            SourcePnlFilter::Any => true,
+            SourcePnlFilter::ProfitOnly => source_pnl.is_some_and(|pnl| pnl > Decimal::ZERO),
+            SourcePnlFilter::LossOnly => source_pnl.is_some_and(|pnl| pnl < Decimal::ZERO),
+        }
+    }
+
+    /// Check the entry price against the configured bounds and its slippage from the
+    /// source's price, returning a denial if either fails.
+    pub fn check_entry_price(
+        &self,
+        current_price: Decimal,
+        source_price: Decimal,
+        book_depth: Option<Decimal>,
+        prior_entries: usize,
+    ) -> Option<EntryValidation> {
+        // Check price bounds
+        if current_price < self.config.min_entry_price {
+            return Some(EntryValidation::deny(format!(
+                "Price too low: {} < {}",
+                current_price, self.config.min_entry_price
+            )));
+        }
+        if current_price > self.config.max_entry_price {
+            return Some(EntryValidation::deny(format!(
+                "Price too high: {} > {}",
+                current_price, self.config.max_entry_price
+            )));
+        }
+
+        // Check slippage from source trade
+        let slippage = if source_price > Decimal::ZERO {
+            ((current_price - source_price) / source_price).abs()
+        } else {
+            Decimal::ZERO
+        };
+        let max_slippage = self.slippage_budget(book_depth, prior_entries);
+        if slippage > max_slippage {
+            return Some(EntryValidation::deny(format!(
+                "Slippage too high: {}% > {}%",
+                slippage * dec!(100),
+                max_slippage * dec!(100)
+            )));
+        }
+
+        None
+    }
+
     /// Check portfolio-level constraints.
     fn check_portfolio_constraints(
         &self,
@@ -384,8 +757,50 @@ impl Strategy {
         None
     }
 
+    /// Patience window for an entry, if configured and the entry isn't urgent.
+    pub fn entry_patience(&self, urgency: ExitUrgency) -> Option<std::time::Duration> {
+        if !matches!(urgency, ExitUrgency::Normal | ExitUrgency::Low) {
+            return None;
+        }
+        self.config
+            .entry_patience_secs
+            .filter(|secs| *secs > 0)
+            .map(|secs| std::time::Duration::from_secs(secs as u64))
+    }
+
+    /// Whether a trader whose copied positions realized `realized_pnl` over the prune
+    /// window should be pruned.
+    pub fn should_prune(&self, realized_pnl: Decimal) -> bool {
+        self.config.auto_prune_losers && realized_pnl <= -self.config.prune_loss_usdc
+    }
+
+    /// How long to wait before copying a source trade, if configured.
+    pub fn copy_delay(&self) -> Option<std::time::Duration> {
+        self.config
+            .copy_delay_secs
+            .filter(|secs| *secs > 0)
+            .map(|secs| std::time::Duration::from_secs(secs as u64))
+    }
+
     // ==================== Exit Signals ====================
 
+    /// Take-profit and stop-loss percentages for a position entered at `entry_price`.
+    ///
+    /// With `exit_price_scaling` > 0, each target shrinks linearly once the room
+    /// to the relevant price bound (1 for upside, 0 for downside) drops below 0.5.
+    pub fn exit_targets(&self, entry_price: Decimal) -> (Decimal, Decimal) {
+        let k = self.config.exit_price_scaling.clamp(Decimal::ZERO, Decimal::ONE);
+        let scale = |room: Decimal| {
+            let room_ratio = (room / dec!(0.5)).clamp(Decimal::ZERO, Decimal::ONE);
+            Decimal::ONE - k * (Decimal::ONE - room_ratio)
+        };
+
+        (
+            self.config.take_profit_pct * scale(Decimal::ONE - entry_price),
+            self.config.stop_loss_pct * scale(entry_price),
+        )
+    }
+
     /// Check if a position should be exited.
     pub fn check_exit(
         &self,
@@ -394,13 +809,54 @@ impl Strategy {
         trader_still_holding: bool,
         market_resolution_time: Option<DateTime<Utc>>,
     ) -> ExitSignal {
-        // Check take profit
+        // An extreme gain is banked no matter what the other rules say
         let return_pct = position.return_pct();
-        if return_pct >= self.config.take_profit_pct {
+        if let Some(hard) = self.config.hard_take_profit.filter(|hard| return_pct >= *hard) {
+            info!(
+                market = %position.market_id,
+                return_pct = %return_pct,
+                target = %hard,
+                "Hard take profit triggered"
+            );
+            return ExitSignal {
+                should_exit: true,
+                reason: ExitReason::HardTakeProfit,
+                urgency: ExitUrgency::Immediate,
+            };
+        }
+
+        // Too fresh to judge: skip straight to the hard portfolio-risk check
+        let min_hold = Duration::seconds(self.config.min_hold_secs);
+        if position.holding_duration(self.clock.now()) < min_hold {
+            return self.portfolio_risk_exit(portfolio);
+        }
+
+        // Near 0 or 1 the remaining risk/reward is lopsided whatever the return so far
+        if let Some((low, high)) = self.config.exit_price_extreme {
+            let price = position.current_price;
+            if price <= low || price >= high {
+                info!(
+                    market = %position.market_id,
+                    price = %price,
+                    low = %low,
+                    high = %high,
+                    "Price reached extreme band"
+                );
+                return ExitSignal {
+                    should_exit: true,
+                    reason: ExitReason::ExtremePrice,
+                    urgency: ExitUrgency::Normal,
+                };
+            }
+        }
+
+        // Check take profit
+        let (take_profit_pct, stop_loss_pct) = self.exit_targets(position.entry_price);
+        if return_pct >= take_profit_pct {
             debug!(
                 market = %position.market_id,
                 return_pct = %return_pct,
-                target = %self.config.take_profit_pct,
+                target = %take_profit_pct,
                 "Take profit triggered"
             );
             return ExitSignal {
@@ -411,11 +867,11 @@ impl Strategy {
         }
 
         // Check stop loss
-        if return_pct <= -self.config.stop_loss_pct {
+        if return_pct <= -stop_loss_pct {
             warn!(
                 market = %position.market_id,
                 return_pct = %return_pct,
-                stop = %self.config.stop_loss_pct,
+                stop = %stop_loss_pct,
                 "Stop loss triggered"
             );
             return ExitSignal {
@@ -425,8 +881,27 @@ impl Strategy {
             };
         }
 
+        // Check trailing stop
+        if let Some(trail) = self.config.trailing_stop_pct {
+            let drawdown = position.drawdown_from_peak();
+            if drawdown >= trail {
+                warn!(
+                    market = %position.market_id,
+                    peak = %position.peak_price,
+                    price = %position.current_price,
+                    trail = %trail,
+                    "Trailing stop triggered"
+                );
+                return ExitSignal {
+                    should_exit: true,
+                    reason: ExitReason::TrailingStop,
+                    urgency: ExitUrgency::Immediate,
+                };
+            }
+        }
+
         // Check max holding period
-        let holding_hours = position.holding_duration().num_hours();
+        let holding_hours = position.holding_duration(self.clock.now()).num_hours();
         if holding_hours >= self.config.max_holding_hours {
             info!(
                 market = %position.market_id,
@@ -457,7 +932,7 @@ impl Strategy {
 
         // Check market resolution proximity
         if let Some(resolution_time) = market_resolution_time {
-            let hours_to_resolution = (resolution_time - Utc::now()).num_hours();
+            let hours_to_resolution = (resolution_time - self.clock.now()).num_hours();
             if hours_to_resolution <= self.config.exit_before_resolution_hours
                 && hours_to_resolution > 0
             {
@@ -474,7 +949,11 @@ impl Strategy {
             }
         }
 
-        // Check portfolio risk
+        self.portfolio_risk_exit(portfolio)
+    }
+
+    /// Exit signal from portfolio-level risk alone.
+    fn portfolio_risk_exit(&self, portfolio: &PortfolioState) -> ExitSignal {
         if portfolio.current_drawdown >= self.config.max_portfolio_drawdown {
             warn!(
                 drawdown = %portfolio.current_drawdown,
@@ -494,21 +973,43 @@ impl Strategy {
         }
     }
 
+    /// Whether a holdings snapshot taken at `as_of` is recent enough to act on.
+    pub fn holdings_fresh(&self, as_of: Option<DateTime<Utc>>) -> bool {
+        as_of.is_some_and(|at| {
+            self.clock.now() - at <= Duration::seconds(self.config.holdings_max_age_secs)
+        })
+    }
+
     /// Evaluate all positions and return those that should be exited.
+    ///
+    /// Trader-exit signals are deferred when the holdings snapshot (taken at
+    /// `holdings_as_of`) is missing or stale, so old data can't close positions.
     pub fn evaluate_exits(
         &self,
         positions: &[StrategyPosition],
         portfolio: &PortfolioState,
         trader_holdings: &std::collections::HashMap<String, Vec<String>>, // trader -> market_ids
+        holdings_as_of: Option<DateTime<Utc>>,
     ) -> Vec<(StrategyPosition, ExitSignal)> {
+        let holdings_fresh = self.holdings_fresh(holdings_as_of);
+        let follows_traders = self.config.follow_trader_exits
+            && positions.iter().any(|p| p.source_trader.is_some());
+        if follows_traders && !holdings_fresh {
+            warn!(
+                as_of = ?holdings_as_of,
+                max_age_secs = self.config.holdings_max_age_secs,
+                "Trader holdings are stale, deferring trader-exit checks"
+            );
+        }
+
         positions
             .iter()
             .filter_map(|pos| {
-                // Check if source trader still holds
-                let trader_holding = pos.source_trader.as_ref().map_or(true, |trader| {
+                // Check if source trader still holds; stale data counts as still holding
+                let trader_holding = !holdings_fresh || pos.source_trader.as_ref().is_none_or(|trader| {
                     trader_holdings
                         .get(trader)
-                        .map_or(false, |markets| markets.contains(&pos.market_id))
+                        .is_some_and(|markets| markets.contains(&pos.market_id))
                 });
 
                 let signal = self.check_exit(pos, portfolio, trader_holding, None);
@@ -525,6 +1026,13 @@ impl Strategy {
 
     /// Check if trading should be halted due to portfolio risk.
     pub fn should_halt_trading(&self, portfolio: &PortfolioState) -> (bool, String) {
+        if let Some(target) = self.portfolio_take_profit_hit(portfolio) {
+            return (
+                true,
+                format!("Portfolio take-profit of {}% reached", (target * dec!(100)).round()),
+            );
+        }
+
         if portfolio.current_drawdown >= self.config.max_portfolio_drawdown {
             return (
                 true,
@@ -542,11 +1050,22 @@ impl Strategy {
         (false, String::new())
     }
 
+    /// The portfolio take-profit target, if total return from starting capital has reached it.
+    pub fn portfolio_take_profit_hit(&self, portfolio: &PortfolioState) -> Option<Decimal> {
+        let target = self.config.portfolio_take_profit?;
+        if portfolio.total_value <= Decimal::ZERO {
+            return None;
+        }
+        let total_return = (portfolio.equity() - portfolio.total_value) / portfolio.total_value;
+        (total_return >= target).then_some(target)
+    }
+
     /// Calculate position-level risk metrics.
     pub fn calculate_position_risk(&self, position: &StrategyPosition) -> PositionRisk {
         let return_pct = position.return_pct();
-        let distance_to_stop = return_pct + self.config.stop_loss_pct;
-        let distance_to_target = self.config.take_profit_pct - return_pct;
+        let (take_profit, stop_loss) = self.exit_targets(position.entry_price);
+        let distance_to_stop = return_pct + stop_loss;
+        let distance_to_target = take_profit - return_pct;
 
         let risk_score = if distance_to_stop <= Decimal::ZERO {
             1.0 // At or past stop loss
@@ -562,10 +1081,24 @@ impl Strategy {
             return_pct,
             distance_to_stop,
             distance_to_target,
-            holding_hours: position.holding_duration().num_hours(),
+            holding_hours: position.holding_duration(self.clock.now()).num_hours(),
             risk_score,
         }
     }
+
+    /// Risk metrics for each position, riskiest first.
+    pub fn aging_report(&self, positions: &[StrategyPosition]) -> Vec<(StrategyPosition, PositionRisk)> {
+        let mut report: Vec<_> = positions
+            .iter()
+            .map(|p| (p.clone(), self.calculate_position_risk(p)))
+            .collect();
+        report.sort_by(|a, b| {
+            b.1.risk_score
+                .total_cmp(&a.1.risk_score)
+                .then(b.1.holding_hours.cmp(&a.1.holding_hours))
+        });
+        report
+    }
 }
 
 /// Risk metrics for a position.
@@ -581,6 +1114,7 @@ pub struct PositionRisk {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::MockClock;
 
     fn make_position(entry: Decimal, current: Decimal, hours_ago: i64) -> StrategyPosition {
         StrategyPosition {
@@ -591,8 +1125,10 @@ mod tests {
             current_price: current,
             size: dec!(100),
             unrealized_pnl: (current - entry) * dec!(100),
+            peak_price: entry.max(current),
             opened_at: Utc::now() - Duration::hours(hours_ago),
             source_trader: Some("0x123".to_string()),
+            entries: 1,
         }
     }
 
@@ -610,6 +1146,123 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_price_scaled_take_profit_tighter_near_extremes() {
+        let strategy = Strategy::new(StrategyConfig {
+            exit_price_scaling: dec!(1),
+            ..StrategyConfig::default()
+        });
+
+        let (tp_mid, _) = strategy.exit_targets(dec!(0.5));
+        let (tp_high, _) = strategy.exit_targets(dec!(0.9));
+        assert!(dec!(0.9) * tp_high < dec!(0.5) * tp_mid);
+
+        // Flat by default
+        let flat = Strategy::default_strategy();
+        assert_eq!(flat.exit_targets(dec!(0.9)), flat.exit_targets(dec!(0.5)));
+    }
+
+    #[test]
+    fn test_aging_report_ranks_near_stop_position_first() {
+        let strategy = Strategy::new(StrategyConfig::default());
+        let healthy = make_position(dec!(0.50), dec!(0.55), 2);
+        // 13% down against a 15% stop
+        let near_stop = make_position(dec!(0.50), dec!(0.435), 1);
+
+        let report = strategy.aging_report(&[healthy, near_stop]);
+
+        assert_eq!(report[0].0.current_price, dec!(0.435));
+        assert!(report[0].1.risk_score >= 0.8);
+        assert!(report[0].1.distance_to_stop < dec!(0.05));
+        assert!(report[1].1.risk_score < report[0].1.risk_score);
+        assert_eq!(report[1].1.holding_hours, 2);
+    }
+
+    #[test]
+    fn test_stale_holdings_defer_trader_exit() {
+        let strategy = Strategy::default_strategy();
+        let position = make_position(dec!(0.50), dec!(0.52), 1);
+        let portfolio = make_portfolio();
+        // The source trader no longer shows the market
+        let holdings = std::collections::HashMap::from([("0x123".to_string(), vec![])]);
+
+        let stale = Utc::now() - Duration::seconds(strategy.config().holdings_max_age_secs + 60);
+        assert!(strategy.evaluate_exits(std::slice::from_ref(&position), &portfolio, &holdings, Some(stale)).is_empty());
+        assert!(strategy.evaluate_exits(std::slice::from_ref(&position), &portfolio, &holdings, None).is_empty());
+
+        let exits = strategy.evaluate_exits(&[position], &portfolio, &holdings, Some(Utc::now()));
+        assert_eq!(exits.len(), 1);
+        assert_eq!(exits[0].1.reason, ExitReason::TraderExited);
+    }
+
+    #[test]
+    fn test_min_hold_suppresses_early_stop_loss() {
+        let strategy = Strategy::new(StrategyConfig {
+            min_hold_secs: 600,
+            ..StrategyConfig::default()
+        });
+        let portfolio = make_portfolio();
+
+        // 20% down, but only a minute old
+        let mut fresh = make_position(dec!(0.50), dec!(0.40), 0);
+        fresh.opened_at = Utc::now() - Duration::seconds(60);
+        let signal = strategy.check_exit(&fresh, &portfolio, true, None);
+        assert!(!signal.should_exit);
+
+        // Portfolio risk still applies
+        let stressed = PortfolioState { current_drawdown: dec!(0.5), ..make_portfolio() };
+        assert_eq!(strategy.check_exit(&fresh, &stressed, true, None).reason, ExitReason::PortfolioRisk);
+
+        let aged = make_position(dec!(0.50), dec!(0.40), 1);
+        assert_eq!(strategy.check_exit(&aged, &portfolio, true, None).reason, ExitReason::StopLoss);
+    }
+
+    #[test]
+    fn test_hard_take_profit_overrides_trader_follow() {
+        // The fixed target is out of reach, so only trader following decides
+        let following = StrategyConfig {
+            take_profit_pct: dec!(1.0),
+            follow_trader_exits: true,
+            ..StrategyConfig::default()
+        };
+        let strategy = Strategy::new(StrategyConfig { hard_take_profit: Some(dec!(0.80)), ..following.clone() });
+        let portfolio = make_portfolio();
+
+        // +85% while the trader still holds
+        let gapped = make_position(dec!(0.50), dec!(0.925), 0);
+        let signal = strategy.check_exit(&gapped, &portfolio, true, None);
+        assert!(signal.should_exit);
+        assert_eq!(signal.reason, ExitReason::HardTakeProfit);
+        assert_eq!(signal.urgency, ExitUrgency::Immediate);
+
+        // Without the override the position follows the trader and is kept
+        let following = Strategy::new(following);
+        assert!(!following.check_exit(&gapped, &portfolio, true, None).should_exit);
+        assert_eq!(following.check_exit(&gapped, &portfolio, false, None).reason, ExitReason::TraderExited);
+    }
+
+    #[test]
+    fn test_extreme_price_exits_regardless_of_return() {
+        let strategy = Strategy::new(StrategyConfig {
+            exit_price_extreme: Some((dec!(0.02), dec!(0.98))),
+            ..StrategyConfig::default()
+        });
+        let portfolio = make_portfolio();
+
+        // Only +4.2%, well short of take profit, but almost nothing left to gain
+        let near_one = make_position(dec!(0.95), dec!(0.99), 24);
+        let signal = strategy.check_exit(&near_one, &portfolio, true, None);
+        assert!(signal.should_exit);
+        assert_eq!(signal.reason, ExitReason::ExtremePrice);
+
+        let near_zero = make_position(dec!(0.02), dec!(0.015), 24);
+        assert_eq!(strategy.check_exit(&near_zero, &portfolio, true, None).reason, ExitReason::ExtremePrice);
+
+        // Off by default
+        let default = Strategy::default_strategy();
+        assert!(!default.check_exit(&near_one, &portfolio, true, None).should_exit);
+    }
+
     #[test]
     fn test_take_profit() {
         let strategy = Strategy::default_strategy();
@@ -651,9 +1304,11 @@ mod tests {
 
         // Price too low
         let result = strategy.validate_entry(
-            Utc::now(),
-            dec!(0.02), // Too low
-            dec!(0.02),
+            &EntryQuote::new(
+                Utc::now(),
+                dec!(0.02), // Too low
+                dec!(0.02),
+            ),
             dec!(100),
             None,
             &portfolio,
@@ -665,9 +1320,11 @@ mod tests {
 
         // Price too high
         let result = strategy.validate_entry(
-            Utc::now(),
-            dec!(0.98), // Too high
-            dec!(0.98),
+            &EntryQuote::new(
+                Utc::now(),
+                dec!(0.98), // Too high
+                dec!(0.98),
+            ),
             dec!(100),
             None,
             &portfolio,
@@ -679,9 +1336,7 @@ mod tests {
 
         // Price OK
         let result = strategy.validate_entry(
-            Utc::now(),
-            dec!(0.50),
-            dec!(0.50),
+            &EntryQuote::new(Utc::now(), dec!(0.50), dec!(0.50)),
             dec!(100),
             None,
             &portfolio,
@@ -691,6 +1346,95 @@ mod tests {
         assert!(result.allowed);
     }
 
+    #[test]
+    fn test_thin_market_gets_wider_slippage_tolerance() {
+        let strategy = Strategy::new(StrategyConfig {
+            slippage_reference_depth: Some(dec!(5000)),
+            ..StrategyConfig::default()
+        });
+
+        let thin = strategy.effective_slippage_tolerance(Some(dec!(2000)));
+        let deep = strategy.effective_slippage_tolerance(Some(dec!(50000)));
+        assert!(thin > deep);
+        assert_eq!(thin, dec!(0.03)); // Fixed value is the ceiling
+        assert_eq!(strategy.effective_slippage_tolerance(None), dec!(0.03));
+
+        // 2% slippage passes in a thin book but not a deep one
+        let portfolio = make_portfolio();
+        let validate = |depth| strategy.validate_entry(
+            &EntryQuote { book_depth: Some(depth), ..EntryQuote::new(Utc::now(), dec!(0.51), dec!(0.50)) }, dec!(100), None, &portfolio, &[], None,
+        );
+        assert!(validate(dec!(2000)).allowed);
+        assert!(!validate(dec!(50000)).allowed);
+    }
+
+    #[test]
+    fn test_repeated_adds_face_tighter_slippage_tolerance() {
+        let strategy = Strategy::new(StrategyConfig {
+            add_slippage_decay: dec!(0.25),
+            ..StrategyConfig::default()
+        });
+
+        let first = strategy.slippage_budget(None, 0);
+        let third = strategy.slippage_budget(None, 2);
+        assert_eq!(first, dec!(0.03));
+        assert_eq!(third, dec!(0.016875));
+
+        // 2.5% slippage passes on the first entry but not the third into the same position
+        let portfolio = make_portfolio();
+        let held = StrategyPosition { entries: 2, ..make_position(dec!(0.50), dec!(0.50), 1) };
+        let validate = |positions: &[StrategyPosition]| strategy.validate_entry(
+            &EntryQuote::new(Utc::now(), dec!(0.5125), dec!(0.50)), dec!(10), None, &portfolio, positions, None,
+        );
+        assert!(validate(&[]).allowed);
+        let third_add = validate(&[held]);
+        assert!(!third_add.allowed);
+        assert!(third_add.reason.contains("Slippage too high"));
+    }
+
+    #[test]
+    fn test_order_larger_than_book_allows_is_capped() {
+        use crate::api::{OrderBook, OrderSide};
+
+        let strategy = Strategy::new(StrategyConfig {
+            max_book_fraction: Some(dec!(0.5)),
+            ..StrategyConfig::default()
+        });
+        let book: OrderBook = serde_json::from_value(serde_json::json!({
+            "bids": [{ "price": "0.48", "size": "1000" }],
+            "asks": [
+                { "price": "0.50", "size": "100" },
+                { "price": "0.51", "size": "100" },
+                { "price": "0.60", "size": "1000" }
+            ],
+            "hash": "test",
+            "timestamp": "0"
+        }))
+        .unwrap();
+
+        // Within 3% of the 0.50 best ask: $50 + $51; the 0.60 level is outside the band
+        let band = strategy.slippage_budget(None, 0);
+        let band_depth = book.depth_within_usdc(OrderSide::Buy, band);
+        assert_eq!(band_depth, dec!(101));
+
+        assert_eq!(strategy.cap_to_book_depth(dec!(200), band_depth), dec!(50.5));
+        assert_eq!(strategy.cap_to_book_depth(dec!(20), band_depth), dec!(20));
+    }
+
+    #[test]
+    fn test_order_exceeding_volume_fraction_is_shrunk() {
+        let strategy = Strategy::new(StrategyConfig {
+            max_volume_fraction: Some(dec!(0.01)),
+            ..StrategyConfig::default()
+        });
+
+        // 1% of $20k daily volume
+        assert_eq!(strategy.cap_to_volume(dec!(500), dec!(20000)), dec!(200));
+        assert_eq!(strategy.cap_to_volume(dec!(150), dec!(20000)), dec!(150));
+        assert_eq!(strategy.cap_to_volume(dec!(150), Decimal::ZERO), Decimal::ZERO);
+        assert_eq!(Strategy::default_strategy().cap_to_volume(dec!(500), dec!(20000)), dec!(500));
+    }
+
     #[test]
     fn test_entry_validation_trade_age() {
         let strategy = Strategy::default_strategy();
@@ -698,9 +1442,11 @@ mod tests {
 
         // Trade too old
         let result = strategy.validate_entry(
-            Utc::now() - Duration::minutes(10), // 10 minutes ago
-            dec!(0.50),
-            dec!(0.50),
+            &EntryQuote::new(
+                Utc::now() - Duration::minutes(10), // 10 minutes ago
+                dec!(0.50),
+                dec!(0.50),
+            ),
             dec!(100),
             None,
             &portfolio,
@@ -710,4 +1456,64 @@ mod tests {
         assert!(!result.allowed);
         assert!(result.reason.contains("too old"));
     }
+
+    #[test]
+    fn test_trailing_stop_exits_after_giving_back_gains() {
+        let strategy = Strategy::new(StrategyConfig {
+            take_profit_pct: dec!(0.50),
+            trailing_stop_pct: Some(dec!(0.10)),
+            ..StrategyConfig::default()
+        });
+        let portfolio = make_portfolio();
+
+        // Ran from 0.50 to 0.65, now 0.62: under 5% off the peak
+        let mut position = make_position(dec!(0.50), dec!(0.62), 1);
+        position.peak_price = dec!(0.65);
+        assert!(!strategy.check_exit(&position, &portfolio, true, None).should_exit);
+
+        // 0.56 is still 12% up on entry, far from the fixed stop, but 13.8% off the peak
+        position.current_price = dec!(0.56);
+        assert_eq!(strategy.check_exit(&position, &portfolio, true, None).reason, ExitReason::TrailingStop);
+
+        // A sell trails the low instead
+        let mut short = make_position(dec!(0.50), dec!(0.45), 1);
+        short.side = "SELL".to_string();
+        short.peak_price = dec!(0.40);
+        assert_eq!(strategy.check_exit(&short, &portfolio, true, None).reason, ExitReason::TrailingStop);
+
+        // Off by default
+        assert!(!Strategy::default_strategy().check_exit(&position, &portfolio, true, None).should_exit);
+    }
+
+    #[test]
+    fn test_mock_clock_drives_max_holding_exit() {
+        let clock = MockClock::new(Utc::now());
+        let strategy = Strategy::new(StrategyConfig::default()).with_clock(Arc::new(clock.clone()));
+        let portfolio = make_portfolio();
+        let mut position = make_position(dec!(0.50), dec!(0.50), 0);
+        position.opened_at = clock.now();
+
+        assert!(!strategy.check_exit(&position, &portfolio, true, None).should_exit);
+
+        clock.advance(Duration::hours(strategy.config().max_holding_hours));
+        let signal = strategy.check_exit(&position, &portfolio, true, None);
+        assert_eq!(signal.reason, ExitReason::MaxHoldingPeriod);
+    }
+
+    #[test]
+    fn test_mock_clock_drives_loss_cooloff_expiry() {
+        let clock = MockClock::new(Utc::now());
+        let strategy = Strategy::new(StrategyConfig::default()).with_clock(Arc::new(clock.clone()));
+        let portfolio = PortfolioState { last_loss_at: Some(clock.now()), ..make_portfolio() };
+        let validate = || {
+            strategy.validate_entry(&EntryQuote::new(clock.now(), dec!(0.50), dec!(0.50)), dec!(100), None, &portfolio, &[], None)
+        };
+
+        let result = validate();
+        assert!(!result.allowed);
+        assert!(result.reason.contains("cool-off"));
+
+        clock.advance(Duration::seconds(strategy.config().loss_cooloff_secs));
+        assert!(validate().allowed);
+    }
 }